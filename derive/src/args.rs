@@ -44,6 +44,22 @@ impl FromMeta for DefaultValue {
     }
 }
 
+#[derive(Debug)]
+pub enum GuardFallback {
+    Default,
+    Expr(String),
+}
+
+impl FromMeta for GuardFallback {
+    fn from_word() -> darling::Result<Self> {
+        Ok(GuardFallback::Default)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(GuardFallback::Expr(value.to_string()))
+    }
+}
+
 #[derive(Debug)]
 pub enum Visible {
     None,
@@ -87,8 +103,18 @@ pub struct SimpleObjectField {
     #[darling(default)]
     pub requires: Option<String>,
     #[darling(default)]
+    pub shareable: bool,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default)]
+    pub override_from: Option<String>,
+    #[darling(default)]
     pub guard: Option<Meta>,
     #[darling(default)]
+    pub guard_fallback: Option<GuardFallback>,
+    #[darling(default)]
     pub visible: Option<Visible>,
 }
 
@@ -115,6 +141,18 @@ pub struct SimpleObject {
     #[darling(default)]
     pub extends: bool,
     #[darling(default)]
+    pub shareable: bool,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default)]
+    pub interface_object: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default, multiple, rename = "unresolvable")]
+    pub unresolvable_keys: Vec<String>,
+    #[darling(default)]
+    pub guard: Option<Meta>,
+    #[darling(default)]
     pub visible: Option<Visible>,
 }
 
@@ -126,6 +164,7 @@ pub struct Argument {
     pub default: Option<DefaultValue>,
     pub default_with: Option<LitStr>,
     pub validator: Option<Meta>,
+    pub deprecation: Option<String>,
     pub key: bool, // for entity
     pub visible: Option<Visible>,
 }
@@ -139,7 +178,15 @@ pub struct Object {
     pub rename_args: Option<RenameRule>,
     pub cache_control: CacheControl,
     pub extends: bool,
+    pub shareable: bool,
+    pub inaccessible: bool,
+    pub interface_object: bool,
+    #[darling(multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(multiple, rename = "unresolvable")]
+    pub unresolvable_keys: Vec<String>,
     pub use_type_description: bool,
+    pub guard: Option<Meta>,
     pub visible: Option<Visible>,
 }
 
@@ -171,13 +218,20 @@ impl FromMeta for ComplexityType {
 pub struct ObjectField {
     pub skip: bool,
     pub entity: bool,
+    pub batch: bool,
     pub name: Option<String>,
     pub deprecation: Option<String>,
     pub cache_control: CacheControl,
     pub external: bool,
     pub provides: Option<String>,
     pub requires: Option<String>,
+    pub shareable: bool,
+    pub inaccessible: bool,
+    #[darling(multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    pub override_from: Option<String>,
     pub guard: Option<Meta>,
+    pub guard_fallback: Option<GuardFallback>,
     pub visible: Option<Visible>,
     pub complexity: Option<ComplexityType>,
 }
@@ -260,6 +314,8 @@ pub struct InputObjectField {
     #[darling(default)]
     pub validator: Option<Meta>,
     #[darling(default)]
+    pub deprecation: Option<String>,
+    #[darling(default)]
     pub flatten: bool,
     #[darling(default)]
     pub skip: bool,
@@ -285,6 +341,40 @@ pub struct InputObject {
     pub visible: Option<Visible>,
 }
 
+#[derive(FromVariant)]
+#[darling(attributes(graphql), forward_attrs(doc))]
+pub struct OneofObjectField {
+    pub ident: Ident,
+    pub attrs: Vec<Attribute>,
+    pub fields: Fields<syn::Type>,
+
+    #[darling(default)]
+    pub name: Option<String>,
+    #[darling(default)]
+    pub validator: Option<Meta>,
+    #[darling(default)]
+    pub deprecation: Option<String>,
+    #[darling(default)]
+    pub visible: Option<Visible>,
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(graphql), forward_attrs(doc))]
+pub struct OneofObject {
+    pub ident: Ident,
+    pub attrs: Vec<Attribute>,
+    pub data: Data<OneofObjectField, Ignored>,
+
+    #[darling(default)]
+    pub internal: bool,
+    #[darling(default)]
+    pub name: Option<String>,
+    #[darling(default)]
+    pub rename_fields: Option<RenameRule>,
+    #[darling(default)]
+    pub visible: Option<Visible>,
+}
+
 #[derive(FromMeta)]
 pub struct InterfaceFieldArgument {
     pub name: String,
@@ -297,6 +387,8 @@ pub struct InterfaceFieldArgument {
     #[darling(default)]
     pub default_with: Option<LitStr>,
     #[darling(default)]
+    pub deprecation: Option<String>,
+    #[darling(default)]
     pub visible: Option<Visible>,
 }
 
@@ -320,6 +412,14 @@ pub struct InterfaceField {
     #[darling(default)]
     pub requires: Option<String>,
     #[darling(default)]
+    pub shareable: bool,
+    #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default)]
+    pub override_from: Option<String>,
+    #[darling(default)]
     pub visible: Option<Visible>,
 }
 
@@ -347,9 +447,17 @@ pub struct Interface {
     pub rename_args: Option<RenameRule>,
     #[darling(default, multiple, rename = "field")]
     pub fields: Vec<InterfaceField>,
+    #[darling(default, multiple, rename = "implements")]
+    pub implements: Vec<String>,
     #[darling(default)]
     pub extends: bool,
     #[darling(default)]
+    pub inaccessible: bool,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
+    #[darling(default)]
+    pub guard: Option<Meta>,
+    #[darling(default)]
     pub visible: Option<Visible>,
 }
 
@@ -360,6 +468,7 @@ pub struct Scalar {
     pub name: Option<String>,
     pub use_type_description: bool,
     pub visible: Option<Visible>,
+    pub specified_by_url: Option<String>,
 }
 
 #[derive(FromMeta, Default)]
@@ -380,6 +489,7 @@ pub struct SubscriptionFieldArgument {
     pub default: Option<DefaultValue>,
     pub default_with: Option<LitStr>,
     pub validator: Option<Meta>,
+    pub deprecation: Option<String>,
     pub visible: Option<Visible>,
 }
 
@@ -43,7 +43,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
     let desc = get_rustdoc(&object_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let mut get_fields = Vec::new();
@@ -105,7 +105,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
             None => quote!(::std::option::Option::None),
         };
         let desc = get_rustdoc(&field.attrs)?
-            .map(|s| quote! { ::std::option::Option::Some(#s) })
+            .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
             .unwrap_or_else(|| quote! {::std::option::Option::None});
         let default = generate_default(&field.default, &field.default_with)?;
         let schema_default = default
@@ -148,13 +148,19 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
         });
 
         fields.push(ident);
-        let visible = visible_fn(&field.visible);
+        let visible = visible_fn(&crate_name, &field.visible);
+        let deprecation = field
+            .deprecation
+            .as_ref()
+            .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
+            .unwrap_or_else(|| quote! {::std::option::Option::None});
         schema_fields.push(quote! {
             fields.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
-                name: #name,
+                name: ::std::borrow::ToOwned::to_owned(#name),
                 description: #desc,
                 ty: <#ty as #crate_name::Type>::create_type_info(registry),
                 default_value: #schema_default,
+                deprecation: #deprecation,
                 validator: #validator,
                 visible: #visible,
             });
@@ -169,7 +175,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
         .into());
     }
 
-    let visible = visible_fn(&object_args.visible);
+    let visible = visible_fn(&crate_name, &object_args.visible);
     let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
         impl #crate_name::Type for #ident {
@@ -187,6 +193,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                         fields
                     },
                     visible: #visible,
+                    oneof: false,
                 })
             }
         }
@@ -203,7 +210,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
             }
 
             fn to_value(&self) -> #crate_name::Value {
-                let mut map = ::std::collections::BTreeMap::new();
+                let mut map = #crate_name::indexmap::IndexMap::new();
                 #(#put_fields)*
                 #crate_name::Value::Object(map)
             }
@@ -9,7 +9,10 @@ use syn::{visit_mut, Error, Lifetime, Type};
 
 use crate::args::{self, InterfaceField, InterfaceFieldArgument, RenameRuleExt, RenameTarget};
 use crate::output_type::OutputType;
-use crate::utils::{generate_default, get_crate_name, get_rustdoc, visible_fn, GeneratorResult};
+use crate::utils::{
+    generate_default, generate_guard_check, get_crate_name, get_rustdoc, visible_fn,
+    GeneratorResult,
+};
 
 pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream> {
     let crate_name = get_crate_name(interface_args.internal);
@@ -24,6 +27,9 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         }
     };
     let extends = interface_args.extends;
+    let inaccessible = interface_args.inaccessible;
+    let tags = &interface_args.tags;
+    let implements = &interface_args.implements;
     let mut enum_names = Vec::new();
     let mut enum_items = HashSet::new();
     let mut type_into_impls = Vec::new();
@@ -33,7 +39,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
     let desc = get_rustdoc(&interface_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let mut registry_types = Vec::new();
@@ -107,7 +113,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             });
 
             get_introspection_typename.push(quote! {
-                #ident::#enum_name(obj) => <#p as #crate_name::Type>::type_name()
+                #ident::#enum_name(obj) => <#p as #crate_name::Type>::introspection_type_name(obj)
             });
 
             collect_all_fields.push(quote! {
@@ -140,6 +146,10 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         external,
         provides,
         requires,
+        shareable,
+        inaccessible,
+        tags,
+        override_from,
         visible,
     } in &interface_args.fields
     {
@@ -164,11 +174,21 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         let mut get_params = Vec::new();
         let mut schema_args = Vec::new();
         let requires = match &requires {
-            Some(requires) => quote! { ::std::option::Option::Some(#requires) },
+            Some(requires) => {
+                quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#requires)) }
+            }
             None => quote! { ::std::option::Option::None },
         };
         let provides = match &provides {
-            Some(provides) => quote! { ::std::option::Option::Some(#provides) },
+            Some(provides) => {
+                quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#provides)) }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+        let override_from = match &override_from {
+            Some(from) => {
+                quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#from)) }
+            }
             None => quote! { ::std::option::Option::None },
         };
 
@@ -181,6 +201,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             ty,
             default,
             default_with,
+            deprecation,
             visible,
         } in args
         {
@@ -206,7 +227,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
 
             let desc = desc
                 .as_ref()
-                .map(|s| quote! {::std::option::Option::Some(#s)})
+                .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
                 .unwrap_or_else(|| quote! {::std::option::Option::None});
             let schema_default = default
                 .as_ref()
@@ -218,13 +239,18 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                     }
                 })
                 .unwrap_or_else(|| quote! {::std::option::Option::None});
-            let visible = visible_fn(&visible);
+            let visible = visible_fn(&crate_name, visible);
+            let deprecation = deprecation
+                .as_ref()
+                .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
+                .unwrap_or_else(|| quote! {::std::option::Option::None});
             schema_args.push(quote! {
-                args.insert(#name, #crate_name::registry::MetaInputValue {
-                    name: #name,
+                args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
+                    name: ::std::borrow::ToOwned::to_owned(#name),
                     description: #desc,
                     ty: <#ty as #crate_name::Type>::create_type_info(registry),
                     default_value: #schema_default,
+                    deprecation: #deprecation,
                     validator: ::std::option::Option::None,
                     visible: #visible,
                 });
@@ -239,11 +265,11 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
 
         let desc = desc
             .as_ref()
-            .map(|s| quote! {::std::option::Option::Some(#s)})
+            .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
             .unwrap_or_else(|| quote! {::std::option::Option::None});
         let deprecation = deprecation
             .as_ref()
-            .map(|s| quote! {::std::option::Option::Some(#s)})
+            .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
             .unwrap_or_else(|| quote! {::std::option::Option::None});
 
         let oty = OutputType::parse(&ty)?;
@@ -262,7 +288,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             }
         });
 
-        let visible = visible_fn(&visible);
+        let visible = visible_fn(&crate_name, visible);
         schema_fields.push(quote! {
             fields.insert(::std::string::ToString::to_string(#name), #crate_name::registry::MetaField {
                 name: ::std::string::ToString::to_string(#name),
@@ -278,6 +304,10 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                 external: #external,
                 provides: #provides,
                 requires: #requires,
+                shareable: #shareable,
+                inaccessible: #inaccessible,
+                tags: ::std::vec![#(::std::string::ToString::to_string(#tags)),*],
+                override_from: #override_from,
                 visible: #visible,
                 compute_complexity: ::std::option::Option::None,
             });
@@ -308,7 +338,26 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         }
     };
 
-    let visible = visible_fn(&interface_args.visible);
+    // The fields of an interface are resolved by the object it wraps, so its guard is checked
+    // when the interface itself is resolved.
+    let guard = generate_guard_check(&crate_name, interface_args.guard.as_ref(), None, None)?.map(
+        |guard| {
+            quote! {
+                {
+                    let ctx_field = ctx.with_current_field(field);
+                    let ctx = &ctx_field;
+                    #guard
+                }
+            }
+        },
+    );
+    let field_ident = if guard.is_some() {
+        quote! { field }
+    } else {
+        quote! { _field }
+    };
+
+    let visible = visible_fn(&crate_name, &interface_args.visible);
     let expanded = quote! {
         #(#type_into_impls)*
 
@@ -330,6 +379,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> ::std::string::String {
                 registry.create_type::<Self, _>(|registry| {
                     #(#registry_types)*
+                    #(registry.add_implements(#gql_typename, #implements);)*
 
                     #crate_name::registry::MetaType::Interface {
                         name: ::std::string::ToString::to_string(#gql_typename),
@@ -346,6 +396,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                         },
                         extends: #extends,
                         keys: ::std::option::Option::None,
+                        inaccessible: #inaccessible,
+                        tags: ::std::vec![#(::std::string::ToString::to_string(#tags)),*],
                         visible: #visible,
                     }
                 })
@@ -370,7 +422,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         #[allow(clippy::all, clippy::pedantic)]
         #[#crate_name::async_trait::async_trait]
         impl #generics #crate_name::OutputType for #ident #generics {
-            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::Value> {
+            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, #field_ident: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::Value> {
+                #guard
                 #crate_name::resolver_utils::resolve_container(ctx, self).await
             }
         }
@@ -11,6 +11,7 @@ mod interface;
 mod merged_object;
 mod merged_subscription;
 mod object;
+mod oneof_object;
 mod output_type;
 mod scalar;
 mod simple_object;
@@ -75,6 +76,19 @@ pub fn derive_input_object(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(OneofObject, attributes(graphql))]
+pub fn derive_oneof_object(input: TokenStream) -> TokenStream {
+    let object_args =
+        match args::OneofObject::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
+            Ok(object_args) => object_args,
+            Err(err) => return TokenStream::from(err.write_errors()),
+        };
+    match oneof_object::generate(&object_args) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
 #[proc_macro_derive(Interface, attributes(graphql))]
 pub fn derive_interface(input: TokenStream) -> TokenStream {
     let interface_args =
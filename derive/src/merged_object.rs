@@ -19,7 +19,7 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
     let desc = get_rustdoc(&object_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let s = match &object_args.data {
@@ -55,7 +55,7 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
         obj
     };
 
-    let visible = visible_fn(&object_args.visible);
+    let visible = visible_fn(&crate_name, &object_args.visible);
     let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
         impl #generics #crate_name::Type for #ident #generics #where_clause {
@@ -84,6 +84,10 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
                         cache_control,
                         extends: #extends,
                         keys: ::std::option::Option::None,
+                        shareable: false,
+                        inaccessible: false,
+                        interface_object: false,
+                        tags: ::std::vec::Vec::new(),
                         visible: #visible,
                     }
                 })
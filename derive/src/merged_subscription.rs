@@ -16,7 +16,7 @@ pub fn generate(object_args: &args::MergedSubscription) -> GeneratorResult<Token
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
     let desc = get_rustdoc(&object_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let s = match &object_args.data {
@@ -44,7 +44,7 @@ pub fn generate(object_args: &args::MergedSubscription) -> GeneratorResult<Token
         |obj, ty| quote!(#crate_name::MergedObject::<#ty, #obj>),
     );
 
-    let visible = visible_fn(&object_args.visible);
+    let visible = visible_fn(&crate_name, &object_args.visible);
     let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
         impl #crate_name::Type for #ident {
@@ -70,6 +70,10 @@ pub fn generate(object_args: &args::MergedSubscription) -> GeneratorResult<Token
                         cache_control: ::std::default::Default::default(),
                         extends: false,
                         keys: ::std::option::Option::None,
+                        shareable: false,
+                        inaccessible: false,
+                        interface_object: false,
+                        tags: ::std::vec::Vec::new(),
                         visible: #visible,
                     }
                 })
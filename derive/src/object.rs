@@ -1,14 +1,15 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::ext::IdentExt;
 use syn::{Block, Error, FnArg, ImplItem, ItemImpl, Pat, ReturnType, Type, TypeReference};
 
 use crate::args::{self, ComplexityType, RenameRuleExt, RenameTarget};
 use crate::output_type::OutputType;
 use crate::utils::{
-    generate_default, generate_guards, generate_validator, get_cfg_attrs, get_crate_name,
-    get_param_getter_ident, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
-    parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
+    generate_default, generate_guard_check, generate_validator, get_cfg_attrs, get_crate_name,
+    get_param_getter_ident, get_rustdoc, get_type_path_and_name, get_vec_element_type,
+    parse_complexity_expr, parse_graphql_attrs, remove_graphql_attrs, unresolvable_keys,
+    visible_fn, GeneratorResult,
 };
 
 pub fn generate(
@@ -20,22 +21,28 @@ pub fn generate(
     let generics = &item_impl.generics;
     let where_clause = &item_impl.generics.where_clause;
     let extends = object_args.extends;
+    let shareable = object_args.shareable;
+    let inaccessible = object_args.inaccessible;
+    let interface_object = object_args.interface_object;
+    let tags = &object_args.tags;
+    let keys = unresolvable_keys(&crate_name, &object_args.unresolvable_keys);
     let gql_typename = object_args
         .name
         .clone()
         .unwrap_or_else(|| RenameTarget::Type.rename(self_name.clone()));
 
     let desc = if object_args.use_type_description {
-        quote! { ::std::option::Option::Some(<Self as #crate_name::Description>::description()) }
+        quote! { ::std::option::Option::Some(::std::string::ToString::to_string(<Self as #crate_name::Description>::description())) }
     } else {
         get_rustdoc(&item_impl.attrs)?
-            .map(|s| quote!(::std::option::Option::Some(#s)))
+            .map(|s| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#s))))
             .unwrap_or_else(|| quote!(::std::option::Option::None))
     };
 
     let mut resolvers = Vec::new();
     let mut schema_fields = Vec::new();
     let mut find_entities = Vec::new();
+    let mut batch_find_entities = Vec::new();
     let mut add_keys = Vec::new();
     let mut create_entity_types = Vec::new();
 
@@ -116,13 +123,35 @@ pub fn generate(
                     method.sig.inputs.insert(1, arg);
                 }
 
-                let entity_type = ty.value_type();
+                let value_type = ty.value_type();
+                let entity_type = if method_args.batch {
+                    if let OutputType::Result(_, _) = &ty {
+                        return Err(Error::new_spanned(
+                            &method.sig.output,
+                            "A batched entity resolver must return a `Vec` with one result per representation, e.g. `Vec<Result<Option<T>>>`, instead of a `Result`.",
+                        )
+                        .into());
+                    }
+                    match get_vec_element_type(&value_type) {
+                        Some(entity_type) => entity_type.clone(),
+                        None => {
+                            return Err(Error::new_spanned(
+                                &method.sig.output,
+                                "A batched entity resolver must return a `Vec`.",
+                            )
+                            .into())
+                        }
+                    }
+                } else {
+                    value_type.clone()
+                };
                 let mut key_pat = Vec::new();
                 let mut key_getter = Vec::new();
                 let mut use_keys = Vec::new();
                 let mut keys = Vec::new();
                 let mut keys_str = String::new();
                 let mut requires_getter = Vec::new();
+                let mut batch_items = Vec::new();
                 let all_key = args.iter().all(|(_, _, arg)| !arg.key);
 
                 if args.is_empty() {
@@ -135,6 +164,22 @@ pub fn generate(
 
                 for (ident, ty, args::Argument { name, key, .. }) in &args {
                     let is_key = all_key || *key;
+                    let arg_ty = Type::Path(ty.clone());
+                    let (ty, item_ident) =
+                        if method_args.batch {
+                            match get_vec_element_type(&arg_ty) {
+                                Some(ty) => {
+                                    (ty.clone(), format_ident!("__{}_item", ident.ident.unraw()))
+                                }
+                                None => return Err(Error::new_spanned(
+                                    ty,
+                                    "The arguments of a batched entity resolver must be `Vec`s.",
+                                )
+                                .into()),
+                            }
+                        } else {
+                            (arg_ty.clone(), ident.ident.clone())
+                        };
                     let name = name.clone().unwrap_or_else(|| {
                         object_args
                             .rename_args
@@ -148,7 +193,7 @@ pub fn generate(
                         keys_str.push_str(&name);
 
                         key_pat.push(quote! {
-                            ::std::option::Option::Some(#ident)
+                            ::std::option::Option::Some(#item_ident)
                         });
                         key_getter.push(quote! {
                             params.get(#name).and_then(|value| {
@@ -160,12 +205,25 @@ pub fn generate(
                         use_keys.push(ident);
                     } else {
                         // requires
-                        requires_getter.push(quote! {
-                            let #ident: #ty = #crate_name::InputType::parse(params.get(#name).cloned()).
-                                map_err(|err| err.into_server_error().at(ctx.item.pos))?;
-                        });
+                        if method_args.batch {
+                            requires_getter.push(quote! {
+                                let #item_ident: #ty = match #crate_name::InputType::parse(params.get(#name).cloned()) {
+                                    ::std::result::Result::Ok(value) => value,
+                                    ::std::result::Result::Err(err) => {
+                                        results[idx] = ::std::option::Option::Some(::std::result::Result::Err(err.into_server_error().at(ctx.item.pos)));
+                                        continue;
+                                    }
+                                };
+                            });
+                        } else {
+                            requires_getter.push(quote! {
+                                let #ident: #ty = #crate_name::InputType::parse(params.get(#name).cloned()).
+                                    map_err(|err| err.into_server_error().at(ctx.item.pos))?;
+                            });
+                        }
                         use_keys.push(ident);
                     }
+                    batch_items.push(item_ident);
                 }
 
                 add_keys.push(quote! { registry.add_keys(&<#entity_type as #crate_name::Type>::type_name(), #keys_str); });
@@ -174,7 +232,8 @@ pub fn generate(
                 );
 
                 let field_ident = &method.sig.ident;
-                if let OutputType::Value(inner_ty) = &ty {
+                // A batched entity resolver can't fail, so it is called as it is.
+                if let (OutputType::Value(inner_ty), false) = (&ty, method_args.batch) {
                     let block = &method.block;
                     let new_block = quote!({
                         {
@@ -189,19 +248,67 @@ pub fn generate(
                 }
                 let do_find = quote! { self.#field_ident(ctx, #(#use_keys),*).await.map_err(|err| err.into_server_error().at(ctx.item.pos))? };
 
-                find_entities.push((
-                    args.len(),
-                    quote! {
+                if method_args.batch {
+                    batch_find_entities.push(quote! {
                         #(#cfg_attrs)*
-                        if typename == &<#entity_type as #crate_name::Type>::type_name() {
-                            if let (#(#key_pat),*) = (#(#key_getter),*) {
-                                #(#requires_getter)*
-                                let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-                                return #crate_name::OutputType::resolve(&#do_find, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
+                        if typename == <#entity_type as #crate_name::Type>::type_name() {
+                            let mut positions = ::std::vec::Vec::new();
+                            #(let mut #use_keys = ::std::vec::Vec::new();)*
+                            for (idx, params) in representations.iter().enumerate() {
+                                if results[idx].is_some() {
+                                    continue;
+                                }
+                                let params = match params {
+                                    #crate_name::Value::Object(params) => params,
+                                    _ => continue,
+                                };
+                                if let (#(#key_pat),*) = (#(#key_getter),*) {
+                                    #(#requires_getter)*
+                                    positions.push(idx);
+                                    #(#use_keys.push(#batch_items);)*
+                                }
+                            }
+                            if !positions.is_empty() {
+                                let entities = self.#field_ident(ctx, #(#use_keys),*).await;
+                                if entities.len() != positions.len() {
+                                    let message = ::std::format!(
+                                        "The batched entity resolver returned {} entities, but {} were expected.",
+                                        entities.len(),
+                                        positions.len(),
+                                    );
+                                    for idx in positions {
+                                        results[idx] = ::std::option::Option::Some(::std::result::Result::Err(#crate_name::ServerError::new(::std::clone::Clone::clone(&message)).at(ctx.item.pos)));
+                                    }
+                                } else {
+                                    let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+                                    for (idx, entity) in positions.into_iter().zip(entities.iter()) {
+                                        results[idx] = ::std::option::Option::Some(match #crate_name::OutputType::resolve(entity, &ctx_obj, ctx.item).await {
+                                            ::std::result::Result::Ok(#crate_name::Value::Null) => {
+                                                ::std::result::Result::Err(#crate_name::ServerError::new("Entity not found.").at(ctx.item.pos))
+                                            }
+                                            res => res,
+                                        });
+                                    }
+                                }
                             }
+                            found = true;
                         }
-                    },
-                ));
+                    });
+                } else {
+                    find_entities.push((
+                        args.len(),
+                        quote! {
+                            #(#cfg_attrs)*
+                            if typename == &<#entity_type as #crate_name::Type>::type_name() {
+                                if let (#(#key_pat),*) = (#(#key_getter),*) {
+                                    #(#requires_getter)*
+                                    let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+                                    return #crate_name::OutputType::resolve(&#do_find, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
+                                }
+                            }
+                        },
+                    ));
+                }
             } else if !method_args.skip {
                 if method.sig.asyncness.is_none() {
                     return Err(Error::new_spanned(&method, "Must be asynchronous").into());
@@ -213,20 +320,33 @@ pub fn generate(
                         .rename(method.sig.ident.unraw().to_string(), RenameTarget::Field)
                 });
                 let field_desc = get_rustdoc(&method.attrs)?
-                    .map(|s| quote! { ::std::option::Option::Some(#s) })
+                    .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
                     .unwrap_or_else(|| quote! {::std::option::Option::None});
                 let field_deprecation = method_args
                     .deprecation
                     .as_ref()
-                    .map(|s| quote! { ::std::option::Option::Some(#s) })
+                    .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
                     .unwrap_or_else(|| quote! {::std::option::Option::None});
                 let external = method_args.external;
                 let requires = match &method_args.requires {
-                    Some(requires) => quote! { ::std::option::Option::Some(#requires) },
+                    Some(requires) => {
+                        quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#requires)) }
+                    }
                     None => quote! { ::std::option::Option::None },
                 };
                 let provides = match &method_args.provides {
-                    Some(provides) => quote! { ::std::option::Option::Some(#provides) },
+                    Some(provides) => {
+                        quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#provides)) }
+                    }
+                    None => quote! { ::std::option::Option::None },
+                };
+                let shareable = method_args.shareable;
+                let inaccessible = method_args.inaccessible;
+                let tags = &method_args.tags;
+                let override_from = match &method_args.override_from {
+                    Some(from) => {
+                        quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#from)) }
+                    }
                     None => quote! { ::std::option::Option::None },
                 };
                 let ty = match &method.sig.output {
@@ -319,6 +439,7 @@ pub fn generate(
                         default,
                         default_with,
                         validator,
+                        deprecation,
                         visible,
                         ..
                     },
@@ -331,7 +452,7 @@ pub fn generate(
                     });
                     let desc = desc
                         .as_ref()
-                        .map(|s| quote! {::std::option::Option::Some(#s)})
+                        .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
                         .unwrap_or_else(|| quote! {::std::option::Option::None});
                     let default = generate_default(&default, &default_with)?;
                     let schema_default = default
@@ -353,13 +474,18 @@ pub fn generate(
                         None => quote!(::std::option::Option::None),
                     };
 
-                    let visible = visible_fn(&visible);
+                    let visible = visible_fn(&crate_name, visible);
+                    let deprecation = deprecation
+                        .as_ref()
+                        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
+                        .unwrap_or_else(|| quote! {::std::option::Option::None});
                     schema_args.push(quote! {
-                        args.insert(#name, #crate_name::registry::MetaInputValue {
-                            name: #name,
+                        args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
+                            name: ::std::borrow::ToOwned::to_owned(#name),
                             description: #desc,
                             ty: <#ty as #crate_name::Type>::create_type_info(registry),
                             default_value: #schema_default,
+                            deprecation: #deprecation,
                             validator: #validator,
                             visible: #visible,
                         });
@@ -387,7 +513,7 @@ pub fn generate(
                 }
 
                 let schema_ty = ty.value_type();
-                let visible = visible_fn(&method_args.visible);
+                let visible = visible_fn(&crate_name, &method_args.visible);
 
                 let complexity = if let Some(complexity) = &method_args.complexity {
                     match complexity {
@@ -456,6 +582,10 @@ pub fn generate(
                         external: #external,
                         provides: #provides,
                         requires: #requires,
+                        shareable: #shareable,
+                        inaccessible: #inaccessible,
+                        tags: ::std::vec![#(::std::string::ToString::to_string(#tags)),*],
+                        override_from: #override_from,
                         visible: #visible,
                         compute_complexity: #complexity,
                     });
@@ -483,17 +613,25 @@ pub fn generate(
                     }
                 };
 
-                let guard = match &method_args.guard {
-                    Some(meta_list) => generate_guards(&crate_name, meta_list)?,
-                    None => None,
-                };
-
-                let guard = guard.map(|guard| {
-                    quote! {
-                        #guard.check(ctx).await
-                            .map_err(|err| err.into_server_error().at(ctx.item.pos))?;
-                    }
-                });
+                if method_args.guard_fallback.is_some()
+                    && object_args.guard.is_none()
+                    && method_args.guard.is_none()
+                {
+                    return Err(Error::new_spanned(
+                        &method.sig.ident,
+                        "The guard fallback requires a guard on the field or on the object.",
+                    )
+                    .into());
+                }
+                let guard = generate_guard_check(
+                    &crate_name,
+                    object_args.guard.as_ref(),
+                    method_args.guard.as_ref(),
+                    method_args
+                        .guard_fallback
+                        .as_ref()
+                        .map(|fallback| (fallback, &schema_ty)),
+                )?;
 
                 resolvers.push(quote! {
                     #(#cfg_attrs)*
@@ -524,6 +662,32 @@ pub fn generate(
 
     find_entities.sort_by(|(a, _), (b, _)| b.cmp(a));
     let find_entities_iter = find_entities.iter().map(|(_, code)| code);
+    let batch_find_entities = if batch_find_entities.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            async fn find_entities(&self, ctx: &#crate_name::Context<'_>, typename: &::std::primitive::str, representations: &[&#crate_name::Value]) -> ::std::option::Option<::std::vec::Vec<#crate_name::ServerResult<#crate_name::Value>>> {
+                let mut results: ::std::vec::Vec<::std::option::Option<#crate_name::ServerResult<#crate_name::Value>>> = representations.iter().map(|_| ::std::option::Option::None).collect();
+                let mut found = false;
+                #(#batch_find_entities)*
+                if !found {
+                    return ::std::option::Option::None;
+                }
+                let mut values = ::std::vec::Vec::with_capacity(results.len());
+                for (params, value) in representations.iter().zip(results) {
+                    values.push(match value {
+                        ::std::option::Option::Some(value) => value,
+                        ::std::option::Option::None => {
+                            #crate_name::ContainerType::find_entity(self, ctx, params).await.and_then(|value| {
+                                value.ok_or_else(|| #crate_name::ServerError::new("Entity not found.").at(ctx.item.pos))
+                            })
+                        }
+                    });
+                }
+                ::std::option::Option::Some(values)
+            }
+        })
+    };
 
     if resolvers.is_empty() && create_entity_types.is_empty() {
         return Err(Error::new_spanned(
@@ -533,7 +697,7 @@ pub fn generate(
         .into());
     }
 
-    let visible = visible_fn(&object_args.visible);
+    let visible = visible_fn(&crate_name, &object_args.visible);
     let expanded = quote! {
         #item_impl
 
@@ -554,7 +718,11 @@ pub fn generate(
                     },
                     cache_control: #cache_control,
                     extends: #extends,
-                    keys: ::std::option::Option::None,
+                    keys: #keys,
+                    shareable: #shareable,
+                    inaccessible: #inaccessible,
+                    interface_object: #interface_object,
+                    tags: ::std::vec![#(::std::string::ToString::to_string(#tags)),*],
                     visible: #visible,
                 });
                 #(#create_entity_types)*
@@ -588,6 +756,8 @@ pub fn generate(
                 #(#find_entities_iter)*
                 ::std::result::Result::Ok(::std::option::Option::None)
             }
+
+            #batch_find_entities
         }
 
         #[allow(clippy::all, clippy::pedantic)]
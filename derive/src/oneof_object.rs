@@ -0,0 +1,177 @@
+use darling::ast::{Data, Style};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::Error;
+
+use crate::args::{self, RenameRuleExt, RenameTarget};
+use crate::utils::{generate_validator, get_crate_name, get_rustdoc, visible_fn, GeneratorResult};
+
+pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream> {
+    let crate_name = get_crate_name(object_args.internal);
+    let ident = &object_args.ident;
+    let s = match &object_args.data {
+        Data::Enum(s) => s,
+        _ => {
+            return Err(
+                Error::new_spanned(ident, "OneofObject can only be applied to an enum.").into(),
+            )
+        }
+    };
+
+    let gql_typename = object_args
+        .name
+        .clone()
+        .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
+
+    let desc = get_rustdoc(&object_args.attrs)?
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
+        .unwrap_or_else(|| quote! {::std::option::Option::None});
+
+    let mut schema_fields = Vec::new();
+    let mut parse_fields = Vec::new();
+    let mut put_fields = Vec::new();
+
+    for variant in s {
+        let enum_name = &variant.ident;
+        let ty = match variant.fields.style {
+            Style::Tuple if variant.fields.fields.len() == 1 => &variant.fields.fields[0],
+            Style::Tuple => {
+                return Err(Error::new_spanned(
+                    enum_name,
+                    "Only single value variants are supported",
+                )
+                .into())
+            }
+            Style::Unit => {
+                return Err(
+                    Error::new_spanned(enum_name, "Empty variants are not supported").into(),
+                )
+            }
+            Style::Struct => {
+                return Err(Error::new_spanned(
+                    enum_name,
+                    "Variants with named fields are not supported",
+                )
+                .into())
+            }
+        };
+
+        let name = variant.name.clone().unwrap_or_else(|| {
+            object_args
+                .rename_fields
+                .rename(enum_name.unraw().to_string(), RenameTarget::Field)
+        });
+        let validator = match &variant.validator {
+            Some(meta) => {
+                let stream = generate_validator(&crate_name, meta)?;
+                quote!(::std::option::Option::Some(#stream))
+            }
+            None => quote!(::std::option::Option::None),
+        };
+        let desc = get_rustdoc(&variant.attrs)?
+            .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
+            .unwrap_or_else(|| quote! {::std::option::Option::None});
+        let visible = visible_fn(&crate_name, &variant.visible);
+        let deprecation = variant
+            .deprecation
+            .as_ref()
+            .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
+            .unwrap_or_else(|| quote! {::std::option::Option::None});
+
+        // The fields of a oneof input object are nullable, since only one of them is set.
+        schema_fields.push(quote! {
+            fields.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
+                name: ::std::borrow::ToOwned::to_owned(#name),
+                description: #desc,
+                ty: {
+                    <#ty as #crate_name::Type>::create_type_info(registry);
+                    ::std::string::ToString::to_string(&<#ty as #crate_name::Type>::type_name())
+                },
+                default_value: ::std::option::Option::None,
+                deprecation: #deprecation,
+                validator: #validator,
+                visible: #visible,
+            });
+        });
+
+        parse_fields.push(quote! {
+            if name.as_str() == #name {
+                return #crate_name::InputType::parse(::std::option::Option::Some(value))
+                    .map(Self::#enum_name)
+                    .map_err(#crate_name::InputValueError::propagate);
+            }
+        });
+
+        put_fields.push(quote! {
+            Self::#enum_name(value) => {
+                map.insert(#crate_name::Name::new(#name), #crate_name::InputType::to_value(value));
+            }
+        });
+    }
+
+    if schema_fields.is_empty() {
+        return Err(Error::new_spanned(
+            ident,
+            "A GraphQL Input Object type must define one or more input fields.",
+        )
+        .into());
+    }
+
+    let visible = visible_fn(&crate_name, &object_args.visible);
+    let expanded = quote! {
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #crate_name::Type for #ident {
+            fn type_name() -> ::std::borrow::Cow<'static, ::std::primitive::str> {
+                ::std::borrow::Cow::Borrowed(#gql_typename)
+            }
+
+            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> ::std::string::String {
+                registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::InputObject {
+                    name: ::std::borrow::ToOwned::to_owned(#gql_typename),
+                    description: #desc,
+                    input_fields: {
+                        let mut fields = #crate_name::indexmap::IndexMap::new();
+                        #(#schema_fields)*
+                        fields
+                    },
+                    visible: #visible,
+                    oneof: true,
+                })
+            }
+        }
+
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #crate_name::InputType for #ident {
+            fn parse(value: ::std::option::Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> {
+                if let ::std::option::Option::Some(#crate_name::Value::Object(obj)) = value {
+                    if obj.len() != 1 {
+                        return ::std::result::Result::Err(#crate_name::InputValueError::custom(
+                            "Oneof input objects must have exactly one field.",
+                        ));
+                    }
+                    let (name, value) = ::std::option::Option::unwrap(::std::iter::Iterator::next(&mut ::std::iter::IntoIterator::into_iter(obj)));
+                    #(#parse_fields)*
+                    ::std::result::Result::Err(#crate_name::InputValueError::custom(
+                        ::std::format!(r#"Unknown field "{}"."#, name),
+                    ))
+                } else {
+                    ::std::result::Result::Err(#crate_name::InputValueError::expected_type(value.unwrap_or_default()))
+                }
+            }
+
+            fn to_value(&self) -> #crate_name::Value {
+                let mut map = #crate_name::indexmap::IndexMap::new();
+                match self {
+                    #(#put_fields)*
+                }
+                #crate_name::Value::Object(map)
+            }
+        }
+
+        impl #crate_name::InputObjectType for #ident {}
+
+        impl #crate_name::OneofObjectType for #ident {}
+    };
+    Ok(expanded.into())
+}
@@ -19,17 +19,22 @@ pub fn generate(
         .unwrap_or_else(|| RenameTarget::Type.rename(self_name.clone()));
 
     let desc = if scalar_args.use_type_description {
-        quote! { ::std::option::Option::Some(<Self as #crate_name::Description>::description()) }
+        quote! { ::std::option::Option::Some(::std::string::ToString::to_string(<Self as #crate_name::Description>::description())) }
     } else {
         get_rustdoc(&item_impl.attrs)?
-            .map(|s| quote!(::std::option::Option::Some(#s)))
+            .map(|s| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#s))))
             .unwrap_or_else(|| quote!(::std::option::Option::None))
     };
 
     let self_ty = &item_impl.self_ty;
     let generic = &item_impl.generics;
     let where_clause = &item_impl.generics.where_clause;
-    let visible = visible_fn(&scalar_args.visible);
+    let visible = visible_fn(&crate_name, &scalar_args.visible);
+    let specified_by_url = scalar_args
+        .specified_by_url
+        .as_ref()
+        .map(|url| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#url))))
+        .unwrap_or_else(|| quote!(::std::option::Option::None));
     let expanded = quote! {
         #item_impl
 
@@ -45,6 +50,7 @@ pub fn generate(
                     description: #desc,
                     is_valid: |value| <#self_ty as #crate_name::ScalarType>::is_valid(value),
                     visible: #visible,
+                    specified_by_url: #specified_by_url,
                 })
             }
         }
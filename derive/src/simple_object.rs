@@ -5,7 +5,10 @@ use syn::ext::IdentExt;
 use syn::Error;
 
 use crate::args::{self, RenameRuleExt, RenameTarget};
-use crate::utils::{generate_guards, get_crate_name, get_rustdoc, visible_fn, GeneratorResult};
+use crate::utils::{
+    generate_guard_check, get_crate_name, get_rustdoc, unresolvable_keys, visible_fn,
+    GeneratorResult,
+};
 
 pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream> {
     let crate_name = get_crate_name(object_args.internal);
@@ -13,13 +16,18 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
     let generics = &object_args.generics;
     let where_clause = &generics.where_clause;
     let extends = object_args.extends;
+    let shareable = object_args.shareable;
+    let inaccessible = object_args.inaccessible;
+    let interface_object = object_args.interface_object;
+    let tags = &object_args.tags;
+    let keys = unresolvable_keys(&crate_name, &object_args.unresolvable_keys);
     let gql_typename = object_args
         .name
         .clone()
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
     let desc = get_rustdoc(&object_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let s = match &object_args.data {
@@ -51,20 +59,33 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 .rename(ident.unraw().to_string(), RenameTarget::Field)
         });
         let field_desc = get_rustdoc(&field.attrs)?
-            .map(|s| quote! {::std::option::Option::Some(#s)})
+            .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
             .unwrap_or_else(|| quote! {::std::option::Option::None});
         let field_deprecation = field
             .deprecation
             .as_ref()
-            .map(|s| quote! {::std::option::Option::Some(#s)})
+            .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
             .unwrap_or_else(|| quote! {::std::option::Option::None});
         let external = field.external;
         let requires = match &field.requires {
-            Some(requires) => quote! { ::std::option::Option::Some(#requires) },
+            Some(requires) => {
+                quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#requires)) }
+            }
             None => quote! { ::std::option::Option::None },
         };
         let provides = match &field.provides {
-            Some(provides) => quote! { ::std::option::Option::Some(#provides) },
+            Some(provides) => {
+                quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#provides)) }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+        let shareable = field.shareable;
+        let inaccessible = field.inaccessible;
+        let tags = &field.tags;
+        let override_from = match &field.override_from {
+            Some(from) => {
+                quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#from)) }
+            }
             None => quote! { ::std::option::Option::None },
         };
         let vis = &field.vis;
@@ -81,7 +102,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             }
         };
 
-        let visible = visible_fn(&field.visible);
+        let visible = visible_fn(&crate_name, &field.visible);
 
         schema_fields.push(quote! {
             fields.insert(::std::borrow::ToOwned::to_owned(#field_name), #crate_name::registry::MetaField {
@@ -94,16 +115,28 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 external: #external,
                 provides: #provides,
                 requires: #requires,
+                shareable: #shareable,
+                inaccessible: #inaccessible,
+                tags: ::std::vec![#(::std::string::ToString::to_string(#tags)),*],
+                override_from: #override_from,
                 visible: #visible,
                 compute_complexity: ::std::option::Option::None,
             });
         });
 
-        let guard = match &field.guard {
-            Some(meta) => generate_guards(&crate_name, &meta)?,
-            None => None,
-        };
-        let guard = guard.map(|guard| quote! { #guard.check(ctx).await.map_err(|err| err.into_server_error().at(ctx.item.pos))?; });
+        if field.guard_fallback.is_some() && object_args.guard.is_none() && field.guard.is_none() {
+            return Err(Error::new_spanned(
+                ident,
+                "The guard fallback requires a guard on the field or on the object.",
+            )
+            .into());
+        }
+        let guard = generate_guard_check(
+            &crate_name,
+            object_args.guard.as_ref(),
+            field.guard.as_ref(),
+            field.guard_fallback.as_ref().map(|fallback| (fallback, ty)),
+        )?;
 
         getters.push(if !field.owned {
             quote! {
@@ -152,7 +185,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         }
     };
 
-    let visible = visible_fn(&object_args.visible);
+    let visible = visible_fn(&crate_name, &object_args.visible);
 
     let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
@@ -177,7 +210,11 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                     },
                     cache_control: #cache_control,
                     extends: #extends,
-                    keys: ::std::option::Option::None,
+                    keys: #keys,
+                    shareable: #shareable,
+                    inaccessible: #inaccessible,
+                    interface_object: #interface_object,
+                    tags: ::std::vec![#(::std::string::ToString::to_string(#tags)),*],
                     visible: #visible,
                 })
             }
@@ -29,10 +29,10 @@ pub fn generate(
         .unwrap_or_else(|| RenameTarget::Type.rename(self_name.clone()));
 
     let desc = if subscription_args.use_type_description {
-        quote! { ::std::option::Option::Some(<Self as #crate_name::Description>::description()) }
+        quote! { ::std::option::Option::Some(::std::string::ToString::to_string(<Self as #crate_name::Description>::description())) }
     } else {
         get_rustdoc(&item_impl.attrs)?
-            .map(|s| quote!(::std::option::Option::Some(#s)))
+            .map(|s| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#s))))
             .unwrap_or_else(|| quote!(::std::option::Option::None))
     };
 
@@ -54,12 +54,12 @@ pub fn generate(
                     .rename(method.sig.ident.unraw().to_string(), RenameTarget::Field)
             });
             let field_desc = get_rustdoc(&method.attrs)?
-                .map(|s| quote! {::std::option::Option::Some(#s)})
+                .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
                 .unwrap_or_else(|| quote! {::std::option::Option::None});
             let field_deprecation = field
                 .deprecation
                 .as_ref()
-                .map(|s| quote! {::std::option::Option::Some(#s)})
+                .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
                 .unwrap_or_else(|| quote! {::std::option::Option::None});
             let cfg_attrs = get_cfg_attrs(&method.attrs);
 
@@ -150,6 +150,7 @@ pub fn generate(
                     default,
                     default_with,
                     validator,
+                    deprecation,
                     visible: arg_visible,
                 },
             ) in &args
@@ -161,7 +162,7 @@ pub fn generate(
                 });
                 let desc = desc
                     .as_ref()
-                    .map(|s| quote! {::std::option::Option::Some(#s)})
+                    .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
                     .unwrap_or_else(|| quote! {::std::option::Option::None});
                 let default = generate_default(&default, &default_with)?;
 
@@ -184,13 +185,18 @@ pub fn generate(
                     })
                     .unwrap_or_else(|| quote! {::std::option::Option::None});
 
-                let visible = visible_fn(&arg_visible);
+                let visible = visible_fn(&crate_name, arg_visible);
+                let deprecation = deprecation
+                    .as_ref()
+                    .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
+                    .unwrap_or_else(|| quote! {::std::option::Option::None});
                 schema_args.push(quote! {
-                    args.insert(#name, #crate_name::registry::MetaInputValue {
-                        name: #name,
+                    args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
+                        name: ::std::borrow::ToOwned::to_owned(#name),
                         description: #desc,
                         ty: <#ty as #crate_name::Type>::create_type_info(registry),
                         default_value: #schema_default,
+                        deprecation: #deprecation,
                         validator: #validator,
                         visible: #visible,
                     });
@@ -238,7 +244,7 @@ pub fn generate(
                         .expect("invalid result type");
             }
 
-            let visible = visible_fn(&field.visible);
+            let visible = visible_fn(&crate_name, &field.visible);
             let complexity = if let Some(complexity) = &field.complexity {
                 match complexity {
                     ComplexityType::Const(n) => {
@@ -306,6 +312,10 @@ pub fn generate(
                     external: false,
                     requires: ::std::option::Option::None,
                     provides: ::std::option::Option::None,
+                    shareable: false,
+                    inaccessible: false,
+                    tags: ::std::vec::Vec::new(),
+                    override_from: ::std::option::Option::None,
                     visible: #visible,
                     compute_complexity: #complexity,
                 });
@@ -445,6 +455,10 @@ pub fn generate(
                     cache_control: ::std::default::Default::default(),
                     extends: false,
                     keys: ::std::option::Option::None,
+                    shareable: false,
+                    inaccessible: false,
+                    interface_object: false,
+                    tags: ::std::vec::Vec::new(),
                     visible: ::std::option::Option::None,
                 })
             }
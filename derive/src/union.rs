@@ -28,7 +28,7 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
     let desc = get_rustdoc(&union_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let mut registry_types = Vec::new();
@@ -148,7 +148,7 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
         .into());
     }
 
-    let visible = visible_fn(&union_args.visible);
+    let visible = visible_fn(&crate_name, &union_args.visible);
     let expanded = quote! {
         #(#type_into_impls)*
 
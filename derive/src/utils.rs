@@ -6,13 +6,13 @@ use proc_macro_crate::crate_name;
 use quote::quote;
 use syn::visit::Visit;
 use syn::{
-    Attribute, Error, Expr, ExprPath, Ident, Lit, LitStr, Meta, NestedMeta, Type, TypeGroup,
-    TypePath,
+    Attribute, Error, Expr, ExprPath, GenericArgument, Ident, Lit, LitStr, Meta, NestedMeta,
+    PathArguments, Type, TypeGroup, TypePath,
 };
 use thiserror::Error;
 
 use crate::args;
-use crate::args::Visible;
+use crate::args::{GuardFallback, Visible};
 
 #[derive(Error, Debug)]
 pub enum GeneratorError {
@@ -210,7 +210,7 @@ pub fn generate_guards(
                                 guards = guard;
                             } else {
                                 guards = Some(
-                                    quote! { #crate_name::guard::GuardExt::and(#guard, #guards) },
+                                    quote! { #crate_name::guard::GuardExt::and(#guards, #guard) },
                                 );
                             }
                         }
@@ -233,7 +233,7 @@ pub fn generate_guards(
                                 guards = guard;
                             } else {
                                 guards = Some(
-                                    quote! { #crate_name::guard::GuardExt::or(#guard, #guards) },
+                                    quote! { #crate_name::guard::GuardExt::or(#guards, #guard) },
                                 );
                             }
                         }
@@ -277,6 +277,61 @@ pub fn generate_guards(
     }
 }
 
+/// Generates the check of the guard of a field, which is the guard of its type followed by its own
+/// guard.
+///
+/// If the check fails, the field is resolved to its fallback value of type `ty` if it has one,
+/// otherwise to an error.
+pub fn generate_guard_check(
+    crate_name: &TokenStream,
+    type_guard: Option<&Meta>,
+    field_guard: Option<&Meta>,
+    fallback: Option<(&GuardFallback, &Type)>,
+) -> GeneratorResult<Option<TokenStream>> {
+    let type_guard = match type_guard {
+        Some(meta) => generate_guards(crate_name, meta)?,
+        None => None,
+    };
+    let field_guard = match field_guard {
+        Some(meta) => generate_guards(crate_name, meta)?,
+        None => None,
+    };
+    let guard = match (type_guard, field_guard) {
+        (Some(type_guard), Some(field_guard)) => {
+            Some(quote! { #crate_name::guard::GuardExt::and(#type_guard, #field_guard) })
+        }
+        (type_guard, field_guard) => type_guard.or(field_guard),
+    };
+    let guard = match guard {
+        Some(guard) => guard,
+        None => return Ok(None),
+    };
+
+    Ok(Some(match fallback {
+        Some((fallback, ty)) => {
+            let value = match fallback {
+                GuardFallback::Default => quote! { ::std::default::Default::default() },
+                GuardFallback::Expr(expr) => {
+                    let expr = syn::parse_str::<Expr>(expr)?;
+                    quote! { #expr }
+                }
+            };
+            quote! {
+                let guard_result = #guard.check(ctx).await;
+                if guard_result.is_err() {
+                    let res: #ty = #value;
+                    let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+                    return #crate_name::OutputType::resolve(&res, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
+                }
+            }
+        }
+        None => quote! {
+            #guard.check(ctx).await
+                .map_err(|err| err.into_server_error().at(ctx.item.pos))?;
+        },
+    }))
+}
+
 pub fn get_rustdoc(attrs: &[Attribute]) -> GeneratorResult<Option<String>> {
     let mut full_docs = String::new();
     for attr in attrs {
@@ -395,13 +450,48 @@ pub fn get_type_path_and_name(ty: &Type) -> GeneratorResult<(&TypePath, String)>
     }
 }
 
-pub fn visible_fn(visible: &Option<Visible>) -> TokenStream {
+pub fn get_vec_element_type(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            if segment.ident != "Vec" {
+                return None;
+            }
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) => match args.args.first()? {
+                    GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        Type::Group(TypeGroup { elem, .. }) => get_vec_element_type(elem),
+        _ => None,
+    }
+}
+
+pub fn visible_fn(crate_name: &TokenStream, visible: &Option<Visible>) -> TokenStream {
     match visible {
         None | Some(Visible::None) => quote! { ::std::option::Option::None },
-        Some(Visible::HiddenAlways) => quote! { ::std::option::Option::Some(|_| false) },
+        Some(Visible::HiddenAlways) => quote! {
+            ::std::option::Option::Some(::std::sync::Arc::new(|_: &#crate_name::Context<'_>| false))
+        },
         Some(Visible::FnName(name)) => {
             let ident = Ident::new(name, Span::call_site());
-            quote! { ::std::option::Option::Some(#ident) }
+            quote! { ::std::option::Option::Some(::std::sync::Arc::new(#ident)) }
+        }
+    }
+}
+
+pub fn unresolvable_keys(crate_name: &TokenStream, keys: &[String]) -> TokenStream {
+    if keys.is_empty() {
+        quote! { ::std::option::Option::None }
+    } else {
+        quote! {
+            ::std::option::Option::Some(::std::vec![#(#crate_name::registry::MetaKey {
+                fields: ::std::borrow::ToOwned::to_owned(#keys),
+                resolvable: false,
+            }),*])
         }
     }
 }
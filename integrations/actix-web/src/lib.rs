@@ -12,11 +12,12 @@ use std::pin::Pin;
 use actix_web::client::PayloadError;
 use actix_web::dev::{Payload, PayloadStream};
 use actix_web::http::{Method, StatusCode};
-use actix_web::{http, Error, FromRequest, HttpRequest, HttpResponse, Responder, Result};
+use actix_web::{http, web, Error, FromRequest, HttpRequest, HttpResponse, Responder, Result};
 use futures_util::future::{self, FutureExt, Ready};
 use futures_util::{StreamExt, TryStreamExt};
 
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{CsrfOptions, MultipartOptions, ResponseFormat};
+use async_graphql::parser::types::OperationType;
 use async_graphql::ParseRequestError;
 
 /// Extractor for GraphQL request.
@@ -53,7 +54,9 @@ impl FromRequest for Request {
 
 /// Extractor for GraphQL batch request.
 ///
-/// `async_graphql::http::MultipartOptions` allows to configure extraction process.
+/// `async_graphql::http::MultipartOptions` allows to configure extraction process. If the app data
+/// contains `async_graphql::http::CsrfOptions`, the requests are checked with them to prevent
+/// Cross-Site Request Forgery.
 pub struct BatchRequest(pub async_graphql::BatchRequest);
 
 impl BatchRequest {
@@ -72,9 +75,29 @@ impl FromRequest for BatchRequest {
     fn from_request(req: &HttpRequest, payload: &mut Payload<PayloadStream>) -> Self::Future {
         let config = req.app_data::<Self::Config>().cloned().unwrap_or_default();
 
+        if let Some(csrf) = req.app_data::<CsrfOptions>() {
+            if let Err(err) = async_graphql::http::check_csrf(
+                csrf,
+                req.headers()
+                    .get(http::header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok()),
+                |name| {
+                    req.headers()
+                        .get(name)
+                        .and_then(|value| value.to_str().ok())
+                },
+            ) {
+                return Box::pin(async move { Err(actix_web::error::ErrorBadRequest(err)) });
+            }
+        }
+
         if req.method() == Method::GET {
-            let res = serde_urlencoded::from_str(req.query_string());
-            Box::pin(async move { Ok(Self(async_graphql::BatchRequest::Single(res?))) })
+            let res = serde_urlencoded::from_str::<async_graphql::Request>(req.query_string());
+            Box::pin(async move {
+                Ok(Self(async_graphql::BatchRequest::Single(
+                    res?.allowed_operation_types(vec![OperationType::Query]),
+                )))
+            })
         } else if req.method() == Method::POST {
             let content_type = req
                 .headers()
@@ -125,6 +148,9 @@ impl FromRequest for BatchRequest {
                         ParseRequestError::PayloadTooLarge => {
                             actix_web::error::ErrorPayloadTooLarge(err)
                         }
+                        ParseRequestError::UnsupportedUploadType(_) => {
+                            actix_web::error::ErrorUnsupportedMediaType(err)
+                        }
                         _ => actix_web::error::ErrorBadRequest(err),
                     })?,
                 ))
@@ -161,14 +187,29 @@ impl Responder for Response {
     type Error = Error;
     type Future = Ready<Result<HttpResponse>>;
 
-    fn respond_to(self, _req: &HttpRequest) -> Self::Future {
-        let mut res = HttpResponse::build(StatusCode::OK);
-        res.content_type("application/json");
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        let format = ResponseFormat::from_accept(
+            req.headers()
+                .get(http::header::ACCEPT)
+                .and_then(|value| value.to_str().ok()),
+        );
+        let mut res = HttpResponse::build(
+            StatusCode::from_u16(format.status_code(&self.0)).unwrap_or(StatusCode::OK),
+        );
+        if self.0.is_method_not_allowed() {
+            res.header("allow", "POST");
+        }
+        res.content_type(format.content_type());
         if self.0.is_ok() {
             if let Some(cache_control) = self.0.cache_control().value() {
                 res.header("cache-control", cache_control);
             }
         }
-        futures_util::future::ok(res.body(serde_json::to_string(&self.0).unwrap()))
+        futures_util::future::ok(
+            res.streaming(Box::pin(
+                async_graphql::http::batch_response_chunks(self.0)
+                    .map(|chunk| chunk.map(web::Bytes::from)),
+            )),
+        )
     }
 }
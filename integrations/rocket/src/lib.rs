@@ -6,6 +6,11 @@
 //! To configure options for sending and receiving multipart requests, add your instance of
 //! `MultipartOptions` to the state managed by Rocket (`.manage(your_multipart_options)`).
 //!
+//! To prevent Cross-Site Request Forgery, add an instance of `CsrfOptions` to the managed state.
+//! The requests extracted from a body are then checked with it. Rocket does not pass the headers
+//! to `FromQuery`, so the routes extracting a request from a query string must add the
+//! [`CsrfCheck`](struct.CsrfCheck.html) request guard.
+//!
 //! **[Full Example](<https://github.com/async-graphql/examples/blob/master/rocket/starwars/src/main.rs>)**
 
 #![warn(missing_docs)]
@@ -13,12 +18,13 @@
 
 use std::io::Cursor;
 
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{CsrfOptions, MultipartOptions, ResponseFormat};
+use async_graphql::parser::types::OperationType;
 use async_graphql::{ObjectType, ParseRequestError, Schema, SubscriptionType};
 use rocket::{
     data::{self, Data, FromData, ToByteUnit},
-    http::{ContentType, Header, Status},
-    request::{self, FromQuery},
+    http::{Header, Status},
+    request::{self, FromQuery, FromRequest},
     response::{self, Responder},
 };
 use serde::de::Deserialize;
@@ -28,6 +34,47 @@ use query_deserializer::QueryDeserializer;
 
 mod query_deserializer;
 
+/// Check the request with the `CsrfOptions` managed by Rocket, if there are any.
+fn check_csrf(req: &rocket::Request<'_>) -> Result<(), ParseRequestError> {
+    match req.managed_state::<CsrfOptions>() {
+        Some(csrf) => {
+            async_graphql::http::check_csrf(csrf, req.headers().get_one("Content-Type"), |name| {
+                req.headers().get_one(name)
+            })
+        }
+        None => Ok(()),
+    }
+}
+
+/// A request guard which checks the request with the `CsrfOptions` managed by Rocket, to prevent
+/// Cross-Site Request Forgery. It always succeeds if there are none.
+///
+/// The requests extracted from a body are already checked, but a route extracting a
+/// [`Request`](struct.Request.html) from a query string must add this guard.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[rocket::get("/graphql?<query..>")]
+/// async fn graphql_query(schema: State<'_, ExampleSchema>, query: Request, _csrf: CsrfCheck) -> Response {
+///     query.execute(&schema).await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CsrfCheck;
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for CsrfCheck {
+    type Error = ParseRequestError;
+
+    async fn from_request(req: &'a rocket::Request<'r>) -> request::Outcome<Self, Self::Error> {
+        match check_csrf(req) {
+            Ok(()) => request::Outcome::Success(Self),
+            Err(e) => request::Outcome::Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
 /// A batch request which can be extracted from a request's body.
 ///
 /// # Examples
@@ -61,7 +108,11 @@ impl FromData for BatchRequest {
     type Error = ParseRequestError;
 
     async fn from_data(req: &rocket::Request<'_>, data: Data) -> data::Outcome<Self, Self::Error> {
-        let opts: MultipartOptions = req.managed_state().copied().unwrap_or_default();
+        let opts: MultipartOptions = req.managed_state().cloned().unwrap_or_default();
+
+        if let Err(e) = check_csrf(req) {
+            return data::Outcome::Failure((Status::BadRequest, e));
+        }
 
         let request = async_graphql::http::receive_batch_body(
             req.headers().get_one("Content-Type"),
@@ -80,6 +131,7 @@ impl FromData for BatchRequest {
             Err(e) => data::Outcome::Failure((
                 match e {
                     ParseRequestError::PayloadTooLarge => Status::PayloadTooLarge,
+                    ParseRequestError::UnsupportedUploadType(_) => Status::UnsupportedMediaType,
                     _ => Status::BadRequest,
                 },
                 e,
@@ -90,11 +142,14 @@ impl FromData for BatchRequest {
 
 /// A GraphQL request which can be extracted from a query string or the request's body.
 ///
+/// A request extracted from a query string is not checked for Cross-Site Request Forgery, add
+/// the [`CsrfCheck`](struct.CsrfCheck.html) guard to its route.
+///
 /// # Examples
 ///
 /// ```ignore
 /// #[rocket::post("/graphql?<query..>", rank = 2)]
-/// async fn graphql_query(schema: State<'_, ExampleSchema>, query: Request) -> Result<Response, Status> {
+/// async fn graphql_query(schema: State<'_, ExampleSchema>, query: Request, _csrf: CsrfCheck) -> Result<Response, Status> {
 ///     query.execute(&schema).await
 /// }
 ///
@@ -125,9 +180,10 @@ impl<'q> FromQuery<'q> for Request {
     type Error = serde::de::value::Error;
 
     fn from_query(query: request::Query<'_>) -> Result<Self, Self::Error> {
-        Ok(Self(async_graphql::Request::deserialize(
-            QueryDeserializer(query),
-        )?))
+        Ok(Self(
+            async_graphql::Request::deserialize(QueryDeserializer(query))?
+                .allowed_operation_types(vec![OperationType::Query]),
+        ))
     }
 }
 
@@ -165,18 +221,24 @@ impl From<async_graphql::Response> for Response {
 }
 
 impl<'r> Responder<'r, 'static> for Response {
-    fn respond_to(self, _: &'r rocket::Request<'_>) -> response::Result<'static> {
+    fn respond_to(self, req: &'r rocket::Request<'_>) -> response::Result<'static> {
         let body = serde_json::to_string(&self.0).unwrap();
+        let format = ResponseFormat::from_accept(req.headers().get_one("Accept"));
 
         let mut response = rocket::Response::new();
 
+        response.set_status(Status::from_code(format.status_code(&self.0)).unwrap_or(Status::Ok));
+        if self.0.is_method_not_allowed() {
+            response.set_header(Header::new("allow", "POST"));
+        }
+
         if self.0.is_ok() {
             if let Some(cache_control) = self.0.cache_control().value() {
                 response.set_header(Header::new("cache-control", cache_control));
             }
         }
 
-        response.set_header(ContentType::new("application", "json"));
+        response.set_header(Header::new("content-type", format.content_type()));
         response.set_sized_body(body.len(), Cursor::new(body));
 
         Ok(response)
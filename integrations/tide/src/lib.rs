@@ -11,7 +11,10 @@
 #![allow(clippy::needless_doctest_main)]
 #![forbid(unsafe_code)]
 
-use async_graphql::http::MultipartOptions;
+use std::convert::TryFrom;
+
+use async_graphql::http::{CsrfOptions, MultipartOptions, ResponseFormat};
+use async_graphql::parser::types::OperationType;
 use async_graphql::{ObjectType, ParseRequestError, Schema, SubscriptionType};
 use tide::utils::async_trait;
 use tide::{
@@ -24,7 +27,8 @@ use tide::{
 
 /// Create a new GraphQL endpoint with the schema.
 ///
-/// Default multipart options are used and batch operations are supported.
+/// Default multipart options are used, batch operations are supported and CSRF prevention is
+/// disabled.
 pub fn endpoint<Query, Mutation, Subscription>(
     schema: Schema<Query, Mutation, Subscription>,
 ) -> Endpoint<Query, Mutation, Subscription> {
@@ -32,6 +36,7 @@ pub fn endpoint<Query, Mutation, Subscription>(
         schema,
         opts: MultipartOptions::default(),
         batch: true,
+        csrf: None,
     }
 }
 
@@ -46,6 +51,8 @@ pub struct Endpoint<Query, Mutation, Subscription> {
     pub opts: MultipartOptions,
     /// Whether to support batch requests in the endpoint.
     pub batch: bool,
+    /// The options for preventing Cross-Site Request Forgery in the endpoint.
+    pub csrf: Option<CsrfOptions>,
 }
 
 impl<Query, Mutation, Subscription> Endpoint<Query, Mutation, Subscription> {
@@ -59,6 +66,15 @@ impl<Query, Mutation, Subscription> Endpoint<Query, Mutation, Subscription> {
     pub fn batch(self, batch: bool) -> Self {
         Self { batch, ..self }
     }
+    /// Check the requests of the endpoint with the given options to prevent Cross-Site Request
+    /// Forgery.
+    #[must_use]
+    pub fn csrf_prevention(self, csrf: CsrfOptions) -> Self {
+        Self {
+            csrf: Some(csrf),
+            ..self
+        }
+    }
 }
 
 // Manual impl to remove bounds on generics
@@ -66,8 +82,9 @@ impl<Query, Mutation, Subscription> Clone for Endpoint<Query, Mutation, Subscrip
     fn clone(&self) -> Self {
         Self {
             schema: self.schema.clone(),
-            opts: self.opts,
+            opts: self.opts.clone(),
             batch: self.batch,
+            csrf: self.csrf.clone(),
         }
     }
 }
@@ -82,20 +99,52 @@ where
     TideState: Clone + Send + Sync + 'static,
 {
     async fn call(&self, request: Request<TideState>) -> tide::Result {
-        respond(
+        if let Some(csrf) = &self.csrf {
+            check_csrf(&request, csrf)?;
+        }
+        let format = ResponseFormat::from_accept(
+            request
+                .header(headers::ACCEPT)
+                .and_then(|values| values.get(0))
+                .map(HeaderValue::as_str),
+        );
+        respond_with_format(
             self.schema
                 .execute_batch(if self.batch {
-                    receive_batch_request_opts(request, self.opts).await
+                    receive_batch_request_opts(request, self.opts.clone()).await
                 } else {
-                    receive_request_opts(request, self.opts)
+                    receive_request_opts(request, self.opts.clone())
                         .await
                         .map(Into::into)
                 }?)
                 .await,
+            format,
         )
     }
 }
 
+/// Check that a Tide request could not have been sent cross-site by a browser without a preflight
+/// request, see [`async_graphql::http::check_csrf`](https://docs.rs/async-graphql/*/async_graphql/http/fn.check_csrf.html).
+///
+/// The receiving functions do not check the requests, so call this before them to prevent
+/// Cross-Site Request Forgery.
+pub fn check_csrf<State>(request: &Request<State>, csrf: &CsrfOptions) -> tide::Result<()> {
+    async_graphql::http::check_csrf(
+        csrf,
+        request
+            .header(headers::CONTENT_TYPE)
+            .and_then(|values| values.get(0))
+            .map(HeaderValue::as_str),
+        |name| {
+            request
+                .header(name)
+                .and_then(|values| values.get(0))
+                .map(HeaderValue::as_str)
+        },
+    )
+    .map_err(|e| tide::Error::new(StatusCode::BadRequest, e))
+}
+
 /// Convert a Tide request to a GraphQL request.
 pub async fn receive_request<State: Clone + Send + Sync + 'static>(
     request: Request<State>,
@@ -127,7 +176,11 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
     opts: MultipartOptions,
 ) -> tide::Result<async_graphql::BatchRequest> {
     if request.method() == Method::Get {
-        request.query::<async_graphql::Request>().map(Into::into)
+        request.query::<async_graphql::Request>().map(|request| {
+            request
+                .allowed_operation_types(vec![OperationType::Query])
+                .into()
+        })
     } else if request.method() == Method::Post {
         let body = request.take_body();
         let content_type = request
@@ -141,6 +194,9 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
                 tide::Error::new(
                     match &e {
                         ParseRequestError::PayloadTooLarge => StatusCode::PayloadTooLarge,
+                        ParseRequestError::UnsupportedUploadType(_) => {
+                            StatusCode::UnsupportedMediaType
+                        }
                         _ => StatusCode::BadRequest,
                     },
                     e,
@@ -154,16 +210,34 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
     }
 }
 
-/// Convert a GraphQL response to a Tide response.
+/// Convert a GraphQL response to a Tide response in the JSON format.
+///
+/// Unlike [`endpoint`](fn.endpoint.html), it does not have access to the request, so the format is
+/// not negotiated from its `Accept` header, use [`respond_with_format`](fn.respond_with_format.html)
+/// for that.
 pub fn respond(gql: impl Into<async_graphql::BatchResponse>) -> tide::Result {
+    respond_with_format(gql, ResponseFormat::Json)
+}
+
+/// Convert a GraphQL response to a Tide response with the given format, see
+/// [`ResponseFormat::from_accept`](https://docs.rs/async-graphql/*/async_graphql/http/enum.ResponseFormat.html#method.from_accept).
+pub fn respond_with_format(
+    gql: impl Into<async_graphql::BatchResponse>,
+    format: ResponseFormat,
+) -> tide::Result {
     let gql = gql.into();
 
-    let mut response = Response::new(StatusCode::Ok);
+    let mut response =
+        Response::new(StatusCode::try_from(format.status_code(&gql)).unwrap_or(StatusCode::Ok));
+    if gql.is_method_not_allowed() {
+        response.insert_header("allow", "POST");
+    }
     if gql.is_ok() {
         if let Some(cache_control) = gql.cache_control().value() {
             response.insert_header(headers::CACHE_CONTROL, cache_control);
         }
     }
     response.set_body(Body::from_json(&gql)?);
+    response.insert_header(headers::CONTENT_TYPE, format.content_type());
     Ok(response)
 }
@@ -174,6 +174,7 @@ async fn upload() -> Result<()> {
                 let mut content = String::new();
                 upload_value
                     .into_read()
+                    .unwrap()
                     .read_to_string(&mut content)
                     .unwrap();
                 assert_eq!(content, "test".to_owned());
@@ -2,9 +2,11 @@ use std::convert::TryInto;
 use std::io;
 use std::io::ErrorKind;
 
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{CsrfOptions, MultipartOptions, ResponseFormat};
+use async_graphql::parser::types::OperationType;
 use async_graphql::{BatchRequest, ObjectType, Schema, SubscriptionType};
 use futures_util::TryStreamExt;
+use warp::http::{HeaderMap, HeaderValue, StatusCode};
 use warp::reply::Response as WarpResponse;
 use warp::{Buf, Filter, Rejection, Reply};
 
@@ -37,26 +39,41 @@ where
     Subscription: SubscriptionType + Send + Sync + 'static,
 {
     warp::any()
-        .and(warp::get().and(warp::query()).map(BatchRequest::Single))
+        .and(
+            warp::get()
+                .and(warp::query())
+                .map(|request: async_graphql::Request| {
+                    BatchRequest::Single(
+                        request.allowed_operation_types(vec![OperationType::Query]),
+                    )
+                }),
+        )
         .or(warp::post()
             .and(warp::header::optional::<String>("content-type"))
             .and(warp::body::stream())
-            .and_then(move |content_type, body| async move {
-                async_graphql::http::receive_batch_body(
-                    content_type,
-                    TryStreamExt::map_err(body, |e| io::Error::new(ErrorKind::Other, e))
-                        .map_ok(|mut buf| Buf::to_bytes(&mut buf))
-                        .into_async_read(),
-                    opts,
-                )
-                .await
-                .map_err(|e| warp::reject::custom(BadRequest(e)))
+            .and_then(move |content_type, body| {
+                let opts = opts.clone();
+                async move {
+                    async_graphql::http::receive_batch_body(
+                        content_type,
+                        TryStreamExt::map_err(body, |e| io::Error::new(ErrorKind::Other, e))
+                            .map_ok(|mut buf| Buf::to_bytes(&mut buf))
+                            .into_async_read(),
+                        opts,
+                    )
+                    .await
+                    .map_err(|e| warp::reject::custom(BadRequest(e)))
+                }
             }))
         .unify()
         .map(move |res| (schema.clone(), res))
 }
 
 /// Reply for `async_graphql::BatchRequest`.
+///
+/// A reply does not have access to the request, so its `Reply` implementation always uses the JSON
+/// format. Use [`with_format`](#method.with_format) with the format extracted by
+/// [`response_format`](fn.response_format.html) to negotiate it from the `Accept` header.
 #[derive(Debug)]
 pub struct BatchResponse(pub async_graphql::BatchResponse);
 
@@ -66,15 +83,24 @@ impl From<async_graphql::BatchResponse> for BatchResponse {
     }
 }
 
-impl Reply for BatchResponse {
-    fn into_response(self) -> WarpResponse {
+impl BatchResponse {
+    /// Convert to a reply with the given format, which can be extracted with
+    /// [`response_format`](fn.response_format.html).
+    pub fn with_format(self, format: ResponseFormat) -> WarpResponse {
         let mut resp = warp::reply::with_header(
             warp::reply::json(&self.0),
             "content-type",
-            "application/json",
+            format.content_type(),
         )
         .into_response();
 
+        *resp.status_mut() =
+            StatusCode::from_u16(format.status_code(&self.0)).unwrap_or(StatusCode::OK);
+        if self.0.is_method_not_allowed() {
+            resp.headers_mut()
+                .insert("allow", HeaderValue::from_static("POST"));
+        }
+
         if self.0.is_ok() {
             if let Some(cache_control) = self.0.cache_control().value() {
                 resp.headers_mut()
@@ -85,3 +111,37 @@ impl Reply for BatchResponse {
         resp
     }
 }
+
+impl Reply for BatchResponse {
+    fn into_response(self) -> WarpResponse {
+        self.with_format(ResponseFormat::Json)
+    }
+}
+
+/// Extracts the format of the response from the `Accept` header of the request, see
+/// [`ResponseFormat::from_accept`](https://docs.rs/async-graphql/*/async_graphql/http/enum.ResponseFormat.html#method.from_accept).
+pub fn response_format() -> impl Filter<Extract = (ResponseFormat,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("accept")
+        .map(|accept: Option<String>| ResponseFormat::from_accept(accept.as_deref()))
+}
+
+/// Rejects the requests which could have been sent cross-site by a browser without a preflight
+/// request, see [`check_csrf`](https://docs.rs/async-graphql/*/async_graphql/http/fn.check_csrf.html).
+///
+/// Combine it with the GraphQL filters to prevent Cross-Site Request Forgery, e.g.
+/// `async_graphql_warp::csrf(CsrfOptions::default()).and(async_graphql_warp::graphql(schema))`.
+pub fn csrf(csrf: CsrfOptions) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::headers_cloned()
+        .and_then(move |headers: HeaderMap| {
+            let result = async_graphql::http::check_csrf(
+                &csrf,
+                headers
+                    .get("content-type")
+                    .and_then(|value| value.to_str().ok()),
+                |name| headers.get(name).and_then(|value| value.to_str().ok()),
+            )
+            .map_err(|e| warp::reject::custom(BadRequest(e)));
+            async move { result }
+        })
+        .untuple_one()
+}
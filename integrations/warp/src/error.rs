@@ -20,6 +20,7 @@ impl BadRequest {
     pub fn status(&self) -> StatusCode {
         match self.0 {
             ParseRequestError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ParseRequestError::UnsupportedUploadType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             _ => StatusCode::BAD_REQUEST,
         }
     }
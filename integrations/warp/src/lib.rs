@@ -8,7 +8,7 @@ mod error;
 mod request;
 mod subscription;
 
-pub use batch_request::{graphql_batch, graphql_batch_opts, BatchResponse};
+pub use batch_request::{csrf, graphql_batch, graphql_batch_opts, response_format, BatchResponse};
 pub use error::BadRequest;
 pub use request::{graphql, graphql_opts, Response};
 pub use subscription::{graphql_subscription, graphql_subscription_with_data};
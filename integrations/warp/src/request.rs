@@ -1,4 +1,4 @@
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{MultipartOptions, ResponseFormat};
 use async_graphql::{BatchRequest, ObjectType, Request, Schema, SubscriptionType};
 use warp::reply::Response as WarpResponse;
 use warp::{Filter, Rejection, Reply};
@@ -78,6 +78,10 @@ where
 }
 
 /// Reply for `async_graphql::Request`.
+///
+/// A reply does not have access to the request, so its `Reply` implementation always uses the JSON
+/// format. Use [`with_format`](#method.with_format) with the format extracted by
+/// [`response_format`](fn.response_format.html) to negotiate it from the `Accept` header.
 #[derive(Debug)]
 pub struct Response(pub async_graphql::Response);
 
@@ -87,6 +91,14 @@ impl From<async_graphql::Response> for Response {
     }
 }
 
+impl Response {
+    /// Convert to a reply with the given format, which can be extracted with
+    /// [`response_format`](fn.response_format.html).
+    pub fn with_format(self, format: ResponseFormat) -> WarpResponse {
+        BatchResponse(self.0.into()).with_format(format)
+    }
+}
+
 impl Reply for Response {
     fn into_response(self) -> WarpResponse {
         BatchResponse(self.0.into()).into_response()
@@ -13,7 +13,7 @@ use pest::RuleType;
 use serde::{Serialize, Serializer};
 use std::fmt::{self, Display, Formatter};
 
-pub use parse::{parse_query, parse_schema};
+pub use parse::{parse_query, parse_query_with_max_tokens, parse_schema};
 pub use pos::{Pos, Positioned};
 
 pub mod types;
@@ -75,6 +75,11 @@ pub enum Error {
     },
     /// The document does not contain any operation.
     MissingOperation,
+    /// The document contains more tokens than allowed.
+    TooManyTokens {
+        /// The maximum number of tokens.
+        limit: usize,
+    },
 }
 
 impl Error {
@@ -103,6 +108,7 @@ impl Error {
                 ErrorPositions::new_2(*second, *first)
             }
             Self::MissingOperation => ErrorPositions::new_0(),
+            Self::TooManyTokens { .. } => ErrorPositions::new_0(),
         }
     }
 }
@@ -123,6 +129,9 @@ impl Display for Error {
                 write!(f, "fragment {} is defined twice", fragment)
             }
             Self::MissingOperation => f.write_str("document does not contain an operation"),
+            Self::TooManyTokens { limit } => {
+                write!(f, "document contains more than {} tokens", limit)
+            }
         }
     }
 }
@@ -1,6 +1,25 @@
 use super::*;
 use async_graphql_value::Name;
 
+/// Parse a GraphQL query document, refusing documents with more than `max_tokens` lexical
+/// tokens.
+///
+/// The tokens are counted by a cheap scan before the document is handed to the parser, so
+/// oversized documents are rejected without building a syntax tree for them.
+///
+/// # Errors
+///
+/// Fails if the query contains too many tokens or is not a valid GraphQL document.
+pub fn parse_query_with_max_tokens<T: AsRef<str>>(
+    input: T,
+    max_tokens: usize,
+) -> Result<ExecutableDocument> {
+    if count_tokens(input.as_ref(), max_tokens) > max_tokens {
+        return Err(Error::TooManyTokens { limit: max_tokens });
+    }
+    parse_query(input)
+}
+
 /// Parse a GraphQL query document.
 ///
 /// # Errors
@@ -211,6 +230,7 @@ fn parse_variable_definition(
     let default_value = parse_if_rule(&mut pairs, Rule::default_value, |pair| {
         parse_default_value(pair, pc)
     })?;
+    let directives = parse_opt_const_directives(&mut pairs, pc)?;
 
     debug_assert_eq!(pairs.next(), None);
 
@@ -219,6 +239,7 @@ fn parse_variable_definition(
             name: variable,
             var_type,
             default_value,
+            directives,
         },
         pos,
     ))
@@ -394,6 +415,70 @@ fn parse_type_condition(
     ))
 }
 
+/// Count the lexical tokens of a document, stopping as soon as the count exceeds `limit`.
+///
+/// Ignored tokens (whitespace, commas and comments) are skipped. Malformed input is counted on a
+/// best-effort basis, the parser reports the actual syntax error afterwards.
+fn count_tokens(input: &str, limit: usize) -> usize {
+    let bytes = input.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+
+    while i < bytes.len() && count <= limit {
+        match bytes[i] {
+            b' ' | b'\t' | b'\n' | b'\r' | b',' => {
+                i += 1;
+                continue;
+            }
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' && bytes[i] != b'\r' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'"' if bytes[i..].starts_with(b"\"\"\"") => {
+                i += 3;
+                while i < bytes.len() && !bytes[i..].starts_with(b"\"\"\"") {
+                    if bytes[i..].starts_with(b"\\\"\"\"") {
+                        i += 4;
+                    } else {
+                        i += 1;
+                    }
+                }
+                i += 3;
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && !matches!(bytes[i], b'"' | b'\n' | b'\r') {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'.' if bytes[i..].starts_with(b"...") => i += 3,
+            b'-' | b'0'..=b'9' => {
+                i += 1;
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'.' | b'+' | b'-'))
+                {
+                    i += 1;
+                }
+            }
+            b'_' | b'a'..=b'z' | b'A'..=b'Z' => {
+                i += 1;
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+            }
+            _ => {
+                i += input[i..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        count += 1;
+    }
+
+    count
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -432,4 +517,25 @@ mod tests {
         assert!(parse_query(query_ok).is_ok());
         assert!(parse_query(query_overflow).is_ok());
     }
+
+    #[test]
+    fn test_count_tokens() {
+        assert_eq!(count_tokens("{ a }", 100), 3);
+        assert_eq!(count_tokens("query A($v: Int = 1) { a(b: $v) }", 100), 19);
+        assert_eq!(
+            count_tokens("{ a(s: \"x \\\" y\", t: \"\"\"a \\\"\"\" b\"\"\") }", 100),
+            11
+        );
+        assert_eq!(count_tokens("# comment\n{ ...A, b: c -1.5e+3 }", 100), 8);
+        assert_eq!(count_tokens("{ a a a a a a }", 3), 4);
+    }
+
+    #[test]
+    fn test_parse_query_with_max_tokens() {
+        assert!(parse_query_with_max_tokens("{ a b }", 4).is_ok());
+        assert_eq!(
+            parse_query_with_max_tokens("{ a b c }", 4).unwrap_err(),
+            Error::TooManyTokens { limit: 4 }
+        );
+    }
 }
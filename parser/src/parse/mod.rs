@@ -16,7 +16,7 @@ mod service;
 mod utils;
 
 use async_graphql_value::{ConstValue, Name, Number, Value};
-pub use executable::parse_query;
+pub use executable::{parse_query, parse_query_with_max_tokens};
 pub use service::parse_schema;
 
 #[derive(Parser)]
@@ -117,6 +117,8 @@ pub struct VariableDefinition {
     pub var_type: Positioned<Type>,
     /// The optional default value of the variable.
     pub default_value: Option<Positioned<ConstValue>>,
+    /// The directives of the variable definition.
+    pub directives: Vec<Positioned<ConstDirective>>,
 }
 
 impl VariableDefinition {
@@ -119,3 +119,6 @@ pub trait UnionType: ContainerType {}
 
 /// A GraphQL input object.
 pub trait InputObjectType: InputType {}
+
+/// A GraphQL oneof input object.
+pub trait OneofObjectType: InputObjectType {}
@@ -6,7 +6,7 @@ use std::convert::TryFrom;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::ops::Deref;
 use std::sync::atomic::AtomicUsize;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use async_graphql_value::Value as InputValue;
 use fnv::FnvHashMap;
@@ -14,14 +14,15 @@ use serde::de::{Deserialize, Deserializer};
 use serde::ser::{SerializeSeq, Serializer};
 use serde::Serialize;
 
-use crate::extensions::Extensions;
+use crate::extensions::{ExtensionContext, Extensions};
 use crate::parser::types::{
-    Directive, Field, FragmentDefinition, OperationDefinition, Selection, SelectionSet,
+    Directive, DocumentOperations, ExecutableDocument, Field, FragmentDefinition,
+    OperationDefinition, Selection, SelectionSet,
 };
 use crate::schema::SchemaEnv;
 use crate::{
-    Error, InputType, Lookahead, Name, Pos, Positioned, Result, ServerError, ServerResult,
-    UploadValue, Value,
+    Error, InputType, Lookahead, Name, PathSegment, Pos, Positioned, Result, ServerError,
+    ServerResult, UploadValue, Value,
 };
 
 /// Variables of a query.
@@ -54,7 +55,7 @@ impl Variables {
     #[must_use]
     pub fn from_value(value: Value) -> Self {
         match value {
-            Value::Object(obj) => Self(obj),
+            Value::Object(obj) => Self(obj.into_iter().collect()),
             _ => Self::default(),
         }
     }
@@ -73,7 +74,7 @@ impl Variables {
     /// Get the variables as a GraphQL value.
     #[must_use]
     pub fn into_value(self) -> Value {
-        Value::Object(self.0)
+        Value::Object(self.0.into_iter().collect())
     }
 
     pub(crate) fn variable_path(&mut self, path: &str) -> Option<&mut Value> {
@@ -316,10 +317,33 @@ pub struct ContextBase<'a, T> {
 pub struct QueryEnvInner {
     pub extensions: Extensions,
     pub variables: Variables,
-    pub operation: Positioned<OperationDefinition>,
-    pub fragments: HashMap<Name, Positioned<FragmentDefinition>>,
+    /// The document of the query, shared with the document cache.
+    pub document: Arc<ExecutableDocument>,
+    /// The name of the operation to execute, `None` if the document has a single anonymous one.
+    pub operation_name: Option<Name>,
     pub uploads: Vec<UploadValue>,
     pub ctx_data: Arc<Data>,
+    pub batch_data: Option<Arc<Data>>,
+    /// Errors reported without failing the field being resolved.
+    pub errors: Mutex<Vec<ServerError>>,
+}
+
+impl QueryEnvInner {
+    #[doc(hidden)]
+    pub fn operation(&self) -> &Positioned<OperationDefinition> {
+        match (&self.document.operations, &self.operation_name) {
+            (DocumentOperations::Single(operation), _) => operation,
+            (DocumentOperations::Multiple(operations), Some(name)) => &operations[name],
+            (DocumentOperations::Multiple(_), None) => {
+                unreachable!("the operation is selected when the query is prepared")
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn fragments(&self) -> &HashMap<Name, Positioned<FragmentDefinition>> {
+        &self.document.fragments
+    }
 }
 
 #[doc(hidden)]
@@ -361,6 +385,31 @@ impl QueryEnv {
 }
 
 impl<'a, T> ContextBase<'a, T> {
+    /// Reports an error without failing the field being resolved, the path of the error is
+    /// relative to the current path.
+    pub(crate) fn add_error(&self, mut error: ServerError) {
+        let mut path = Vec::new();
+        if let Some(node) = self.path_node {
+            node.for_each(|segment| {
+                path.push(match segment {
+                    QueryPathSegment::Index(idx) => PathSegment::Index(*idx),
+                    QueryPathSegment::Name(name) => PathSegment::Field(name.to_string()),
+                })
+            });
+        }
+        path.append(&mut error.path);
+        error.path = path;
+
+        self.query_env.extensions.error(
+            &ExtensionContext {
+                schema_data: &self.schema_env.data,
+                query_data: &self.query_env.ctx_data,
+            },
+            &error,
+        );
+        self.query_env.errors.lock().unwrap().push(error);
+    }
+
     #[doc(hidden)]
     pub fn get_child_resolve_id(&self) -> ResolveId {
         let id = self
@@ -438,13 +487,19 @@ impl<'a, T> ContextBase<'a, T> {
             .ctx_data
             .0
             .get(&TypeId::of::<D>())
+            .or_else(|| {
+                self.query_env
+                    .batch_data
+                    .as_ref()
+                    .and_then(|data| data.0.get(&TypeId::of::<D>()))
+            })
             .or_else(|| self.schema_env.data.0.get(&TypeId::of::<D>()))
             .and_then(|d| d.downcast_ref::<D>())
     }
 
     fn var_value(&self, name: &str, pos: Pos) -> ServerResult<Value> {
         self.query_env
-            .operation
+            .operation()
             .node
             .variable_definitions
             .iter()
@@ -460,7 +515,7 @@ impl<'a, T> ContextBase<'a, T> {
             .ok_or_else(|| ServerError::new(format!("Variable {} is not defined.", name)).at(pos))
     }
 
-    fn resolve_input_value(&self, value: Positioned<InputValue>) -> ServerResult<Value> {
+    pub(crate) fn resolve_input_value(&self, value: Positioned<InputValue>) -> ServerResult<Value> {
         let pos = value.pos;
         value
             .node
@@ -505,6 +560,21 @@ impl<'a, T> ContextBase<'a, T> {
 }
 
 impl<'a> ContextBase<'a, &'a Positioned<SelectionSet>> {
+    #[doc(hidden)]
+    pub fn with_current_field<'b>(
+        &'b self,
+        field: &'b Positioned<Field>,
+    ) -> ContextBase<'b, &'b Positioned<Field>> {
+        ContextBase {
+            path_node: self.path_node,
+            item: field,
+            resolve_id: self.resolve_id,
+            inc_resolve_id: self.inc_resolve_id,
+            schema_env: self.schema_env,
+            query_env: self.query_env,
+        }
+    }
+
     #[doc(hidden)]
     pub fn with_index(&'a self, idx: usize) -> ContextBase<'a, &'a Positioned<SelectionSet>> {
         ContextBase {
@@ -578,7 +648,7 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
     /// }
     /// ```
     pub fn look_ahead(&self) -> Lookahead {
-        Lookahead::new(&self.query_env.fragments, &self.item.node)
+        Lookahead::new(self.query_env.fragments(), &self.item.node)
     }
 
     /// Get the current field.
@@ -616,7 +686,7 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
     /// ```
     pub fn field(&self) -> SelectionField<'a> {
         SelectionField {
-            fragments: &self.query_env.fragments,
+            fragments: self.query_env.fragments(),
             field: &self.item.node,
         }
     }
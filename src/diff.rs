@@ -0,0 +1,1010 @@
+//! Comparison of schemas
+//!
+//! The changes between two versions of a schema are classified as breaking, dangerous or safe, so
+//! that a CI can block the breaking changes of an API.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::diff::{diff_sdl, ChangeKind, ChangeSeverity};
+//!
+//! let old_sdl = r#"
+//!     type Query {
+//!         user(id: ID!): User
+//!     }
+//!
+//!     type User {
+//!         name: String!
+//!         email: String
+//!     }
+//! "#;
+//!
+//! let new_sdl = r#"
+//!     type Query {
+//!         user(id: ID!, version: Int!): User
+//!     }
+//!
+//!     type User {
+//!         name: String!
+//!     }
+//! "#;
+//!
+//! let changes = diff_sdl(old_sdl, new_sdl).unwrap();
+//! assert_eq!(
+//!     changes
+//!         .iter()
+//!         .map(|change| (change.kind, change.coordinate.as_str()))
+//!         .collect::<Vec<_>>(),
+//!     vec![
+//!         (ChangeKind::ArgumentAdded, "Query.user(version:)"),
+//!         (ChangeKind::FieldRemoved, "User.email"),
+//!     ]
+//! );
+//! assert!(changes
+//!     .iter()
+//!     .all(|change| change.severity == ChangeSeverity::Breaking));
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::model::__DirectiveLocation;
+use crate::parser::parse_schema;
+use crate::parser::types::{
+    BaseType, InputValueDefinition, ServiceDocument, Type, TypeKind, TypeSystemDefinition,
+};
+use crate::registry::{MetaInputValue, MetaType, Registry};
+use crate::Positioned;
+
+/// How a change of a schema affects its clients.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ChangeSeverity {
+    /// The change breaks some queries which were valid, or the handling of their results.
+    Breaking,
+
+    /// The change does not break any query, but may break the clients, e.g. those that expect
+    /// an exhaustive list of enum values.
+    Dangerous,
+
+    /// The change is backward compatible.
+    Safe,
+}
+
+impl Display for ChangeSeverity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeSeverity::Breaking => f.write_str("BREAKING"),
+            ChangeSeverity::Dangerous => f.write_str("DANGEROUS"),
+            ChangeSeverity::Safe => f.write_str("SAFE"),
+        }
+    }
+}
+
+/// The kinds of changes of a schema.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum ChangeKind {
+    TypeAdded,
+    TypeRemoved,
+    TypeKindChanged,
+    FieldAdded,
+    FieldRemoved,
+    FieldTypeChanged,
+    ArgumentAdded,
+    ArgumentRemoved,
+    ArgumentTypeChanged,
+    ArgumentDefaultValueChanged,
+    InputFieldAdded,
+    InputFieldRemoved,
+    InputFieldTypeChanged,
+    InputFieldDefaultValueChanged,
+    EnumValueAdded,
+    EnumValueRemoved,
+    UnionMemberAdded,
+    UnionMemberRemoved,
+    InterfaceAdded,
+    InterfaceRemoved,
+    DirectiveAdded,
+    DirectiveRemoved,
+    DirectiveLocationAdded,
+    DirectiveLocationRemoved,
+}
+
+/// A change between two versions of a schema.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SchemaChange {
+    /// How the change affects the clients.
+    pub severity: ChangeSeverity,
+
+    /// The kind of the change.
+    pub kind: ChangeKind,
+
+    /// The schema coordinate of the changed element, e.g. `User.name` for a field,
+    /// `Query.users(first:)` for an argument, `Role.ADMIN` for an enum value or `@auth` for a
+    /// directive.
+    pub coordinate: String,
+
+    /// The description of the change.
+    pub message: String,
+}
+
+impl Display for SchemaChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// Compares two registries.
+pub fn diff_registries(old: &Registry, new: &Registry) -> Vec<SchemaChange> {
+    diff_schemas(&Schema::from_registry(old), &Schema::from_registry(new))
+}
+
+/// Compares two parsed SDL documents.
+pub fn diff_service_documents(old: &ServiceDocument, new: &ServiceDocument) -> Vec<SchemaChange> {
+    diff_schemas(
+        &Schema::from_service_document(old),
+        &Schema::from_service_document(new),
+    )
+}
+
+/// Compares two SDL documents, returning an error if one of them is invalid.
+pub fn diff_sdl(old: &str, new: &str) -> crate::parser::Result<Vec<SchemaChange>> {
+    Ok(diff_service_documents(
+        &parse_schema(old)?,
+        &parse_schema(new)?,
+    ))
+}
+
+/// Compares the schema described by a SDL document with a registry.
+pub(crate) fn diff_sdl_registry(
+    old: &str,
+    new: &Registry,
+) -> crate::parser::Result<Vec<SchemaChange>> {
+    Ok(diff_schemas(
+        &Schema::from_service_document(&parse_schema(old)?),
+        &Schema::from_registry(new),
+    ))
+}
+
+const BUILTIN_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+const BUILTIN_DIRECTIVES: &[&str] = &[
+    "include",
+    "skip",
+    "ifdef",
+    "deprecated",
+    "specifiedBy",
+    "oneOf",
+];
+
+/// The part of a schema which matters to its clients, built either from a registry or from a
+/// SDL document.
+#[derive(Default)]
+struct Schema {
+    types: IndexMap<String, TypeInfo>,
+    directives: IndexMap<String, DirectiveInfo>,
+}
+
+#[derive(Default)]
+struct TypeInfo {
+    kind: &'static str,
+    fields: IndexMap<String, FieldInfo>,
+    input_fields: IndexMap<String, InputValueInfo>,
+    enum_values: IndexSet<String>,
+    possible_types: IndexSet<String>,
+    implements: IndexSet<String>,
+}
+
+struct FieldInfo {
+    ty: String,
+    args: IndexMap<String, InputValueInfo>,
+}
+
+struct InputValueInfo {
+    ty: String,
+    default_value: Option<String>,
+}
+
+struct DirectiveInfo {
+    args: IndexMap<String, InputValueInfo>,
+    locations: IndexSet<String>,
+}
+
+impl InputValueInfo {
+    fn from_meta(input_value: &MetaInputValue) -> Self {
+        Self {
+            ty: input_value.ty.clone(),
+            default_value: input_value.default_value.clone(),
+        }
+    }
+
+    fn from_definition(input_value: &InputValueDefinition) -> Self {
+        Self {
+            ty: input_value.ty.node.to_string(),
+            default_value: input_value
+                .default_value
+                .as_ref()
+                .map(|value| value.node.to_string()),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.ty.ends_with('!') && self.default_value.is_none()
+    }
+}
+
+fn convert_input_values<'a>(
+    values: impl IntoIterator<Item = &'a Positioned<InputValueDefinition>>,
+) -> IndexMap<String, InputValueInfo> {
+    values
+        .into_iter()
+        .map(|value| {
+            (
+                value.node.name.node.to_string(),
+                InputValueInfo::from_definition(&value.node),
+            )
+        })
+        .collect()
+}
+
+impl Schema {
+    fn from_registry(registry: &Registry) -> Self {
+        let mut schema = Schema::default();
+
+        for ty in registry.types.values() {
+            let name = ty.name();
+            if name.starts_with("__") || BUILTIN_SCALARS.contains(&name) {
+                continue;
+            }
+
+            let mut info = TypeInfo::default();
+            match ty {
+                MetaType::Scalar { .. } => info.kind = "scalar",
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                    info.kind = match ty {
+                        MetaType::Object { .. } => "object",
+                        _ => "interface",
+                    };
+                    info.fields = fields
+                        .values()
+                        .filter(|field| !field.name.starts_with("__"))
+                        .map(|field| {
+                            (
+                                field.name.clone(),
+                                FieldInfo {
+                                    ty: field.ty.clone(),
+                                    args: field
+                                        .args
+                                        .values()
+                                        .map(|arg| {
+                                            (arg.name.to_string(), InputValueInfo::from_meta(arg))
+                                        })
+                                        .collect(),
+                                },
+                            )
+                        })
+                        .collect();
+                    if let Some(implements) = registry.implements.get(name) {
+                        let mut implements = implements.iter().cloned().collect::<Vec<_>>();
+                        implements.sort();
+                        info.implements = implements.into_iter().collect();
+                    }
+                }
+                MetaType::Union { possible_types, .. } => {
+                    info.kind = "union";
+                    info.possible_types = possible_types.clone();
+                }
+                MetaType::Enum { enum_values, .. } => {
+                    info.kind = "enum";
+                    info.enum_values = enum_values
+                        .values()
+                        .map(|value| value.name.to_string())
+                        .collect();
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    info.kind = "input object";
+                    info.input_fields = input_fields
+                        .values()
+                        .map(|field| (field.name.to_string(), InputValueInfo::from_meta(field)))
+                        .collect();
+                }
+            }
+            schema.types.insert(name.to_string(), info);
+        }
+
+        let mut directives = registry
+            .directives
+            .values()
+            .filter(|directive| !BUILTIN_DIRECTIVES.contains(&directive.name.as_str()))
+            .collect::<Vec<_>>();
+        directives.sort_by(|a, b| a.name.cmp(&b.name));
+        for directive in directives {
+            schema.directives.insert(
+                directive.name.to_string(),
+                DirectiveInfo {
+                    args: directive
+                        .args
+                        .values()
+                        .map(|arg| (arg.name.to_string(), InputValueInfo::from_meta(arg)))
+                        .collect(),
+                    locations: directive
+                        .locations
+                        .iter()
+                        .map(|location| format!("{:?}", location))
+                        .collect(),
+                },
+            );
+        }
+
+        schema
+    }
+
+    fn from_service_document(document: &ServiceDocument) -> Self {
+        let mut schema = Schema::default();
+
+        for definition in &document.definitions {
+            match definition {
+                TypeSystemDefinition::Type(ty) => {
+                    let name = ty.node.name.node.as_str();
+                    if BUILTIN_SCALARS.contains(&name) {
+                        continue;
+                    }
+
+                    // The extensions of a type are merged into its definition.
+                    let info = schema.types.entry(name.to_string()).or_default();
+                    match &ty.node.kind {
+                        TypeKind::Scalar => info.kind = "scalar",
+                        TypeKind::Object(object) => {
+                            info.kind = "object";
+                            add_fields(info, &object.fields, &object.implements);
+                        }
+                        TypeKind::Interface(interface) => {
+                            info.kind = "interface";
+                            add_fields(info, &interface.fields, &interface.implements);
+                        }
+                        TypeKind::Union(union) => {
+                            info.kind = "union";
+                            info.possible_types
+                                .extend(union.members.iter().map(|member| member.node.to_string()));
+                        }
+                        TypeKind::Enum(enum_type) => {
+                            info.kind = "enum";
+                            info.enum_values.extend(
+                                enum_type
+                                    .values
+                                    .iter()
+                                    .map(|value| value.node.value.node.to_string()),
+                            );
+                        }
+                        TypeKind::InputObject(input_object) => {
+                            info.kind = "input object";
+                            info.input_fields
+                                .extend(convert_input_values(&input_object.fields));
+                        }
+                    }
+                }
+                TypeSystemDefinition::Directive(directive) => {
+                    let name = directive.node.name.node.as_str();
+                    if BUILTIN_DIRECTIVES.contains(&name) {
+                        continue;
+                    }
+                    schema.directives.insert(
+                        name.to_string(),
+                        DirectiveInfo {
+                            args: convert_input_values(&directive.node.arguments),
+                            locations: directive
+                                .node
+                                .locations
+                                .iter()
+                                .map(|location| {
+                                    format!("{:?}", __DirectiveLocation::from(location.node))
+                                })
+                                .collect(),
+                        },
+                    );
+                }
+                TypeSystemDefinition::Schema(_) => {}
+            }
+        }
+
+        schema
+    }
+}
+
+fn add_fields(
+    info: &mut TypeInfo,
+    fields: &[Positioned<crate::parser::types::FieldDefinition>],
+    implements: &[Positioned<crate::Name>],
+) {
+    info.fields.extend(fields.iter().map(|field| {
+        (
+            field.node.name.node.to_string(),
+            FieldInfo {
+                ty: field.node.ty.node.to_string(),
+                args: convert_input_values(&field.node.arguments),
+            },
+        )
+    }));
+    info.implements
+        .extend(implements.iter().map(|name| name.node.to_string()));
+}
+
+/// Returns `true` if the values of the type `new` can be returned where a value of the type
+/// `old` was expected, e.g. if a nullable field becomes non-null.
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    if !old.nullable && new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(old), BaseType::Named(new)) => old == new,
+        (BaseType::List(old), BaseType::List(new)) => is_safe_output_change(old, new),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the values of the type `old` are still accepted by the type `new`, e.g. if
+/// a non-null argument becomes nullable.
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    if old.nullable && !new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(old), BaseType::Named(new)) => old == new,
+        (BaseType::List(old), BaseType::List(new)) => is_safe_input_change(old, new),
+        _ => false,
+    }
+}
+
+fn type_change_severity(old: &str, new: &str, is_safe: fn(&Type, &Type) -> bool) -> ChangeSeverity {
+    match (Type::new(old), Type::new(new)) {
+        (Some(old), Some(new)) if is_safe(&old, &new) => ChangeSeverity::Safe,
+        _ => ChangeSeverity::Breaking,
+    }
+}
+
+#[derive(Default)]
+struct Changes(Vec<SchemaChange>);
+
+impl Changes {
+    fn add(
+        &mut self,
+        severity: ChangeSeverity,
+        kind: ChangeKind,
+        coordinate: String,
+        message: String,
+    ) {
+        self.0.push(SchemaChange {
+            severity,
+            kind,
+            coordinate,
+            message,
+        });
+    }
+
+    /// Compares the arguments of a field or a directive, or the fields of an input object.
+    fn diff_input_values(
+        &mut self,
+        old: &IndexMap<String, InputValueInfo>,
+        new: &IndexMap<String, InputValueInfo>,
+        coordinate: impl Fn(&str) -> String,
+        is_input_field: bool,
+    ) {
+        let (what, kinds) = if is_input_field {
+            (
+                "Input field",
+                [
+                    ChangeKind::InputFieldRemoved,
+                    ChangeKind::InputFieldTypeChanged,
+                    ChangeKind::InputFieldDefaultValueChanged,
+                    ChangeKind::InputFieldAdded,
+                ],
+            )
+        } else {
+            (
+                "Argument",
+                [
+                    ChangeKind::ArgumentRemoved,
+                    ChangeKind::ArgumentTypeChanged,
+                    ChangeKind::ArgumentDefaultValueChanged,
+                    ChangeKind::ArgumentAdded,
+                ],
+            )
+        };
+
+        for (name, old_value) in old {
+            let coordinate = coordinate(name);
+            let new_value = match new.get(name) {
+                Some(new_value) => new_value,
+                None => {
+                    self.add(
+                        ChangeSeverity::Breaking,
+                        kinds[0],
+                        coordinate.clone(),
+                        format!(r#"{} "{}" was removed."#, what, coordinate),
+                    );
+                    continue;
+                }
+            };
+
+            if old_value.ty != new_value.ty {
+                self.add(
+                    type_change_severity(&old_value.ty, &new_value.ty, is_safe_input_change),
+                    kinds[1],
+                    coordinate.clone(),
+                    format!(
+                        r#"{} "{}" changed type from "{}" to "{}"."#,
+                        what, coordinate, old_value.ty, new_value.ty
+                    ),
+                );
+            }
+
+            if old_value.default_value != new_value.default_value {
+                self.add(
+                    ChangeSeverity::Dangerous,
+                    kinds[2],
+                    coordinate.clone(),
+                    format!(
+                        r#"{} "{}" changed default value from {} to {}."#,
+                        what,
+                        coordinate,
+                        old_value.default_value.as_deref().unwrap_or("none"),
+                        new_value.default_value.as_deref().unwrap_or("none"),
+                    ),
+                );
+            }
+        }
+
+        for (name, new_value) in new {
+            if old.contains_key(name) {
+                continue;
+            }
+            let coordinate = coordinate(name);
+            let (severity, required) = if new_value.is_required() {
+                (ChangeSeverity::Breaking, "Required")
+            } else {
+                (ChangeSeverity::Dangerous, "Optional")
+            };
+            self.add(
+                severity,
+                kinds[3],
+                coordinate.clone(),
+                format!(
+                    r#"{} {} "{}" was added."#,
+                    required,
+                    what.to_lowercase(),
+                    coordinate
+                ),
+            );
+        }
+    }
+
+    fn diff_type(&mut self, name: &str, old: &TypeInfo, new: &TypeInfo) {
+        if old.kind != new.kind {
+            self.add(
+                ChangeSeverity::Breaking,
+                ChangeKind::TypeKindChanged,
+                name.to_string(),
+                format!(
+                    r#""{}" changed from {} type to {} type."#,
+                    name,
+                    article(old.kind),
+                    article(new.kind)
+                ),
+            );
+            return;
+        }
+
+        for (field_name, old_field) in &old.fields {
+            let coordinate = format!("{}.{}", name, field_name);
+            let new_field = match new.fields.get(field_name) {
+                Some(new_field) => new_field,
+                None => {
+                    self.add(
+                        ChangeSeverity::Breaking,
+                        ChangeKind::FieldRemoved,
+                        coordinate.clone(),
+                        format!(r#"Field "{}" was removed."#, coordinate),
+                    );
+                    continue;
+                }
+            };
+
+            if old_field.ty != new_field.ty {
+                self.add(
+                    type_change_severity(&old_field.ty, &new_field.ty, is_safe_output_change),
+                    ChangeKind::FieldTypeChanged,
+                    coordinate.clone(),
+                    format!(
+                        r#"Field "{}" changed type from "{}" to "{}"."#,
+                        coordinate, old_field.ty, new_field.ty
+                    ),
+                );
+            }
+
+            self.diff_input_values(
+                &old_field.args,
+                &new_field.args,
+                |arg| format!("{}({}:)", coordinate, arg),
+                false,
+            );
+        }
+        for field_name in new.fields.keys() {
+            if !old.fields.contains_key(field_name) {
+                let coordinate = format!("{}.{}", name, field_name);
+                self.add(
+                    ChangeSeverity::Safe,
+                    ChangeKind::FieldAdded,
+                    coordinate.clone(),
+                    format!(r#"Field "{}" was added."#, coordinate),
+                );
+            }
+        }
+
+        self.diff_input_values(
+            &old.input_fields,
+            &new.input_fields,
+            |field| format!("{}.{}", name, field),
+            true,
+        );
+
+        for value in old.enum_values.difference(&new.enum_values) {
+            let coordinate = format!("{}.{}", name, value);
+            self.add(
+                ChangeSeverity::Breaking,
+                ChangeKind::EnumValueRemoved,
+                coordinate.clone(),
+                format!(r#"Enum value "{}" was removed."#, coordinate),
+            );
+        }
+        for value in new.enum_values.difference(&old.enum_values) {
+            let coordinate = format!("{}.{}", name, value);
+            self.add(
+                ChangeSeverity::Dangerous,
+                ChangeKind::EnumValueAdded,
+                coordinate.clone(),
+                format!(r#"Enum value "{}" was added."#, coordinate),
+            );
+        }
+
+        for member in old.possible_types.difference(&new.possible_types) {
+            self.add(
+                ChangeSeverity::Breaking,
+                ChangeKind::UnionMemberRemoved,
+                name.to_string(),
+                format!(r#""{}" was removed from union type "{}"."#, member, name),
+            );
+        }
+        for member in new.possible_types.difference(&old.possible_types) {
+            self.add(
+                ChangeSeverity::Dangerous,
+                ChangeKind::UnionMemberAdded,
+                name.to_string(),
+                format!(r#""{}" was added to union type "{}"."#, member, name),
+            );
+        }
+
+        for interface in old.implements.difference(&new.implements) {
+            self.add(
+                ChangeSeverity::Breaking,
+                ChangeKind::InterfaceRemoved,
+                name.to_string(),
+                format!(
+                    r#""{}" no longer implements interface "{}"."#,
+                    name, interface
+                ),
+            );
+        }
+        for interface in new.implements.difference(&old.implements) {
+            self.add(
+                ChangeSeverity::Dangerous,
+                ChangeKind::InterfaceAdded,
+                name.to_string(),
+                format!(r#""{}" implements new interface "{}"."#, name, interface),
+            );
+        }
+    }
+
+    fn diff_directive(&mut self, name: &str, old: &DirectiveInfo, new: &DirectiveInfo) {
+        let coordinate = format!("@{}", name);
+
+        for location in old.locations.difference(&new.locations) {
+            self.add(
+                ChangeSeverity::Breaking,
+                ChangeKind::DirectiveLocationRemoved,
+                coordinate.clone(),
+                format!(
+                    r#"Location "{}" was removed from directive "{}"."#,
+                    location, coordinate
+                ),
+            );
+        }
+        for location in new.locations.difference(&old.locations) {
+            self.add(
+                ChangeSeverity::Safe,
+                ChangeKind::DirectiveLocationAdded,
+                coordinate.clone(),
+                format!(
+                    r#"Location "{}" was added to directive "{}"."#,
+                    location, coordinate
+                ),
+            );
+        }
+
+        self.diff_input_values(
+            &old.args,
+            &new.args,
+            |arg| format!("{}({}:)", coordinate, arg),
+            false,
+        );
+    }
+}
+
+fn article(kind: &str) -> String {
+    match kind {
+        "object" | "interface" | "enum" | "input object" => format!("an {}", kind),
+        _ => format!("a {}", kind),
+    }
+}
+
+fn diff_schemas(old: &Schema, new: &Schema) -> Vec<SchemaChange> {
+    let mut changes = Changes::default();
+
+    for (name, old_type) in &old.types {
+        match new.types.get(name) {
+            Some(new_type) => changes.diff_type(name, old_type, new_type),
+            None => changes.add(
+                ChangeSeverity::Breaking,
+                ChangeKind::TypeRemoved,
+                name.clone(),
+                format!(r#"Type "{}" was removed."#, name),
+            ),
+        }
+    }
+    for name in new.types.keys() {
+        if !old.types.contains_key(name) {
+            changes.add(
+                ChangeSeverity::Safe,
+                ChangeKind::TypeAdded,
+                name.clone(),
+                format!(r#"Type "{}" was added."#, name),
+            );
+        }
+    }
+
+    for (name, old_directive) in &old.directives {
+        match new.directives.get(name) {
+            Some(new_directive) => changes.diff_directive(name, old_directive, new_directive),
+            None => changes.add(
+                ChangeSeverity::Breaking,
+                ChangeKind::DirectiveRemoved,
+                format!("@{}", name),
+                format!(r#"Directive "@{}" was removed."#, name),
+            ),
+        }
+    }
+    for name in new.directives.keys() {
+        if !old.directives.contains_key(name) {
+            changes.add(
+                ChangeSeverity::Safe,
+                ChangeKind::DirectiveAdded,
+                format!("@{}", name),
+                format!(r#"Directive "@{}" was added."#, name),
+            );
+        }
+    }
+
+    changes.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(old: &str, new: &str, expected: &[(ChangeSeverity, &str)]) {
+        let changes = diff_sdl(old, new).unwrap();
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| (change.severity, change.message.as_str()))
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn types() {
+        check(
+            "type A { a: Int } type B { b: Int } union U = A",
+            "type A { a: Int } interface B { b: Int } scalar C union U = A",
+            &[
+                (
+                    ChangeSeverity::Breaking,
+                    r#""B" changed from an object type to an interface type."#,
+                ),
+                (ChangeSeverity::Safe, r#"Type "C" was added."#),
+            ],
+        );
+        check(
+            "type A { a: Int } scalar C",
+            "type A { a: Int }",
+            &[(ChangeSeverity::Breaking, r#"Type "C" was removed."#)],
+        );
+    }
+
+    #[test]
+    fn fields() {
+        check(
+            r#"
+            type A {
+                a: Int
+                b: Int!
+                c: [Int]
+                d: Int
+                e: String
+            }"#,
+            r#"
+            type A {
+                a: Int!
+                b: Int
+                c: [Int!]!
+                e: Int
+                f: Int
+            }"#,
+            &[
+                (
+                    ChangeSeverity::Safe,
+                    r#"Field "A.a" changed type from "Int" to "Int!"."#,
+                ),
+                (
+                    ChangeSeverity::Breaking,
+                    r#"Field "A.b" changed type from "Int!" to "Int"."#,
+                ),
+                (
+                    ChangeSeverity::Safe,
+                    r#"Field "A.c" changed type from "[Int]" to "[Int!]!"."#,
+                ),
+                (ChangeSeverity::Breaking, r#"Field "A.d" was removed."#),
+                (
+                    ChangeSeverity::Breaking,
+                    r#"Field "A.e" changed type from "String" to "Int"."#,
+                ),
+                (ChangeSeverity::Safe, r#"Field "A.f" was added."#),
+            ],
+        );
+    }
+
+    #[test]
+    fn arguments() {
+        check(
+            "type Query { a(x: Int!, y: Int, z: Int = 1, w: Int): Int }",
+            "type Query { a(x: Int, y: Int!, z: Int = 2, u: Int!, v: Int! = 1, t: Int): Int }",
+            &[
+                (
+                    ChangeSeverity::Safe,
+                    r#"Argument "Query.a(x:)" changed type from "Int!" to "Int"."#,
+                ),
+                (
+                    ChangeSeverity::Breaking,
+                    r#"Argument "Query.a(y:)" changed type from "Int" to "Int!"."#,
+                ),
+                (
+                    ChangeSeverity::Dangerous,
+                    r#"Argument "Query.a(z:)" changed default value from 1 to 2."#,
+                ),
+                (
+                    ChangeSeverity::Breaking,
+                    r#"Argument "Query.a(w:)" was removed."#,
+                ),
+                (
+                    ChangeSeverity::Breaking,
+                    r#"Required argument "Query.a(u:)" was added."#,
+                ),
+                (
+                    ChangeSeverity::Dangerous,
+                    r#"Optional argument "Query.a(v:)" was added."#,
+                ),
+                (
+                    ChangeSeverity::Dangerous,
+                    r#"Optional argument "Query.a(t:)" was added."#,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn input_fields() {
+        check(
+            "input I { a: [Int!] b: Int }",
+            "input I { a: [Int] c: Int! }",
+            &[
+                (
+                    ChangeSeverity::Safe,
+                    r#"Input field "I.a" changed type from "[Int!]" to "[Int]"."#,
+                ),
+                (
+                    ChangeSeverity::Breaking,
+                    r#"Input field "I.b" was removed."#,
+                ),
+                (
+                    ChangeSeverity::Breaking,
+                    r#"Required input field "I.c" was added."#,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn enums_unions_and_interfaces() {
+        check(
+            r#"
+            enum E { A B }
+            union U = X | Y
+            interface I { a: Int }
+            type X implements I { a: Int }
+            type Y { a: Int }
+            "#,
+            r#"
+            enum E { A C }
+            union U = X
+            extend union U = Z
+            interface I { a: Int }
+            type X { a: Int }
+            type Y implements I { a: Int }
+            type Z { a: Int }
+            "#,
+            &[
+                (ChangeSeverity::Breaking, r#"Enum value "E.B" was removed."#),
+                (ChangeSeverity::Dangerous, r#"Enum value "E.C" was added."#),
+                (
+                    ChangeSeverity::Breaking,
+                    r#""Y" was removed from union type "U"."#,
+                ),
+                (
+                    ChangeSeverity::Dangerous,
+                    r#""Z" was added to union type "U"."#,
+                ),
+                (
+                    ChangeSeverity::Breaking,
+                    r#""X" no longer implements interface "I"."#,
+                ),
+                (
+                    ChangeSeverity::Dangerous,
+                    r#""Y" implements new interface "I"."#,
+                ),
+                (ChangeSeverity::Safe, r#"Type "Z" was added."#),
+            ],
+        );
+    }
+
+    #[test]
+    fn directives() {
+        check(
+            r#"
+            directive @a(x: Int) on FIELD | FRAGMENT_SPREAD
+            directive @b on FIELD
+            directive @skip(if: Boolean!) on FIELD
+            "#,
+            r#"
+            directive @a(x: Int, y: Int!) on FIELD | INLINE_FRAGMENT
+            directive @c on FIELD
+            "#,
+            &[
+                (
+                    ChangeSeverity::Breaking,
+                    r#"Location "FRAGMENT_SPREAD" was removed from directive "@a"."#,
+                ),
+                (
+                    ChangeSeverity::Safe,
+                    r#"Location "INLINE_FRAGMENT" was added to directive "@a"."#,
+                ),
+                (
+                    ChangeSeverity::Breaking,
+                    r#"Required argument "@a(y:)" was added."#,
+                ),
+                (ChangeSeverity::Breaking, r#"Directive "@b" was removed."#),
+                (ChangeSeverity::Safe, r#"Directive "@c" was added."#),
+            ],
+        );
+    }
+}
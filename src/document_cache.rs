@@ -0,0 +1,156 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::parser::types::ExecutableDocument;
+use crate::{ServerError, ValidationResult};
+
+pub(crate) struct CachedDocument {
+    scope: u64,
+    query: String,
+
+    pub(crate) document: Arc<ExecutableDocument>,
+
+    /// The result of all the validation checks if the document has a single operation and declares
+    /// no variables, otherwise the result of the checks which do not depend on the request.
+    pub(crate) validation_result: Result<ValidationResult, Vec<ServerError>>,
+}
+
+struct DocumentCacheInner {
+    cache: Mutex<lru::LruCache<u64, Arc<CachedDocument>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// A bounded LRU cache of parsed and validated query documents.
+///
+/// The cache is keyed by the query source, so identical documents sent by clients are only parsed
+/// and validated once. For documents that declare variables, only the checks which depend on the
+/// values of the variables, such as the argument and upload checks, are run on every request.
+///
+/// Cloning a cache is cheap, the clones share the same storage and metrics. A cache can be shared
+/// by several schemas, the documents validated by a schema are only used by that schema.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let cache = DocumentCache::new(1000);
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .document_cache(cache.clone())
+///         .finish();
+///
+///     schema.execute("{ value }").await;
+///     schema.execute("{ value }").await;
+///     assert_eq!(cache.misses(), 1);
+///     assert_eq!(cache.hits(), 1);
+/// });
+/// ```
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "document_cache")))]
+#[derive(Clone)]
+pub struct DocumentCache {
+    inner: Arc<DocumentCacheInner>,
+    scope: u64,
+}
+
+impl DocumentCache {
+    /// Creates a new cache that holds at most `cap` documents.
+    pub fn new(cap: usize) -> Self {
+        Self {
+            inner: Arc::new(DocumentCacheInner {
+                cache: Mutex::new(lru::LruCache::new(cap)),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+            scope: 0,
+        }
+    }
+
+    /// Returns a clone of the cache whose documents are not visible to the other clones, it is
+    /// used by a schema to keep its validation results to itself.
+    pub(crate) fn scoped(&self) -> Self {
+        static NEXT_SCOPE: AtomicU64 = AtomicU64::new(1);
+        Self {
+            inner: self.inner.clone(),
+            scope: NEXT_SCOPE.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    fn key(&self, query: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.scope.hash(&mut hasher);
+        query.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the number of lookups that found a cached document.
+    pub fn hits(&self) -> u64 {
+        self.inner.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of lookups that did not find a cached document.
+    pub fn misses(&self) -> u64 {
+        self.inner.misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of cached documents.
+    pub fn len(&self) -> usize {
+        self.inner.cache.lock().unwrap().len()
+    }
+
+    /// Returns `true` if there are no cached documents.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached documents.
+    pub fn clear(&self) {
+        self.inner.cache.lock().unwrap().clear();
+    }
+
+    pub(crate) fn get(&self, query: &str) -> Option<Arc<CachedDocument>> {
+        let entry = self
+            .inner
+            .cache
+            .lock()
+            .unwrap()
+            .get(&self.key(query))
+            .filter(|entry| entry.scope == self.scope && entry.query == query)
+            .cloned();
+        match &entry {
+            Some(_) => self.inner.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.inner.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        entry
+    }
+
+    pub(crate) fn insert(
+        &self,
+        query: String,
+        document: Arc<ExecutableDocument>,
+        validation_result: Result<ValidationResult, Vec<ServerError>>,
+    ) {
+        let key = self.key(&query);
+        self.inner.cache.lock().unwrap().put(
+            key,
+            Arc::new(CachedDocument {
+                scope: self.scope,
+                query,
+                document,
+                validation_result,
+            }),
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+
+use crate::dynamic::registry::{meta_field, DefaultValues};
+use crate::dynamic::resolver::{box_resolver, BoxResolverFn};
+use crate::dynamic::{FieldFuture, FieldValue, ResolverContext, SchemaError};
+use crate::registry::{MetaField, MetaInputValue, MetaVisibleFn};
+use crate::{Context, Name, Result, Value};
+
+fn check_type(ty: &str, location: impl FnOnce() -> String) -> Result<(), SchemaError> {
+    match crate::parser::types::Type::new(ty) {
+        Some(_) => Ok(()),
+        None => Err(SchemaError(format!(
+            r#"Invalid type "{}" of "{}"."#,
+            ty,
+            location()
+        ))),
+    }
+}
+
+/// An argument of a field, or a field of an input object, defined at runtime.
+pub struct InputValue {
+    name: String,
+    description: Option<String>,
+    ty: String,
+    default_value: Option<Value>,
+    deprecation: Option<String>,
+    visible: Option<MetaVisibleFn>,
+}
+
+impl InputValue {
+    /// Create an input value, whose type is written as in the SDL, e.g. `[Int!]!`.
+    pub fn new(name: impl Into<String>, ty: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            ty: ty.into(),
+            default_value: None,
+            deprecation: None,
+            visible: None,
+        }
+    }
+
+    /// Set the description of the input value.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the default value of the input value.
+    pub fn default_value(mut self, value: impl Into<Value>) -> Self {
+        self.default_value = Some(value.into());
+        self
+    }
+
+    /// Mark the input value as deprecated.
+    pub fn deprecation(mut self, reason: impl Into<String>) -> Self {
+        self.deprecation = Some(reason.into());
+        self
+    }
+
+    /// Hide the input value from the requests for which `visible` returns `false`.
+    pub fn visible(
+        mut self,
+        visible: impl Fn(&Context<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.visible = Some(Arc::new(visible));
+        self
+    }
+}
+
+/// The converted input values, and their default values.
+type InputValues = (IndexMap<String, MetaInputValue>, IndexMap<Name, Value>);
+
+pub(crate) fn convert_input_values(
+    values: Vec<InputValue>,
+    location: impl Fn(&str) -> String,
+) -> Result<InputValues, SchemaError> {
+    let mut meta_values = IndexMap::new();
+    let mut defaults = IndexMap::new();
+    for value in values {
+        check_type(&value.ty, || location(&value.name))?;
+        if meta_values.contains_key(value.name.as_str()) {
+            return Err(SchemaError(format!(
+                r#"Input value "{}" is defined more than once."#,
+                location(&value.name)
+            )));
+        }
+        meta_values.insert(
+            value.name.clone(),
+            MetaInputValue {
+                name: value.name.clone(),
+                description: value.description,
+                ty: value.ty,
+                default_value: value.default_value.as_ref().map(ToString::to_string),
+                deprecation: value.deprecation,
+                validator: None,
+                visible: value.visible,
+            },
+        );
+        if let Some(default_value) = value.default_value {
+            defaults.insert(Name::new(&value.name), default_value);
+        }
+    }
+    Ok((meta_values, defaults))
+}
+
+/// A field of an object or an interface defined at runtime.
+pub struct Field {
+    name: String,
+    description: Option<String>,
+    args: Vec<InputValue>,
+    ty: String,
+    deprecation: Option<String>,
+    visible: Option<MetaVisibleFn>,
+    resolver: Option<BoxResolverFn>,
+}
+
+impl Field {
+    /// Create a field, whose type is written as in the SDL, e.g. `[User!]!`.
+    ///
+    /// A field without resolver returns the entry with the same name of its parent object.
+    pub fn new(name: impl Into<String>, ty: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            args: Vec::new(),
+            ty: ty.into(),
+            deprecation: None,
+            visible: None,
+            resolver: None,
+        }
+    }
+
+    /// Set the description of the field.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add an argument to the field.
+    pub fn argument(mut self, arg: InputValue) -> Self {
+        self.args.push(arg);
+        self
+    }
+
+    /// Mark the field as deprecated.
+    pub fn deprecation(mut self, reason: impl Into<String>) -> Self {
+        self.deprecation = Some(reason.into());
+        self
+    }
+
+    /// Hide the field from the requests for which `visible` returns `false`.
+    pub fn visible(
+        mut self,
+        visible: impl Fn(&Context<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.visible = Some(Arc::new(visible));
+        self
+    }
+
+    /// Set the resolver of the field.
+    pub fn resolver<F, V>(mut self, resolver: F) -> Self
+    where
+        F: for<'a> Fn(ResolverContext<'a>) -> Result<V> + Send + Sync + 'static,
+        V: Into<FieldValue> + Send + 'static,
+    {
+        self.resolver = Some(box_resolver(resolver));
+        self
+    }
+
+    /// Set the asynchronous resolver of the field.
+    pub fn async_resolver<F>(mut self, resolver: F) -> Self
+    where
+        F: for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static,
+    {
+        self.resolver = Some(Arc::new(resolver));
+        self
+    }
+}
+
+/// Converts the fields of a type, and moves their resolvers to `resolvers` if it is an object
+/// type.
+pub(crate) fn convert_fields(
+    type_name: &str,
+    fields: Vec<Field>,
+    defaults: &mut DefaultValues,
+    mut resolvers: Option<&mut HashMap<String, BoxResolverFn>>,
+) -> Result<IndexMap<String, MetaField>, SchemaError> {
+    let mut meta_fields = IndexMap::new();
+    for field in fields {
+        check_type(&field.ty, || format!("{}.{}", type_name, field.name))?;
+        if meta_fields.contains_key(&field.name) {
+            return Err(SchemaError(format!(
+                r#"Field "{}.{}" is defined more than once."#,
+                type_name, field.name
+            )));
+        }
+
+        if let Some(resolver) = field.resolver {
+            match &mut resolvers {
+                Some(resolvers) => {
+                    resolvers.insert(field.name.clone(), resolver);
+                }
+                None => {
+                    return Err(SchemaError(format!(
+                        r#"Cannot set the resolver of "{}.{}", which is not a field of an object type."#,
+                        type_name, field.name
+                    )))
+                }
+            }
+        }
+
+        let field_name = &field.name;
+        let (args, arg_defaults) = convert_input_values(field.args, |name| {
+            format!("{}.{}({}:)", type_name, field_name, name)
+        })?;
+        defaults.add_args(type_name, &field.name, arg_defaults);
+        meta_fields.insert(
+            field.name.clone(),
+            MetaField {
+                visible: field.visible,
+                ..meta_field(
+                    &field.name,
+                    field.description,
+                    args,
+                    field.ty,
+                    field.deprecation,
+                )
+            },
+        );
+    }
+    Ok(meta_fields)
+}
@@ -0,0 +1,89 @@
+//! Executable schemas whose types are defined at runtime.
+//!
+//! A dynamic schema is loaded from its SDL(Schema Definition Language), and the resolvers of its
+//! fields are attached by name. Its types can also be built with [`Object`], [`Interface`],
+//! [`Union`], [`Enum`], [`InputObject`] and [`Scalar`], e.g. from the metadata of the tables of a
+//! database, and mixed with types defined in Rust with
+//! [`SchemaBuilder::register_type`]. The requests are executed with the same validation, limits,
+//! extensions and introspection as a [`Schema`](crate::Schema) built from Rust types.
+//!
+//! Every object is represented by a `Value`. A field without resolver returns the entry with the
+//! same name of the value of its parent object, and the value of an interface or a union must
+//! have a `__typename` entry naming its concrete type.
+//!
+//! Subscriptions are not supported yet.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::dynamic::{FieldFuture, Schema};
+//! use async_graphql::*;
+//!
+//! let schema = Schema::build_from_sdl(
+//!     r#"
+//!     type Query {
+//!         add(a: Int!, b: Int = 1): Int!
+//!         user: User
+//!     }
+//!
+//!     type User {
+//!         name: String!
+//!     }
+//!     "#,
+//! )
+//! .resolver("Query.add", |ctx| {
+//!     Ok(Value::from(ctx.arg::<i32>("a")? + ctx.arg::<i32>("b")?))
+//! })
+//! .async_resolver("Query.user", |_| {
+//!     FieldFuture::new(async move { Ok(value!({ "name": "Alice" })) })
+//! })
+//! .finish()
+//! .unwrap();
+//!
+//! async_std::task::block_on(async move {
+//!     let res = schema.execute("{ add(a: 10) user { name } }").await.into_result().unwrap().data;
+//!     assert_eq!(res, value!({ "add": 11, "user": { "name": "Alice" } }));
+//! });
+//! ```
+//!
+//! The same schema built with the types defined at runtime:
+//!
+//! ```rust
+//! use async_graphql::dynamic::{Field, InputValue, Object, Schema};
+//! use async_graphql::*;
+//!
+//! let schema = Schema::build("Query", None, None)
+//!     .register(
+//!         Object::new("Query")
+//!             .field(
+//!                 Field::new("add", "Int!")
+//!                     .argument(InputValue::new("a", "Int!"))
+//!                     .argument(InputValue::new("b", "Int").default_value(1))
+//!                     .resolver(|ctx| {
+//!                         Ok(Value::from(ctx.arg::<i32>("a")? + ctx.arg::<i32>("b")?))
+//!                     }),
+//!             )
+//!             .field(Field::new("user", "User").resolver(|_| Ok(value!({ "name": "Alice" })))),
+//!     )
+//!     .register(Object::new("User").field(Field::new("name", "String!")))
+//!     .finish()
+//!     .unwrap();
+//!
+//! async_std::task::block_on(async move {
+//!     let res = schema.execute("{ add(a: 10) user { name } }").await.into_result().unwrap().data;
+//!     assert_eq!(res, value!({ "add": 11, "user": { "name": "Alice" } }));
+//! });
+//! ```
+
+mod field;
+mod registry;
+mod resolve;
+mod resolver;
+mod schema;
+mod sdl;
+mod types;
+
+pub use field::{Field, InputValue};
+pub use resolver::{FieldFuture, FieldValue, ResolverContext};
+pub use schema::{Schema, SchemaBuilder, SchemaError, SchemaInner};
+pub use types::{Enum, EnumItem, InputObject, Interface, Object, Scalar, Type, Union};
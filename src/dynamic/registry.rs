@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::dynamic::SchemaError;
+use crate::registry::{MetaField, MetaInputValue, MetaType, MetaTypeName, MetaVisibleFn, Registry};
+use crate::schema::register_builtins;
+use crate::types::add_introspection_fields;
+use crate::{model, Name, Type, Value};
+
+/// The default values of the input values of a schema, which the registry only stores as
+/// strings.
+#[derive(Default)]
+pub(crate) struct DefaultValues {
+    pub(crate) args: HashMap<String, HashMap<String, IndexMap<Name, Value>>>,
+    pub(crate) input_fields: HashMap<String, IndexMap<Name, Value>>,
+}
+
+impl DefaultValues {
+    pub(crate) fn arg(&self, type_name: &str, field_name: &str, name: &str) -> Option<&Value> {
+        self.args.get(type_name)?.get(field_name)?.get(name)
+    }
+
+    pub(crate) fn input_field(&self, type_name: &str, name: &str) -> Option<&Value> {
+        self.input_fields.get(type_name)?.get(name)
+    }
+
+    pub(crate) fn add_args(
+        &mut self,
+        type_name: &str,
+        field_name: &str,
+        args: IndexMap<Name, Value>,
+    ) {
+        if !args.is_empty() {
+            self.args
+                .entry(type_name.to_string())
+                .or_default()
+                .insert(field_name.to_string(), args);
+        }
+    }
+
+    pub(crate) fn add_input_fields(&mut self, type_name: &str, fields: IndexMap<Name, Value>) {
+        if !fields.is_empty() {
+            self.input_fields
+                .entry(type_name.to_string())
+                .or_default()
+                .extend(fields);
+        }
+    }
+}
+
+/// The root types of a schema.
+#[derive(Default)]
+pub(crate) struct RootTypes {
+    pub(crate) query: Option<String>,
+    pub(crate) mutation: Option<String>,
+    pub(crate) subscription: Option<String>,
+    /// Whether the types named `Query`, `Mutation` and `Subscription` are the root types that are
+    /// not set.
+    pub(crate) by_name: bool,
+}
+
+pub(crate) fn meta_field(
+    name: &str,
+    description: Option<String>,
+    args: IndexMap<String, MetaInputValue>,
+    ty: String,
+    deprecation: Option<String>,
+) -> MetaField {
+    MetaField {
+        name: name.to_string(),
+        description,
+        args,
+        ty,
+        deprecation,
+        cache_control: Default::default(),
+        external: false,
+        requires: None,
+        provides: None,
+        shareable: false,
+        inaccessible: false,
+        tags: Default::default(),
+        override_from: None,
+        visible: None,
+        compute_complexity: None,
+    }
+}
+
+pub(crate) fn object_type(
+    name: &str,
+    description: Option<String>,
+    fields: IndexMap<String, MetaField>,
+    visible: Option<MetaVisibleFn>,
+) -> MetaType {
+    MetaType::Object {
+        name: name.to_string(),
+        description,
+        fields,
+        cache_control: Default::default(),
+        extends: false,
+        keys: None,
+        shareable: false,
+        inaccessible: false,
+        interface_object: false,
+        tags: Default::default(),
+        visible,
+    }
+}
+
+pub(crate) fn interface_type(
+    name: &str,
+    description: Option<String>,
+    fields: IndexMap<String, MetaField>,
+    visible: Option<MetaVisibleFn>,
+) -> MetaType {
+    MetaType::Interface {
+        name: name.to_string(),
+        description,
+        fields,
+        possible_types: Default::default(),
+        extends: false,
+        keys: None,
+        inaccessible: false,
+        tags: Default::default(),
+        visible,
+    }
+}
+
+/// Adds a type defined at runtime to the registry.
+pub(crate) fn add_type(registry: &mut Registry, meta_type: MetaType) -> Result<(), SchemaError> {
+    let name = meta_type.name().to_string();
+    if registry.types.contains_key(&name) {
+        return Err(SchemaError(format!(
+            r#"Type "{}" is defined more than once."#,
+            name
+        )));
+    }
+    registry.types.insert(name, meta_type);
+    Ok(())
+}
+
+/// Creates a registry with the built-in scalars, directives and introspection types.
+pub(crate) fn new_registry() -> (Registry, String) {
+    let mut registry = Registry::default();
+    register_builtins(&mut registry);
+    let schema_type = model::__Schema::create_type_info(&mut registry);
+    (registry, schema_type)
+}
+
+/// Completes a registry once all the types are added, and checks it.
+pub(crate) fn finish_registry(
+    registry: &mut Registry,
+    roots: RootTypes,
+    schema_type: String,
+) -> Result<(), SchemaError> {
+    // The implementations of the interfaces are only known once all the types are added.
+    registry.add_inherited_interfaces();
+
+    let defined = |name: &str| {
+        Some(name.to_string()).filter(|name| roots.by_name && registry.types.contains_key(name))
+    };
+    let mutation_type = roots.mutation.clone().or_else(|| defined("Mutation"));
+    let subscription_type = roots
+        .subscription
+        .clone()
+        .or_else(|| defined("Subscription"));
+    registry.query_type = roots.query.unwrap_or_else(|| "Query".to_string());
+    registry.mutation_type = mutation_type;
+    registry.subscription_type = subscription_type;
+
+    check_registry(registry)?;
+    let query_type = registry.query_type.clone();
+    add_introspection_fields(registry, &query_type, schema_type);
+    Ok(())
+}
+
+fn check_type_ref(
+    registry: &Registry,
+    ty: &str,
+    input: bool,
+    location: impl FnOnce() -> String,
+) -> Result<(), SchemaError> {
+    match registry.concrete_type_by_name(ty) {
+        Some(meta_type) if input && !meta_type.is_input() => Err(SchemaError(format!(
+            r#"The type of "{}" must be an input type, "{}" is not."#,
+            location(),
+            meta_type.name()
+        ))),
+        Some(MetaType::InputObject { name, .. }) if !input => Err(SchemaError(format!(
+            r#"The type of "{}" must be an output type, "{}" is not."#,
+            location(),
+            name
+        ))),
+        Some(_) => Ok(()),
+        None => Err(SchemaError(format!(
+            r#"Unknown type "{}" referenced by "{}"."#,
+            MetaTypeName::concrete_typename(ty),
+            location()
+        ))),
+    }
+}
+
+fn check_registry(registry: &Registry) -> Result<(), SchemaError> {
+    for meta_type in registry.types.values() {
+        let type_name = meta_type.name();
+        if type_name.starts_with("__") {
+            continue;
+        }
+
+        if let Some(fields) = meta_type.fields() {
+            for field in fields.values() {
+                check_type_ref(registry, &field.ty, false, || {
+                    format!("{}.{}", type_name, field.name)
+                })?;
+                for arg in field.args.values() {
+                    check_type_ref(registry, &arg.ty, true, || {
+                        format!("{}.{}({}:)", type_name, field.name, arg.name)
+                    })?;
+                }
+            }
+        }
+
+        match meta_type {
+            MetaType::Union { possible_types, .. } => {
+                for member in possible_types {
+                    if !matches!(registry.types.get(member), Some(MetaType::Object { .. })) {
+                        return Err(SchemaError(format!(
+                            r#"The member "{}" of union "{}" is not an object type."#,
+                            member, type_name
+                        )));
+                    }
+                }
+            }
+            MetaType::InputObject { input_fields, .. } => {
+                for field in input_fields.values() {
+                    check_type_ref(registry, &field.ty, true, || {
+                        format!("{}.{}", type_name, field.name)
+                    })?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (type_name, interfaces) in &registry.implements {
+        let fields = match registry.types.get(type_name) {
+            Some(MetaType::Object { fields, .. }) | Some(MetaType::Interface { fields, .. }) => {
+                fields
+            }
+            _ => continue,
+        };
+        if interfaces.contains(type_name) {
+            return Err(SchemaError(format!(
+                r#"Interface "{}" cannot implement itself."#,
+                type_name
+            )));
+        }
+        for interface in interfaces {
+            let interface_fields = match registry.types.get(interface) {
+                Some(MetaType::Interface { fields, .. }) => fields,
+                _ => {
+                    return Err(SchemaError(format!(
+                        r#"Type "{}" implements "{}", which is not an interface."#,
+                        type_name, interface
+                    )))
+                }
+            };
+            for field_name in interface_fields.keys() {
+                if !fields.contains_key(field_name) {
+                    return Err(SchemaError(format!(
+                        r#"Field "{}.{}" is expected by interface "{}" but is not defined."#,
+                        type_name, field_name, interface
+                    )));
+                }
+            }
+        }
+    }
+
+    for root in std::iter::once(Some(&registry.query_type))
+        .chain(std::iter::once(registry.mutation_type.as_ref()))
+        .chain(std::iter::once(registry.subscription_type.as_ref()))
+        .flatten()
+    {
+        if !matches!(registry.types.get(root), Some(MetaType::Object { .. })) {
+            return Err(SchemaError(format!(
+                r#"The root type "{}" must be a defined object type."#,
+                root
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,372 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use futures_util::future::BoxFuture;
+use indexmap::IndexMap;
+
+use crate::dynamic::resolver::FieldValueInner;
+use crate::dynamic::schema::SchemaInner;
+use crate::dynamic::{FieldValue, ResolverContext};
+use crate::extensions::{ErrorLogger, ExtensionContext, ResolveInfo};
+use crate::parser::types::{Field, Selection};
+use crate::registry::{MetaField, MetaType, MetaTypeName};
+use crate::resolver_utils::insert_value;
+use crate::types::resolve_introspection_field;
+use crate::{
+    Context, ContextSelectionSet, Name, PathSegment, Positioned, ServerError, ServerResult, Value,
+};
+
+type BoxFieldFuture<'a> = Pin<Box<dyn Future<Output = ServerResult<(Name, Value)>> + 'a + Send>>;
+
+/// Resolve the selection set of an object type from the value of the object.
+pub(crate) async fn resolve_object<'a>(
+    schema: &'a SchemaInner,
+    ctx: &ContextSelectionSet<'a>,
+    type_name: &'a str,
+    parent_value: &'a Value,
+    parallel: bool,
+) -> ServerResult<Value> {
+    let mut fields = Vec::new();
+    collect_fields(schema, ctx, type_name, parent_value, &mut fields)?;
+
+    let res = if parallel {
+        futures_util::future::try_join_all(fields).await?
+    } else {
+        let mut results = Vec::with_capacity(fields.len());
+        for field in fields {
+            results.push(field.await?);
+        }
+        results
+    };
+
+    let mut map = IndexMap::new();
+    for (name, value) in res {
+        insert_value(&mut map, name, value);
+    }
+    Ok(Value::Object(map))
+}
+
+fn collect_fields<'a>(
+    schema: &'a SchemaInner,
+    ctx: &ContextSelectionSet<'a>,
+    type_name: &'a str,
+    parent_value: &'a Value,
+    fields: &mut Vec<BoxFieldFuture<'a>>,
+) -> ServerResult<()> {
+    let registry = &ctx.schema_env.registry;
+
+    for selection in &ctx.item.node.items {
+        if ctx.is_skip(&selection.node.directives())? {
+            continue;
+        }
+
+        match &selection.node {
+            Selection::Field(field) => {
+                if field.node.name.node == "__typename" {
+                    let field_name = field.node.response_key().node.clone();
+                    fields.push(Box::pin(async move {
+                        Ok((field_name, Value::String(type_name.to_string())))
+                    }));
+                    continue;
+                }
+
+                if ctx.is_ifdef(&field.node.directives)
+                    && registry
+                        .types
+                        .get(type_name)
+                        .and_then(|ty| ty.field_by_name(&field.node.name.node))
+                        .is_none()
+                {
+                    continue;
+                }
+
+                let ctx = ctx.clone();
+                fields.push(Box::pin(async move {
+                    let ctx_field = ctx.with_field(field);
+                    resolve_field(schema, &ctx_field, type_name, parent_value).await
+                }));
+            }
+            selection => {
+                let (type_condition, selection_set) = match selection {
+                    Selection::Field(_) => unreachable!(),
+                    Selection::FragmentSpread(spread) => {
+                        let fragment = ctx
+                            .query_env
+                            .fragments()
+                            .get(&spread.node.fragment_name.node)
+                            .ok_or_else(|| {
+                                ServerError::new(format!(
+                                    r#"Unknown fragment "{}"."#,
+                                    spread.node.fragment_name.node
+                                ))
+                                .at(spread.pos)
+                            })?;
+                        (
+                            Some(&fragment.node.type_condition),
+                            &fragment.node.selection_set,
+                        )
+                    }
+                    Selection::InlineFragment(fragment) => (
+                        fragment.node.type_condition.as_ref(),
+                        &fragment.node.selection_set,
+                    ),
+                };
+
+                let applies = match type_condition {
+                    Some(condition) => matches!(
+                        registry.types.get(condition.node.on.node.as_str()),
+                        Some(ty) if ty.is_possible_type(type_name)
+                    ),
+                    None => true,
+                };
+                if applies {
+                    collect_fields(
+                        schema,
+                        &ctx.with_selection_set(selection_set),
+                        type_name,
+                        parent_value,
+                        fields,
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn resolve_field(
+    schema: &SchemaInner,
+    ctx: &Context<'_>,
+    type_name: &str,
+    parent_value: &Value,
+) -> ServerResult<(Name, Value)> {
+    let field = ctx.item;
+    let field_name = field.node.response_key().node.clone();
+    let registry = &ctx.schema_env.registry;
+
+    if type_name == registry.query_type {
+        if let Some(value) = resolve_introspection_field(ctx, schema.disable_introspection)
+            .await
+            .map_err(|e| e.path(PathSegment::Field(field_name.to_string())))?
+        {
+            return Ok((field_name, value));
+        }
+    }
+
+    let meta_field = match registry
+        .types
+        .get(type_name)
+        .and_then(|ty| ty.field_by_name(&field.node.name.node))
+    {
+        Some(meta_field) => meta_field,
+        None => {
+            return Err(ServerError::new(format!(
+                r#"Cannot query field "{}" on type "{}"."#,
+                field.node.name.node, type_name
+            ))
+            .at(field.pos)
+            .path(PathSegment::Field(field_name.to_string())));
+        }
+    };
+
+    if ctx.query_env.extensions.is_empty() {
+        let value = resolve_field_value(schema, ctx, type_name, meta_field, parent_value)
+            .await
+            .map_err(|e| e.path(PathSegment::Field(field_name.to_string())))?;
+        Ok((field_name, value))
+    } else {
+        let ctx_extension = ExtensionContext {
+            schema_data: &ctx.schema_env.data,
+            query_data: &ctx.query_env.ctx_data,
+        };
+        let resolve_info = ResolveInfo {
+            resolve_id: ctx.resolve_id,
+            path_node: ctx.path_node.as_ref().unwrap(),
+            parent_type: type_name,
+            return_type: &meta_field.ty,
+        };
+
+        ctx.query_env
+            .extensions
+            .resolve_start(&ctx_extension, &resolve_info);
+
+        let value = resolve_field_value(schema, ctx, type_name, meta_field, parent_value)
+            .await
+            .map_err(|e| e.path(PathSegment::Field(field_name.to_string())))
+            .log_error(&ctx_extension, &ctx.query_env.extensions)?;
+
+        ctx.query_env
+            .extensions
+            .resolve_end(&ctx_extension, &resolve_info);
+
+        Ok((field_name, value))
+    }
+}
+
+async fn resolve_field_value(
+    schema: &SchemaInner,
+    ctx: &Context<'_>,
+    type_name: &str,
+    meta_field: &MetaField,
+    parent_value: &Value,
+) -> ServerResult<Value> {
+    let field = ctx.item;
+
+    let mut args = IndexMap::new();
+    for arg in meta_field.args.values() {
+        let value = match field.node.get_argument(&arg.name) {
+            Some(value) => Some(ctx.resolve_input_value(value.clone())?),
+            None => schema
+                .defaults
+                .arg(type_name, &meta_field.name, &arg.name)
+                .cloned(),
+        };
+        if let Some(value) = value {
+            args.insert(Name::new(&arg.name), schema.coerce_input(&arg.ty, value));
+        }
+    }
+
+    let resolver = schema
+        .resolvers
+        .get(type_name)
+        .and_then(|resolvers| resolvers.get(&meta_field.name));
+    let value: FieldValue = match resolver {
+        Some(resolver) => resolver(ResolverContext {
+            ctx,
+            parent_value,
+            args: &args,
+        })
+        .0
+        .await
+        .map_err(|err| err.into_server_error().at(field.pos))?,
+        None => match parent_value {
+            Value::Object(object) => object
+                .get(meta_field.name.as_str())
+                .cloned()
+                .unwrap_or_default(),
+            _ => Value::Null,
+        }
+        .into(),
+    };
+
+    let ctx_selection_set = ctx.with_selection_set(&field.node.selection_set);
+    match value.0 {
+        FieldValueInner::Value(value) => {
+            resolve_output(schema, &ctx_selection_set, field, &meta_field.ty, value).await
+        }
+        FieldValueInner::Output(value) => value.resolve_erased(&ctx_selection_set, field).await,
+    }
+}
+
+fn resolve_output<'a>(
+    schema: &'a SchemaInner,
+    ctx: &'a ContextSelectionSet<'a>,
+    field: &'a Positioned<Field>,
+    ty: &'a str,
+    value: Value,
+) -> BoxFuture<'a, ServerResult<Value>> {
+    Box::pin(async move {
+        let registry = &ctx.schema_env.registry;
+
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(inner_ty) => {
+                let value = resolve_output(schema, ctx, field, inner_ty, value).await?;
+                if value == Value::Null {
+                    return Err(ServerError::new(format!(
+                        r#"Cannot return null for a field of type "{}"."#,
+                        ty
+                    ))
+                    .at(field.pos));
+                }
+                Ok(value)
+            }
+            MetaTypeName::List(item_ty) => {
+                let items = match value {
+                    Value::Null => return Ok(Value::Null),
+                    Value::List(items) => items,
+                    _ => {
+                        return Err(ServerError::new(format!(
+                            r#"Expected a list for a field of type "{}"."#,
+                            ty
+                        ))
+                        .at(field.pos))
+                    }
+                };
+
+                let mut futures = Vec::with_capacity(items.len());
+                for (idx, item) in items.into_iter().enumerate() {
+                    let ctx_idx = ctx.with_index(idx);
+                    futures.push(async move {
+                        resolve_output(schema, &ctx_idx, field, item_ty, item)
+                            .await
+                            .map_err(|e| e.path(PathSegment::Index(idx)))
+                    });
+                }
+                Ok(Value::List(
+                    futures_util::future::try_join_all(futures).await?,
+                ))
+            }
+            MetaTypeName::Named(_) if value == Value::Null => Ok(Value::Null),
+            MetaTypeName::Named(type_name) => match registry.types.get(type_name) {
+                Some(MetaType::Scalar { is_valid, .. }) => {
+                    if is_valid(&value) {
+                        Ok(value)
+                    } else {
+                        Err(ServerError::new(format!(
+                            r#"Invalid value for a field of type "{}"."#,
+                            type_name
+                        ))
+                        .at(field.pos))
+                    }
+                }
+                Some(MetaType::Enum { enum_values, .. }) => {
+                    let name = match value {
+                        Value::Enum(name) => name,
+                        Value::String(name) => Name::new(name),
+                        _ => Name::new(""),
+                    };
+                    if enum_values.contains_key(name.as_str()) {
+                        Ok(Value::Enum(name))
+                    } else {
+                        Err(ServerError::new(format!(
+                            r#"Invalid value for a field of type "{}"."#,
+                            type_name
+                        ))
+                        .at(field.pos))
+                    }
+                }
+                Some(MetaType::Object { name, .. }) => {
+                    resolve_object(schema, ctx, name, &value, true).await
+                }
+                Some(meta_type @ MetaType::Interface { .. })
+                | Some(meta_type @ MetaType::Union { .. }) => {
+                    let concrete_type = match &value {
+                        Value::Object(object) => match object.get("__typename") {
+                            Some(Value::String(name)) => registry
+                                .types
+                                .get(name)
+                                .filter(|_| meta_type.is_possible_type(name)),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    match concrete_type {
+                        Some(concrete_type) => {
+                            resolve_object(schema, ctx, concrete_type.name(), &value, true).await
+                        }
+                        None => Err(ServerError::new(format!(
+                            r#"The value of the abstract type "{}" must have a "__typename" naming one of its possible types."#,
+                            type_name
+                        ))
+                        .at(field.pos)),
+                    }
+                }
+                Some(MetaType::InputObject { .. }) | None => Err(ServerError::new(format!(
+                    r#"Type "{}" is not an output type."#,
+                    type_name
+                ))
+                .at(field.pos)),
+            },
+        }
+    })
+}
@@ -0,0 +1,100 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use indexmap::IndexMap;
+
+use crate::parser::types::Field;
+use crate::{
+    Context, ContextSelectionSet, InputType, Name, OutputType, Positioned, Result, ServerResult,
+    Value,
+};
+
+/// The context passed to a field resolver of a dynamic schema.
+pub struct ResolverContext<'a> {
+    /// The context of the field being resolved.
+    pub ctx: &'a Context<'a>,
+    /// The value of the object the field belongs to, `Value::Null` for the root types.
+    pub parent_value: &'a Value,
+    /// The arguments of the field, with the default values of the omitted ones.
+    pub args: &'a IndexMap<Name, Value>,
+}
+
+impl<'a> ResolverContext<'a> {
+    /// Parses the value of an argument, which is `None` if it was omitted and has no default
+    /// value.
+    pub fn arg<T: InputType>(&self, name: &str) -> Result<T> {
+        T::parse(self.args.get(name).cloned()).map_err(|err| err.into_server_error().into())
+    }
+}
+
+#[async_trait::async_trait]
+pub(crate) trait ErasedOutputType: Send + Sync {
+    async fn resolve_erased(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> ServerResult<Value>;
+}
+
+#[async_trait::async_trait]
+impl<T: OutputType + Send + Sync> ErasedOutputType for T {
+    async fn resolve_erased(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> ServerResult<Value> {
+        OutputType::resolve(self, ctx, field).await
+    }
+}
+
+pub(crate) enum FieldValueInner {
+    Value(Value),
+    Output(Box<dyn ErasedOutputType>),
+}
+
+/// The value returned by a field resolver.
+///
+/// A `Value` is resolved with the type of the field in the dynamic schema, while a Rust value is
+/// resolved by its own `OutputType` implementation.
+pub struct FieldValue(pub(crate) FieldValueInner);
+
+impl FieldValue {
+    /// Create a `FieldValue` from a Rust value, whose type must be registered in the schema with
+    /// [`SchemaBuilder::register_type`](crate::dynamic::SchemaBuilder::register_type) and be the
+    /// type of the field.
+    pub fn output<T: OutputType + Send + Sync + 'static>(value: T) -> Self {
+        Self(FieldValueInner::Output(Box::new(value)))
+    }
+}
+
+impl From<Value> for FieldValue {
+    fn from(value: Value) -> Self {
+        Self(FieldValueInner::Value(value))
+    }
+}
+
+/// The result of an asynchronous field resolver.
+pub struct FieldFuture<'a>(pub(crate) BoxFuture<'a, Result<FieldValue>>);
+
+impl<'a> FieldFuture<'a> {
+    /// Create a `FieldFuture` from a future.
+    pub fn new<V: Into<FieldValue>>(future: impl Future<Output = Result<V>> + Send + 'a) -> Self {
+        Self(Box::pin(future.map(|res| res.map(Into::into))))
+    }
+}
+
+pub(crate) type BoxResolverFn =
+    Arc<dyn for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync>;
+
+pub(crate) fn box_resolver<F, V>(resolver: F) -> BoxResolverFn
+where
+    F: for<'a> Fn(ResolverContext<'a>) -> Result<V> + Send + Sync + 'static,
+    V: Into<FieldValue> + Send + 'static,
+{
+    Arc::new(move |ctx| {
+        let res = resolver(ctx);
+        FieldFuture::new(async move { res })
+    })
+}
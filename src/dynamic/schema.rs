@@ -0,0 +1,466 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+
+use crate::context::{Data, QueryEnv, QueryEnvInner, ResolveId};
+use crate::diff::{diff_sdl_registry, SchemaChange};
+use crate::dynamic::registry::{finish_registry, new_registry, DefaultValues, RootTypes};
+use crate::dynamic::resolve::resolve_object;
+use crate::dynamic::resolver::{box_resolver, BoxResolverFn};
+use crate::dynamic::sdl::load_sdl;
+use crate::dynamic::{FieldFuture, FieldValue, ResolverContext, Type};
+use crate::extensions::{ExtensionContext, ExtensionFactory};
+use crate::parser::types::OperationType;
+use crate::registry::{MetaType, MetaTypeName, Registry, SDLExportOptions};
+use crate::schema::{SchemaEnvInner, SchemaOptions};
+#[cfg(feature = "document_cache")]
+use crate::DocumentCache;
+use crate::{
+    BatchRequest, BatchResponse, BuiltinRule, CacheControl, Name, Request, Response, Result,
+    SchemaEnv, ServerError, ValidationMode, ValidationRuleFactory, Value,
+};
+
+/// An error building a dynamic schema.
+#[derive(Debug, thiserror::Error, Clone, PartialEq)]
+#[error("{0}")]
+pub struct SchemaError(pub String);
+
+/// Dynamic schema builder
+pub struct SchemaBuilder {
+    sdl: Option<String>,
+    roots: RootTypes,
+    types: Vec<Type>,
+    static_types: Vec<fn(&mut Registry) -> String>,
+    resolvers: HashMap<String, HashMap<String, BoxResolverFn>>,
+    data: Data,
+    options: SchemaOptions,
+    disable_introspection: bool,
+}
+
+impl SchemaBuilder {
+    /// Add a type defined at runtime to the schema.
+    pub fn register(mut self, ty: impl Into<Type>) -> Self {
+        self.types.push(ty.into());
+        self
+    }
+
+    /// Add a type defined in Rust, and the types it references, to the schema.
+    ///
+    /// Input types are parsed from the arguments with [`ResolverContext::arg`], and the values of
+    /// output types are returned by the resolvers with [`FieldValue::output`].
+    pub fn register_type<T: crate::Type>(mut self) -> Self {
+        self.static_types.push(T::create_type_info);
+        self
+    }
+
+    /// Set the resolver of a field, named `Type.field`.
+    ///
+    /// A field without resolver returns the entry with the same name of its parent object.
+    pub fn resolver<F, V>(self, field: &str, resolver: F) -> Self
+    where
+        F: for<'a> Fn(ResolverContext<'a>) -> Result<V> + Send + Sync + 'static,
+        V: Into<FieldValue> + Send + 'static,
+    {
+        self.add_resolver(field, box_resolver(resolver))
+    }
+
+    /// Set the asynchronous resolver of a field, named `Type.field`.
+    pub fn async_resolver<F>(self, field: &str, resolver: F) -> Self
+    where
+        F: for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static,
+    {
+        self.add_resolver(field, Arc::new(resolver))
+    }
+
+    fn add_resolver(mut self, field: &str, resolver: BoxResolverFn) -> Self {
+        let (type_name, field_name) = match field.find('.') {
+            Some(idx) => (&field[..idx], &field[idx + 1..]),
+            None => (field, ""),
+        };
+        self.resolvers
+            .entry(type_name.to_string())
+            .or_default()
+            .insert(field_name.to_string(), resolver);
+        self
+    }
+
+    /// Disable introspection queries.
+    pub fn disable_introspection(mut self) -> Self {
+        self.disable_introspection = true;
+        self
+    }
+
+    /// Set the maximum complexity a query can have. By default, there is no limit.
+    pub fn limit_complexity(mut self, complexity: usize) -> Self {
+        self.options.complexity = Some(complexity);
+        self
+    }
+
+    /// Set the maximum depth a query can have. By default, there is no limit.
+    pub fn limit_depth(mut self, depth: usize) -> Self {
+        self.options.depth = Some(depth);
+        self
+    }
+
+    /// Set the maximum number of aliases a query can have. By default, there is no limit.
+    pub fn limit_aliases(mut self, aliases: usize) -> Self {
+        self.options.aliases = Some(aliases);
+        self
+    }
+
+    /// Set the maximum number of root fields an operation can select. By default, there is no limit.
+    pub fn limit_root_fields(mut self, root_fields: usize) -> Self {
+        self.options.root_fields = Some(root_fields);
+        self
+    }
+
+    /// Set the maximum number of times the same directive can be applied to a single location,
+    /// that is a field, fragment spread, inline fragment, fragment definition, operation or
+    /// variable definition. By default, there is no limit.
+    pub fn limit_directives(mut self, directives: usize) -> Self {
+        self.options.directives = Some(directives);
+        self
+    }
+
+    /// Set the maximum number of selections a query can have. By default, there is no limit.
+    pub fn limit_selections(mut self, selections: usize) -> Self {
+        self.options.selections = Some(selections);
+        self
+    }
+
+    /// Set the maximum number of tokens a query document can have. By default, there is no limit.
+    pub fn limit_tokens(mut self, tokens: usize) -> Self {
+        self.options.tokens = Some(tokens);
+        self
+    }
+
+    /// Set the maximum number of requests a batch can contain. By default, there is no limit.
+    pub fn limit_batch_size(mut self, batch_size: usize) -> Self {
+        self.options.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Set the maximum number of requests of a batch that are executed concurrently. By default,
+    /// at most 8 requests of a batch are executed concurrently. A batch containing a mutation is
+    /// always executed sequentially.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_concurrency` is zero.
+    pub fn limit_batch_concurrency(mut self, batch_concurrency: usize) -> Self {
+        assert!(
+            batch_concurrency > 0,
+            "The batch concurrency must be greater than 0."
+        );
+        self.options.batch_concurrency = batch_concurrency;
+        self
+    }
+
+    /// Add an extension to the schema.
+    pub fn extension(mut self, extension: impl ExtensionFactory) -> Self {
+        self.options.extensions.push(Box::new(extension));
+        self
+    }
+
+    /// Add a global data that can be accessed in the `Schema`. You access it with `Context::data`.
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.data.insert(data);
+        self
+    }
+
+    /// Add a custom validation rule to the schema.
+    pub fn validation_rule(mut self, rule: impl ValidationRuleFactory) -> Self {
+        self.options.validation_rules.push(Box::new(rule));
+        self
+    }
+
+    /// Disable a built-in validation rule.
+    pub fn disable_validation_rule(mut self, rule: BuiltinRule) -> Self {
+        self.options.disabled_rules.insert(rule);
+        self
+    }
+
+    /// Cache parsed and validated documents in the specified cache.
+    #[cfg(feature = "document_cache")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "document_cache")))]
+    pub fn document_cache(mut self, cache: DocumentCache) -> Self {
+        self.options.document_cache = Some(cache.scoped());
+        self
+    }
+
+    /// Set the validation mode, default is `ValidationMode::Strict`.
+    pub fn validation_mode(mut self, validation_mode: ValidationMode) -> Self {
+        self.options.validation_mode = validation_mode;
+        self
+    }
+
+    /// Validate the queries against the visibility of the schema, see
+    /// [`SchemaBuilder::validate_visibility`](../struct.SchemaBuilder.html#method.validate_visibility).
+    pub fn validate_visibility(mut self) -> Self {
+        self.options.validate_visibility = true;
+        self
+    }
+
+    /// Build schema.
+    ///
+    /// Returns an error if the SDL or a type is invalid, or if a resolver is set for a field that
+    /// is not defined on an object type.
+    pub fn finish(self) -> Result<Schema, SchemaError> {
+        let (mut registry, schema_type) = new_registry();
+        let mut defaults = DefaultValues::default();
+        let mut resolvers = HashMap::new();
+
+        for create_type_info in &self.static_types {
+            create_type_info(&mut registry);
+        }
+        let roots = match &self.sdl {
+            Some(sdl) => load_sdl(sdl, &mut registry, &mut defaults)?,
+            None => self.roots,
+        };
+        for ty in self.types {
+            ty.register(&mut registry, &mut defaults, &mut resolvers)?;
+        }
+        finish_registry(&mut registry, roots, schema_type)?;
+
+        for (type_name, resolvers) in &self.resolvers {
+            for field_name in resolvers.keys() {
+                let defined = match registry.types.get(type_name) {
+                    Some(MetaType::Object { fields, .. }) => fields.contains_key(field_name),
+                    _ => false,
+                };
+                if !defined {
+                    return Err(SchemaError(format!(
+                        r#"Cannot set the resolver of "{}.{}", which is not a field of an object type."#,
+                        type_name, field_name
+                    )));
+                }
+            }
+        }
+        for (type_name, field_resolvers) in self.resolvers {
+            resolvers
+                .entry(type_name)
+                .or_default()
+                .extend(field_resolvers);
+        }
+
+        Ok(Schema(Arc::new(SchemaInner {
+            options: self.options,
+            env: SchemaEnv(Arc::new(SchemaEnvInner {
+                registry,
+                data: self.data,
+            })),
+            resolvers,
+            defaults,
+            disable_introspection: self.disable_introspection,
+        })))
+    }
+}
+
+#[doc(hidden)]
+pub struct SchemaInner {
+    options: SchemaOptions,
+    env: SchemaEnv,
+    pub(crate) resolvers: HashMap<String, HashMap<String, BoxResolverFn>>,
+    pub(crate) defaults: DefaultValues,
+    pub(crate) disable_introspection: bool,
+}
+
+impl SchemaInner {
+    /// Applies the default values of the fields of input objects and converts the names of enum
+    /// values passed as strings by variables.
+    pub(crate) fn coerce_input(&self, ty: &str, value: Value) -> Value {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.coerce_input(ty, value),
+            MetaTypeName::List(ty) => match value {
+                Value::List(items) => Value::List(
+                    items
+                        .into_iter()
+                        .map(|item| self.coerce_input(ty, item))
+                        .collect(),
+                ),
+                Value::Null => Value::Null,
+                value => Value::List(vec![self.coerce_input(ty, value)]),
+            },
+            MetaTypeName::Named(type_name) => match (self.env.registry.types.get(type_name), value)
+            {
+                (Some(MetaType::Enum { .. }), Value::String(name)) => Value::Enum(Name::new(name)),
+                (Some(MetaType::InputObject { input_fields, .. }), Value::Object(mut object)) => {
+                    let mut fields = IndexMap::new();
+                    for field in input_fields.values() {
+                        let value = object
+                            .remove(field.name.as_str())
+                            .or_else(|| self.defaults.input_field(type_name, &field.name).cloned());
+                        if let Some(value) = value {
+                            fields.insert(
+                                Name::new(&field.name),
+                                self.coerce_input(&field.ty, value),
+                            );
+                        }
+                    }
+                    Value::Object(fields)
+                }
+                (_, value) => value,
+            },
+        }
+    }
+}
+
+/// Dynamic GraphQL schema.
+///
+/// Cloning a schema is cheap, so it can be easily shared.
+#[derive(Clone)]
+pub struct Schema(Arc<SchemaInner>);
+
+impl Deref for Schema {
+    type Target = SchemaInner;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Schema {
+    /// Create a schema builder with the names of its root types, whose types are added with
+    /// [`SchemaBuilder::register`].
+    pub fn build(query: &str, mutation: Option<&str>, subscription: Option<&str>) -> SchemaBuilder {
+        Self::builder(
+            None,
+            RootTypes {
+                query: Some(query.to_string()),
+                mutation: mutation.map(ToString::to_string),
+                subscription: subscription.map(ToString::to_string),
+                by_name: false,
+            },
+        )
+    }
+
+    /// Create a schema builder from the SDL (Schema Definition Language) of the schema.
+    ///
+    /// The root types are the ones of the `schema` definition, or the types named `Query`,
+    /// `Mutation` and `Subscription` if there is none. More types can be added with
+    /// [`SchemaBuilder::register`].
+    pub fn build_from_sdl(sdl: impl Into<String>) -> SchemaBuilder {
+        Self::builder(Some(sdl.into()), RootTypes::default())
+    }
+
+    fn builder(sdl: Option<String>, roots: RootTypes) -> SchemaBuilder {
+        SchemaBuilder {
+            sdl,
+            roots,
+            types: Vec::new(),
+            static_types: Vec::new(),
+            resolvers: Default::default(),
+            data: Default::default(),
+            options: Default::default(),
+            disable_introspection: false,
+        }
+    }
+
+    /// Returns SDL(Schema Definition Language) of this schema.
+    pub fn sdl(&self) -> String {
+        self.sdl_with_options(SDLExportOptions::new())
+    }
+
+    /// Returns SDL(Schema Definition Language) of this schema, exported with the specified
+    /// options.
+    pub fn sdl_with_options(&self, options: SDLExportOptions) -> String {
+        self.env.export_sdl(options)
+    }
+
+    /// Compares this schema with a previous version of it described by a SDL document, returning
+    /// the changes classified as breaking, dangerous or safe.
+    ///
+    /// See the [`diff`](../diff/index.html) module.
+    pub fn diff_from_sdl(&self, old_sdl: &str) -> crate::parser::Result<Vec<SchemaChange>> {
+        diff_sdl_registry(old_sdl, &self.env.registry)
+    }
+
+    async fn execute_once(&self, env: QueryEnv) -> Response {
+        let inc_resolve_id = AtomicUsize::default();
+        let ctx = env.create_context(
+            &self.env,
+            None,
+            &env.operation().node.selection_set,
+            ResolveId::root(),
+            &inc_resolve_id,
+        );
+        let ctx_extension = ExtensionContext {
+            schema_data: &self.env.data,
+            query_data: &env.ctx_data,
+        };
+
+        env.extensions.execution_start(&ctx_extension);
+
+        let registry = &self.env.registry;
+        let data = match &env.operation().node.ty {
+            OperationType::Query => {
+                resolve_object(&self.0, &ctx, &registry.query_type, &Value::Null, true).await
+            }
+            OperationType::Mutation => match &registry.mutation_type {
+                Some(mutation_type) => {
+                    resolve_object(&self.0, &ctx, mutation_type, &Value::Null, false).await
+                }
+                None => Err(ServerError::new("Schema is not configured for mutations.")),
+            },
+            OperationType::Subscription => {
+                return Response::from_errors(vec![ServerError::new(
+                    "Subscriptions are not supported by dynamic schemas.",
+                )])
+            }
+        };
+
+        env.extensions.execution_end(&ctx_extension);
+        let extensions = env.extensions.result(&ctx_extension);
+
+        let mut response = match data {
+            Ok(data) => Response::new(data),
+            Err(e) => Response::from_errors(vec![e]),
+        }
+        .extensions(extensions);
+        response
+            .errors
+            .splice(0..0, env.errors.lock().unwrap().drain(..));
+        response
+    }
+
+    /// Execute a GraphQL query.
+    pub async fn execute(&self, request: impl Into<Request>) -> Response {
+        match self
+            .options
+            .prepare_request(&self.env, request.into(), None, None)
+            .await
+        {
+            Ok((env, cache_control)) => self.execute_prepared(env, cache_control).await,
+            Err(response) => response,
+        }
+    }
+
+    async fn execute_prepared(&self, env: QueryEnvInner, cache_control: CacheControl) -> Response {
+        self.execute_once(QueryEnv::new(env))
+            .await
+            .cache_control(cache_control)
+    }
+
+    /// Execute a GraphQL batch query.
+    pub async fn execute_batch(&self, batch_request: BatchRequest) -> BatchResponse {
+        self.execute_batch_with_data(batch_request, Data::default())
+            .await
+    }
+
+    /// Execute a GraphQL batch query with some data shared by all the requests of the batch.
+    pub async fn execute_batch_with_data(
+        &self,
+        batch_request: BatchRequest,
+        data: Data,
+    ) -> BatchResponse {
+        self.options
+            .execute_batch(&self.env, batch_request, data, |env, cache_control| {
+                self.execute_prepared(env, cache_control)
+            })
+            .await
+    }
+}
@@ -0,0 +1,338 @@
+use indexmap::IndexMap;
+
+use crate::dynamic::registry::{
+    add_type, interface_type, meta_field, object_type, DefaultValues, RootTypes,
+};
+use crate::dynamic::SchemaError;
+use crate::parser::parse_schema;
+use crate::parser::types::{
+    ConstDirective, FieldDefinition, InputValueDefinition, SchemaDefinition, TypeDefinition,
+    TypeKind, TypeSystemDefinition,
+};
+use crate::registry::{
+    MetaDirective, MetaEnumValue, MetaField, MetaInputValue, MetaType, Registry,
+};
+use crate::{Name, Positioned, Value};
+
+fn description(description: &Option<Positioned<String>>) -> Option<String> {
+    description
+        .as_ref()
+        .map(|description| description.node.clone())
+}
+
+fn deprecation(directives: &[Positioned<ConstDirective>]) -> Option<String> {
+    directives
+        .iter()
+        .find(|directive| directive.node.name.node == "deprecated")
+        .map(|directive| match directive.node.get_argument("reason") {
+            Some(Positioned {
+                node: Value::String(reason),
+                ..
+            }) => reason.clone(),
+            _ => "No longer supported".to_string(),
+        })
+}
+
+fn specified_by_url(directives: &[Positioned<ConstDirective>]) -> Option<String> {
+    directives
+        .iter()
+        .find(|directive| directive.node.name.node == "specifiedBy")
+        .and_then(|directive| match directive.node.get_argument("url") {
+            Some(Positioned {
+                node: Value::String(url),
+                ..
+            }) => Some(url.clone()),
+            _ => None,
+        })
+}
+
+fn convert_input_values(
+    values: &[Positioned<InputValueDefinition>],
+) -> (IndexMap<String, MetaInputValue>, IndexMap<Name, Value>) {
+    let mut meta_values = IndexMap::new();
+    let mut defaults = IndexMap::new();
+    for value in values {
+        let value = &value.node;
+        if let Some(default_value) = &value.default_value {
+            defaults.insert(value.name.node.clone(), default_value.node.clone());
+        }
+        meta_values.insert(
+            value.name.node.to_string(),
+            MetaInputValue {
+                name: value.name.node.to_string(),
+                description: description(&value.description),
+                ty: value.ty.node.to_string(),
+                default_value: value
+                    .default_value
+                    .as_ref()
+                    .map(|value| value.node.to_string()),
+                deprecation: deprecation(&value.directives),
+                validator: None,
+                visible: None,
+            },
+        );
+    }
+    (meta_values, defaults)
+}
+
+fn convert_fields(
+    type_name: &str,
+    fields: &[Positioned<FieldDefinition>],
+    meta_fields: &mut IndexMap<String, MetaField>,
+    defaults: &mut DefaultValues,
+) -> Result<(), SchemaError> {
+    for field in fields {
+        let field = &field.node;
+        if meta_fields.contains_key(field.name.node.as_str()) {
+            return Err(SchemaError(format!(
+                r#"Field "{}.{}" is defined more than once."#,
+                type_name, field.name.node
+            )));
+        }
+
+        let (args, arg_defaults) = convert_input_values(&field.arguments);
+        defaults.add_args(type_name, &field.name.node, arg_defaults);
+        meta_fields.insert(
+            field.name.node.to_string(),
+            meta_field(
+                &field.name.node,
+                description(&field.description),
+                args,
+                field.ty.node.to_string(),
+                deprecation(&field.directives),
+            ),
+        );
+    }
+    Ok(())
+}
+
+fn convert_type(
+    ty: &TypeDefinition,
+    registry: &mut Registry,
+    defaults: &mut DefaultValues,
+) -> Result<(), SchemaError> {
+    let name = ty.name.node.to_string();
+    let meta_type = match &ty.kind {
+        TypeKind::Scalar => MetaType::Scalar {
+            name: name.clone(),
+            description: description(&ty.description),
+            is_valid: |_| true,
+            visible: None,
+            specified_by_url: specified_by_url(&ty.directives),
+        },
+        TypeKind::Object(object) => {
+            let mut fields = IndexMap::new();
+            convert_fields(&name, &object.fields, &mut fields, defaults)?;
+            for interface in &object.implements {
+                registry.add_implements(&name, &interface.node);
+            }
+            object_type(&name, description(&ty.description), fields, None)
+        }
+        TypeKind::Interface(interface) => {
+            let mut fields = IndexMap::new();
+            convert_fields(&name, &interface.fields, &mut fields, defaults)?;
+            for implement in &interface.implements {
+                registry.add_implements(&name, &implement.node);
+            }
+            interface_type(&name, description(&ty.description), fields, None)
+        }
+        TypeKind::Union(union) => MetaType::Union {
+            name: name.clone(),
+            description: description(&ty.description),
+            possible_types: union
+                .members
+                .iter()
+                .map(|member| member.node.to_string())
+                .collect(),
+            visible: None,
+        },
+        TypeKind::Enum(enum_type) => MetaType::Enum {
+            name: name.clone(),
+            description: description(&ty.description),
+            enum_values: enum_type
+                .values
+                .iter()
+                .map(|value| {
+                    let value = &value.node;
+                    (
+                        value.value.node.to_string(),
+                        MetaEnumValue {
+                            name: value.value.node.to_string(),
+                            description: description(&value.description),
+                            deprecation: deprecation(&value.directives),
+                            visible: None,
+                        },
+                    )
+                })
+                .collect(),
+            visible: None,
+        },
+        TypeKind::InputObject(input_object) => {
+            let (input_fields, field_defaults) = convert_input_values(&input_object.fields);
+            defaults.add_input_fields(&name, field_defaults);
+            MetaType::InputObject {
+                name: name.clone(),
+                description: description(&ty.description),
+                input_fields,
+                visible: None,
+                oneof: ty
+                    .directives
+                    .iter()
+                    .any(|directive| directive.node.name.node == "oneOf"),
+            }
+        }
+    };
+    add_type(registry, meta_type)
+}
+
+fn extend_type(
+    ty: &TypeDefinition,
+    registry: &mut Registry,
+    defaults: &mut DefaultValues,
+) -> Result<(), SchemaError> {
+    let name = ty.name.node.as_str();
+    let meta_type = registry.types.get_mut(name).ok_or_else(|| {
+        SchemaError(format!(
+            r#"Cannot extend type "{}" because it is not defined."#,
+            name
+        ))
+    })?;
+
+    match (&ty.kind, meta_type) {
+        (
+            TypeKind::Scalar,
+            MetaType::Scalar {
+                specified_by_url: url,
+                ..
+            },
+        ) => {
+            if let Some(extended_url) = specified_by_url(&ty.directives) {
+                *url = Some(extended_url);
+            }
+        }
+        (TypeKind::Object(object), MetaType::Object { fields, .. }) => {
+            convert_fields(name, &object.fields, fields, defaults)?;
+            for interface in &object.implements {
+                registry.add_implements(name, &interface.node);
+            }
+        }
+        (TypeKind::Interface(interface), MetaType::Interface { fields, .. }) => {
+            convert_fields(name, &interface.fields, fields, defaults)?;
+            for implement in &interface.implements {
+                registry.add_implements(name, &implement.node);
+            }
+        }
+        (TypeKind::Union(union), MetaType::Union { possible_types, .. }) => {
+            possible_types.extend(union.members.iter().map(|member| member.node.to_string()));
+        }
+        (TypeKind::Enum(enum_type), MetaType::Enum { enum_values, .. }) => {
+            for value in &enum_type.values {
+                let value = &value.node;
+                enum_values.insert(
+                    value.value.node.to_string(),
+                    MetaEnumValue {
+                        name: value.value.node.to_string(),
+                        description: description(&value.description),
+                        deprecation: deprecation(&value.directives),
+                        visible: None,
+                    },
+                );
+            }
+        }
+        (TypeKind::InputObject(input_object), MetaType::InputObject { input_fields, .. }) => {
+            let (new_fields, field_defaults) = convert_input_values(&input_object.fields);
+            input_fields.extend(
+                new_fields
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value)),
+            );
+            defaults.add_input_fields(name, field_defaults);
+        }
+        _ => {
+            return Err(SchemaError(format!(
+                r#"Cannot extend type "{}" with a different kind of type."#,
+                name
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// Adds the definitions of a SDL document to a registry, and returns the root types of its
+/// `schema` definition.
+pub(crate) fn load_sdl(
+    sdl: &str,
+    registry: &mut Registry,
+    defaults: &mut DefaultValues,
+) -> Result<RootTypes, SchemaError> {
+    let document = parse_schema(sdl).map_err(|err| SchemaError(err.to_string()))?;
+
+    let mut schema_definition: Option<SchemaDefinition> = None;
+    let mut schema_extensions = Vec::new();
+    let mut type_definitions = Vec::new();
+    let mut type_extensions = Vec::new();
+    let mut directive_definitions = Vec::new();
+    for definition in document.definitions {
+        match definition {
+            TypeSystemDefinition::Schema(schema) if schema.node.extend => {
+                schema_extensions.push(schema.node)
+            }
+            TypeSystemDefinition::Schema(schema) => {
+                if schema_definition.is_some() {
+                    return Err(SchemaError(
+                        "The schema is defined more than once.".to_string(),
+                    ));
+                }
+                schema_definition = Some(schema.node);
+            }
+            TypeSystemDefinition::Type(ty) if ty.node.extend => type_extensions.push(ty.node),
+            TypeSystemDefinition::Type(ty) => type_definitions.push(ty.node),
+            TypeSystemDefinition::Directive(directive) => {
+                directive_definitions.push(directive.node)
+            }
+        }
+    }
+
+    for ty in &type_definitions {
+        const BUILTIN_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+        if BUILTIN_SCALARS.contains(&ty.name.node.as_str()) {
+            continue;
+        }
+        convert_type(ty, registry, defaults)?;
+    }
+    for ty in &type_extensions {
+        extend_type(ty, registry, defaults)?;
+    }
+
+    for directive in directive_definitions {
+        let (args, _) = convert_input_values(&directive.arguments);
+        registry.add_directive(MetaDirective {
+            name: directive.name.node.to_string(),
+            description: description(&directive.description),
+            locations: directive
+                .locations
+                .iter()
+                .map(|location| location.node.into())
+                .collect(),
+            args,
+            is_repeatable: directive.is_repeatable,
+        });
+    }
+
+    let mut roots = RootTypes {
+        by_name: schema_definition.is_none(),
+        ..RootTypes::default()
+    };
+    for schema in schema_definition.iter().chain(&schema_extensions) {
+        if let Some(query) = &schema.query {
+            roots.query = Some(query.node.to_string());
+        }
+        if let Some(mutation) = &schema.mutation {
+            roots.mutation = Some(mutation.node.to_string());
+        }
+        if let Some(subscription) = &schema.subscription {
+            roots.subscription = Some(subscription.node.to_string());
+        }
+    }
+    Ok(roots)
+}
@@ -0,0 +1,463 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::dynamic::field::{convert_fields, convert_input_values};
+use crate::dynamic::registry::{add_type, interface_type, object_type, DefaultValues};
+use crate::dynamic::resolver::BoxResolverFn;
+use crate::dynamic::{Field, InputValue, SchemaError};
+use crate::registry::{MetaEnumValue, MetaType, MetaVisibleFn, Registry};
+use crate::{Context, Value};
+
+/// An object type defined at runtime.
+pub struct Object {
+    name: String,
+    description: Option<String>,
+    fields: Vec<Field>,
+    implements: IndexSet<String>,
+    visible: Option<MetaVisibleFn>,
+}
+
+impl Object {
+    /// Create an object type.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Vec::new(),
+            implements: Default::default(),
+            visible: None,
+        }
+    }
+
+    /// Set the description of the object.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a field to the object.
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Add an interface implemented by the object.
+    pub fn implement(mut self, interface: impl Into<String>) -> Self {
+        self.implements.insert(interface.into());
+        self
+    }
+
+    /// Hide the object from the requests for which `visible` returns `false`.
+    pub fn visible(
+        mut self,
+        visible: impl Fn(&Context<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.visible = Some(Arc::new(visible));
+        self
+    }
+}
+
+/// An interface type defined at runtime.
+///
+/// The fields of an interface have no resolvers, they are resolved by the object types that
+/// implement it.
+pub struct Interface {
+    name: String,
+    description: Option<String>,
+    fields: Vec<Field>,
+    implements: IndexSet<String>,
+    visible: Option<MetaVisibleFn>,
+}
+
+impl Interface {
+    /// Create an interface type.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Vec::new(),
+            implements: Default::default(),
+            visible: None,
+        }
+    }
+
+    /// Set the description of the interface.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a field to the interface.
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Add an interface implemented by the interface.
+    pub fn implement(mut self, interface: impl Into<String>) -> Self {
+        self.implements.insert(interface.into());
+        self
+    }
+
+    /// Hide the interface from the requests for which `visible` returns `false`.
+    pub fn visible(
+        mut self,
+        visible: impl Fn(&Context<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.visible = Some(Arc::new(visible));
+        self
+    }
+}
+
+/// A union type defined at runtime.
+pub struct Union {
+    name: String,
+    description: Option<String>,
+    possible_types: IndexSet<String>,
+    visible: Option<MetaVisibleFn>,
+}
+
+impl Union {
+    /// Create a union type.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            possible_types: Default::default(),
+            visible: None,
+        }
+    }
+
+    /// Set the description of the union.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a member to the union, which must be an object type.
+    pub fn possible_type(mut self, ty: impl Into<String>) -> Self {
+        self.possible_types.insert(ty.into());
+        self
+    }
+
+    /// Hide the union from the requests for which `visible` returns `false`.
+    pub fn visible(
+        mut self,
+        visible: impl Fn(&Context<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.visible = Some(Arc::new(visible));
+        self
+    }
+}
+
+/// An item of an enum type defined at runtime.
+pub struct EnumItem {
+    name: String,
+    description: Option<String>,
+    deprecation: Option<String>,
+    visible: Option<MetaVisibleFn>,
+}
+
+impl EnumItem {
+    /// Create an enum item.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            deprecation: None,
+            visible: None,
+        }
+    }
+
+    /// Set the description of the item.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Mark the item as deprecated.
+    pub fn deprecation(mut self, reason: impl Into<String>) -> Self {
+        self.deprecation = Some(reason.into());
+        self
+    }
+
+    /// Hide the item from the requests for which `visible` returns `false`.
+    pub fn visible(
+        mut self,
+        visible: impl Fn(&Context<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.visible = Some(Arc::new(visible));
+        self
+    }
+}
+
+impl<T: Into<String>> From<T> for EnumItem {
+    fn from(name: T) -> Self {
+        EnumItem::new(name)
+    }
+}
+
+/// An enum type defined at runtime.
+pub struct Enum {
+    name: String,
+    description: Option<String>,
+    items: Vec<EnumItem>,
+    visible: Option<MetaVisibleFn>,
+}
+
+impl Enum {
+    /// Create an enum type.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            items: Vec::new(),
+            visible: None,
+        }
+    }
+
+    /// Set the description of the enum.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add an item to the enum.
+    pub fn item(mut self, item: impl Into<EnumItem>) -> Self {
+        self.items.push(item.into());
+        self
+    }
+
+    /// Hide the enum from the requests for which `visible` returns `false`.
+    pub fn visible(
+        mut self,
+        visible: impl Fn(&Context<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.visible = Some(Arc::new(visible));
+        self
+    }
+}
+
+/// An input object type defined at runtime.
+pub struct InputObject {
+    name: String,
+    description: Option<String>,
+    fields: Vec<InputValue>,
+    oneof: bool,
+    visible: Option<MetaVisibleFn>,
+}
+
+impl InputObject {
+    /// Create an input object type.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Vec::new(),
+            oneof: false,
+            visible: None,
+        }
+    }
+
+    /// Set the description of the input object.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a field to the input object.
+    pub fn field(mut self, field: InputValue) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Require exactly one of the fields to be set, like `input Name @oneOf` in the SDL.
+    pub fn oneof(mut self) -> Self {
+        self.oneof = true;
+        self
+    }
+
+    /// Hide the input object from the requests for which `visible` returns `false`.
+    pub fn visible(
+        mut self,
+        visible: impl Fn(&Context<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.visible = Some(Arc::new(visible));
+        self
+    }
+}
+
+/// A scalar type defined at runtime.
+pub struct Scalar {
+    name: String,
+    description: Option<String>,
+    validator: fn(&Value) -> bool,
+    specified_by_url: Option<String>,
+    visible: Option<MetaVisibleFn>,
+}
+
+impl Scalar {
+    /// Create a scalar type, which accepts any value.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            validator: |_| true,
+            specified_by_url: None,
+            visible: None,
+        }
+    }
+
+    /// Set the description of the scalar.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the function checking whether a value is valid for the scalar.
+    pub fn validator(mut self, validator: fn(&Value) -> bool) -> Self {
+        self.validator = validator;
+        self
+    }
+
+    /// Set the URL of the specification of the scalar, like `@specifiedBy(url: "...")` in the
+    /// SDL.
+    pub fn specified_by_url(mut self, url: impl Into<String>) -> Self {
+        self.specified_by_url = Some(url.into());
+        self
+    }
+
+    /// Hide the scalar from the requests for which `visible` returns `false`.
+    pub fn visible(
+        mut self,
+        visible: impl Fn(&Context<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.visible = Some(Arc::new(visible));
+        self
+    }
+}
+
+/// A type defined at runtime.
+pub enum Type {
+    /// An object type.
+    Object(Object),
+    /// An interface type.
+    Interface(Interface),
+    /// A union type.
+    Union(Union),
+    /// An enum type.
+    Enum(Enum),
+    /// An input object type.
+    InputObject(InputObject),
+    /// A scalar type.
+    Scalar(Scalar),
+}
+
+macro_rules! impl_from_type {
+    ($($ty:ident),*) => {
+        $(
+            impl From<$ty> for Type {
+                fn from(ty: $ty) -> Self {
+                    Type::$ty(ty)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_type!(Object, Interface, Union, Enum, InputObject, Scalar);
+
+impl Type {
+    /// Adds the type to the registry, and its resolvers to `resolvers`.
+    pub(crate) fn register(
+        self,
+        registry: &mut Registry,
+        defaults: &mut DefaultValues,
+        resolvers: &mut HashMap<String, HashMap<String, BoxResolverFn>>,
+    ) -> Result<(), SchemaError> {
+        let meta_type = match self {
+            Type::Object(object) => {
+                let mut object_resolvers = HashMap::new();
+                let fields = convert_fields(
+                    &object.name,
+                    object.fields,
+                    defaults,
+                    Some(&mut object_resolvers),
+                )?;
+                for interface in &object.implements {
+                    registry.add_implements(&object.name, interface);
+                }
+                if !object_resolvers.is_empty() {
+                    resolvers
+                        .entry(object.name.clone())
+                        .or_default()
+                        .extend(object_resolvers);
+                }
+                object_type(&object.name, object.description, fields, object.visible)
+            }
+            Type::Interface(interface) => {
+                let fields = convert_fields(&interface.name, interface.fields, defaults, None)?;
+                for implement in &interface.implements {
+                    registry.add_implements(&interface.name, implement);
+                }
+                interface_type(
+                    &interface.name,
+                    interface.description,
+                    fields,
+                    interface.visible,
+                )
+            }
+            Type::Union(union) => MetaType::Union {
+                name: union.name,
+                description: union.description,
+                possible_types: union.possible_types,
+                visible: union.visible,
+            },
+            Type::Enum(enum_type) => {
+                let mut enum_values = IndexMap::new();
+                for item in enum_type.items {
+                    enum_values.insert(
+                        item.name.clone(),
+                        MetaEnumValue {
+                            name: item.name,
+                            description: item.description,
+                            deprecation: item.deprecation,
+                            visible: item.visible,
+                        },
+                    );
+                }
+                MetaType::Enum {
+                    name: enum_type.name,
+                    description: enum_type.description,
+                    enum_values,
+                    visible: enum_type.visible,
+                }
+            }
+            Type::InputObject(input_object) => {
+                let type_name = input_object.name;
+                let (input_fields, field_defaults) =
+                    convert_input_values(input_object.fields, |name| {
+                        format!("{}.{}", type_name, name)
+                    })?;
+                defaults.add_input_fields(&type_name, field_defaults);
+                MetaType::InputObject {
+                    name: type_name.clone(),
+                    description: input_object.description,
+                    input_fields,
+                    visible: input_object.visible,
+                    oneof: input_object.oneof,
+                }
+            }
+            Type::Scalar(scalar) => MetaType::Scalar {
+                name: scalar.name,
+                description: scalar.description,
+                is_valid: scalar.validator,
+                visible: scalar.visible,
+                specified_by_url: scalar.specified_by_url,
+            },
+        };
+        add_type(registry, meta_type)
+    }
+}
@@ -257,6 +257,16 @@ pub enum ParseRequestError {
     #[error("Payload too large")]
     PayloadTooLarge,
 
+    /// The content type of an uploaded file is not allowed by this server.
+    #[error("The content type \"{0}\" of the uploaded file is not allowed")]
+    UnsupportedUploadType(String),
+
+    /// The request has been blocked as a potential Cross-Site Request Forgery, because it could
+    /// have been sent by a browser without a preflight request and has none of the required
+    /// headers.
+    #[error("This request has been blocked as a potential Cross-Site Request Forgery. Specify a content type other than \"application/x-www-form-urlencoded\", \"multipart/form-data\" or \"text/plain\", or provide a non-empty value for one of the following headers: {0}")]
+    CsrfPrevented(String),
+
     /// The request is a batch request, but the server does not support batch requests.
     #[error("Batch requests are not supported")]
     UnsupportedBatch,
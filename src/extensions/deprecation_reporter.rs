@@ -0,0 +1,319 @@
+use std::sync::Arc;
+
+use crate::extensions::deprecation_warnings::deprecations_value;
+use crate::extensions::{Extension, ExtensionContext, ExtensionFactory};
+use crate::parser::types::{DocumentOperations, ExecutableDocument};
+use crate::{DeprecatedUsage, Request, ServerResult, ValidationResult, Value};
+
+/// The uses of deprecated elements of the schema by a query.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeprecationReport {
+    /// The name of the executed operation, if it has one.
+    pub operation_name: Option<String>,
+
+    /// The identifier of the client which sent the query, if it is known.
+    pub client: Option<String>,
+
+    /// The uses of deprecated fields, arguments, input fields and enum values.
+    pub usages: Vec<DeprecatedUsage>,
+}
+
+/// Receives the reports of the [`DeprecationReporter`](struct.DeprecationReporter.html)
+/// extension, e.g. to log them or to collect metrics.
+///
+/// It is implemented for the functions taking a `DeprecationReport`.
+pub trait DeprecationSink: Send + Sync + 'static {
+    /// Called after the validation of each query using deprecated elements of the schema.
+    fn report(&self, report: DeprecationReport);
+}
+
+impl<F> DeprecationSink for F
+where
+    F: Fn(DeprecationReport) + Send + Sync + 'static,
+{
+    fn report(&self, report: DeprecationReport) {
+        self(report)
+    }
+}
+
+type ClientFn = dyn Fn(&ExtensionContext<'_>) -> Option<String> + Send + Sync;
+
+/// Deprecation reporter extension
+///
+/// This extension detects the uses of deprecated fields, arguments, input fields and enum values
+/// in each query during the validation, and reports them to a [`DeprecationSink`](trait.DeprecationSink.html),
+/// with the name of the operation and the identifier of the client.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::DeprecationReporter;
+///
+/// struct ClientName(String);
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     #[graphql(deprecation = "Use `value`.")]
+///     async fn old_value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(
+///         DeprecationReporter::new(|report| println!("{:?}", report))
+///             .client(|ctx| ctx.data_opt::<ClientName>().map(|client| client.0.clone())),
+///     )
+///     .finish();
+/// ```
+pub struct DeprecationReporter {
+    sink: Arc<dyn DeprecationSink>,
+    client: Option<Arc<ClientFn>>,
+    response_extensions: bool,
+}
+
+impl DeprecationReporter {
+    /// Create a `DeprecationReporter` sending its reports to `sink`.
+    pub fn new(sink: impl DeprecationSink) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            client: None,
+            response_extensions: false,
+        }
+    }
+
+    /// Set the function getting the identifier of the client from the data of the request, or of
+    /// the schema.
+    pub fn client(
+        mut self,
+        client: impl Fn(&ExtensionContext<'_>) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.client = Some(Arc::new(client));
+        self
+    }
+
+    /// Also output the `deprecations` field in the response extension, like the
+    /// [`DeprecationWarnings`](struct.DeprecationWarnings.html) extension.
+    pub fn response_extensions(mut self) -> Self {
+        self.response_extensions = true;
+        self
+    }
+}
+
+impl ExtensionFactory for DeprecationReporter {
+    fn create(&self) -> Box<dyn Extension> {
+        Box::new(DeprecationReporterExtension {
+            sink: self.sink.clone(),
+            client: self.client.clone(),
+            response_extensions: self.response_extensions,
+            operation_name: None,
+            deprecations: Vec::new(),
+        })
+    }
+}
+
+struct DeprecationReporterExtension {
+    sink: Arc<dyn DeprecationSink>,
+    client: Option<Arc<ClientFn>>,
+    response_extensions: bool,
+    operation_name: Option<String>,
+    deprecations: Vec<DeprecatedUsage>,
+}
+
+#[async_trait::async_trait]
+impl Extension for DeprecationReporterExtension {
+    fn name(&self) -> Option<&'static str> {
+        if self.response_extensions {
+            Some("deprecations")
+        } else {
+            None
+        }
+    }
+
+    async fn prepare_request(
+        &mut self,
+        _ctx: &ExtensionContext<'_>,
+        request: Request,
+    ) -> ServerResult<Request> {
+        self.operation_name = request.operation_name.clone();
+        Ok(request)
+    }
+
+    fn parse_end(&mut self, _ctx: &ExtensionContext<'_>, document: &ExecutableDocument) {
+        if self.operation_name.is_none() {
+            if let DocumentOperations::Multiple(operations) = &document.operations {
+                if operations.len() == 1 {
+                    self.operation_name = operations.keys().next().map(ToString::to_string);
+                }
+            }
+        }
+    }
+
+    fn validation_end(&mut self, ctx: &ExtensionContext<'_>, result: &ValidationResult) {
+        if result.deprecations.is_empty() {
+            return;
+        }
+        self.sink.report(DeprecationReport {
+            operation_name: self.operation_name.clone(),
+            client: self.client.as_ref().and_then(|client| client(ctx)),
+            usages: result.deprecations.clone(),
+        });
+        if self.response_extensions {
+            self.deprecations = result.deprecations.clone();
+        }
+    }
+
+    fn result(&mut self, _ctx: &ExtensionContext<'_>) -> Option<Value> {
+        deprecations_value(&self.deprecations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::*;
+
+    struct ClientName(&'static str);
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(internal)]
+    enum Color {
+        Red,
+        #[graphql(deprecation = "Use `RED`.")]
+        Crimson,
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        #[graphql(deprecation = "Use `value`.")]
+        async fn old_value(&self) -> i32 {
+            10
+        }
+
+        async fn color(&self, color: Color) -> Color {
+            color
+        }
+    }
+
+    #[async_std::test]
+    async fn deprecation_reporter() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(
+                DeprecationReporter::new({
+                    let reports = reports.clone();
+                    move |report| reports.lock().unwrap().push(report)
+                })
+                .client(|ctx| {
+                    ctx.data_opt::<ClientName>()
+                        .map(|client| client.0.to_string())
+                }),
+            )
+            .finish();
+
+        let resp = schema.execute("{ value }").await;
+        assert_eq!(resp.data, value!({ "value": 10 }));
+        assert!(reports.lock().unwrap().is_empty());
+
+        let resp = schema
+            .execute(
+                Request::new("query A { oldValue color(color: CRIMSON) }").data(ClientName("web")),
+            )
+            .await;
+        assert_eq!(resp.data, value!({ "oldValue": 10, "color": "CRIMSON" }));
+        assert!(resp.extensions.is_none());
+        assert_eq!(
+            std::mem::take(&mut *reports.lock().unwrap()),
+            vec![DeprecationReport {
+                operation_name: Some("A".to_string()),
+                client: Some("web".to_string()),
+                usages: vec![
+                    DeprecatedUsage {
+                        coordinate: "Query.oldValue".to_string(),
+                        reason: "Use `value`.".to_string(),
+                        pos: Pos {
+                            line: 1,
+                            column: 11
+                        },
+                    },
+                    DeprecatedUsage {
+                        coordinate: "Color.CRIMSON".to_string(),
+                        reason: "Use `RED`.".to_string(),
+                        pos: Pos {
+                            line: 1,
+                            column: 26
+                        },
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[async_std::test]
+    async fn deprecation_reporter_selected_operation() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(DeprecationReporter::new({
+                let reports = reports.clone();
+                move |report| reports.lock().unwrap().push(report)
+            }))
+            .finish();
+        let query = "query A { oldValue } query B { value }";
+
+        let resp = schema
+            .execute(Request::new(query).operation_name("B"))
+            .await;
+        assert_eq!(resp.data, value!({ "value": 10 }));
+        assert!(reports.lock().unwrap().is_empty());
+
+        let resp = schema
+            .execute(Request::new(query).operation_name("A"))
+            .await;
+        assert_eq!(resp.data, value!({ "oldValue": 10 }));
+        assert_eq!(
+            std::mem::take(&mut *reports.lock().unwrap()),
+            vec![DeprecationReport {
+                operation_name: Some("A".to_string()),
+                client: None,
+                usages: vec![DeprecatedUsage {
+                    coordinate: "Query.oldValue".to_string(),
+                    reason: "Use `value`.".to_string(),
+                    pos: Pos {
+                        line: 1,
+                        column: 11
+                    },
+                }],
+            }]
+        );
+    }
+
+    #[async_std::test]
+    async fn deprecation_reporter_response_extensions() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(DeprecationReporter::new(|_| {}).response_extensions())
+            .finish();
+
+        let resp = schema.execute("{ oldValue }").await;
+        assert_eq!(
+            resp.extensions,
+            Some(value!({
+                "deprecations": [{
+                    "coordinate": "Query.oldValue",
+                    "reason": "Use `value`.",
+                    "locations": [{ "line": 1, "column": 3 }],
+                }],
+            }))
+        );
+    }
+}
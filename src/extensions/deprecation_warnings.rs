@@ -0,0 +1,125 @@
+use crate::extensions::{Extension, ExtensionContext, ExtensionFactory};
+use crate::{value, DeprecatedUsage, ValidationResult, Value};
+
+/// Deprecation warnings extension
+///
+/// This extension will output the `deprecations` field in the response extension of each query
+/// using deprecated fields, arguments, input fields or enum values, with their schema coordinate,
+/// the reason of the deprecation and their locations in the query.
+pub struct DeprecationWarnings;
+
+impl ExtensionFactory for DeprecationWarnings {
+    fn create(&self) -> Box<dyn Extension> {
+        Box::new(DeprecationWarningsExtension::default())
+    }
+}
+
+#[derive(Default)]
+struct DeprecationWarningsExtension {
+    deprecations: Vec<DeprecatedUsage>,
+}
+
+impl Extension for DeprecationWarningsExtension {
+    fn name(&self) -> Option<&'static str> {
+        Some("deprecations")
+    }
+
+    fn validation_end(&mut self, _ctx: &ExtensionContext<'_>, result: &ValidationResult) {
+        self.deprecations = result.deprecations.clone();
+    }
+
+    fn result(&mut self, _ctx: &ExtensionContext<'_>) -> Option<Value> {
+        deprecations_value(&self.deprecations)
+    }
+}
+
+/// Converts the uses of deprecated elements to the `deprecations` entry of the response
+/// extensions, which is omitted if there are none.
+pub(crate) fn deprecations_value(deprecations: &[DeprecatedUsage]) -> Option<Value> {
+    if deprecations.is_empty() {
+        return None;
+    }
+    Some(Value::List(
+        deprecations
+            .iter()
+            .map(|usage| {
+                value!({
+                    "coordinate": usage.coordinate.clone(),
+                    "reason": usage.reason.clone(),
+                    "locations": [{
+                        "line": usage.pos.line,
+                        "column": usage.pos.column,
+                    }],
+                })
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[derive(InputObject)]
+    #[graphql(internal)]
+    struct MyInput {
+        a: Option<i32>,
+        #[graphql(deprecation = "Use `a`.")]
+        b: Option<i32>,
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(
+            &self,
+            #[graphql(deprecation = "Use `input`.")] a: Option<i32>,
+            input: Option<MyInput>,
+        ) -> i32 {
+            a.unwrap_or_default()
+                + input
+                    .map(|input| input.a.unwrap_or_default() + input.b.unwrap_or_default())
+                    .unwrap_or_default()
+        }
+    }
+
+    #[async_std::test]
+    async fn deprecation_warnings() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(extensions::DeprecationWarnings)
+            .finish();
+
+        let resp = schema.execute("{ value(input: { a: 1 }) }").await;
+        assert_eq!(resp.data, value!({ "value": 1 }));
+        assert!(resp.extensions.is_none());
+
+        let resp = schema
+            .execute(
+                Request::new("query($input: MyInput) { value(a: 1, input: $input) }").variables(
+                    Variables::from_json(serde_json::json!({
+                        "input": { "b": 2 },
+                    })),
+                ),
+            )
+            .await;
+        assert_eq!(resp.data, value!({ "value": 3 }));
+        assert_eq!(
+            resp.extensions,
+            Some(value!({
+                "deprecations": [
+                    {
+                        "coordinate": "Query.value(a:)",
+                        "reason": "Use `input`.",
+                        "locations": [{ "line": 1, "column": 32 }],
+                    },
+                    {
+                        "coordinate": "MyInput.b",
+                        "reason": "Use `a`.",
+                        "locations": [{ "line": 1, "column": 38 }],
+                    },
+                ],
+            }))
+        );
+    }
+}
@@ -5,13 +5,16 @@ mod analyzer;
 pub mod apollo_persisted_queries;
 #[cfg(feature = "apollo_tracing")]
 mod apollo_tracing;
+mod deprecation_reporter;
+mod deprecation_warnings;
 #[cfg(feature = "log")]
 mod logger;
 #[cfg(feature = "tracing")]
 mod tracing;
 
 use std::any::{Any, TypeId};
-use std::collections::BTreeMap;
+
+use indexmap::IndexMap;
 
 use crate::context::{QueryPathNode, ResolveId};
 use crate::parser::types::ExecutableDocument;
@@ -21,6 +24,8 @@ use crate::{Error, Name, Value};
 pub use self::analyzer::Analyzer;
 #[cfg(feature = "apollo_tracing")]
 pub use self::apollo_tracing::ApolloTracing;
+pub use self::deprecation_reporter::{DeprecationReport, DeprecationReporter, DeprecationSink};
+pub use self::deprecation_warnings::DeprecationWarnings;
 #[cfg(feature = "log")]
 pub use self::logger::Logger;
 #[cfg(feature = "tracing")]
@@ -290,7 +295,7 @@ impl Extensions {
                         None
                     }
                 })
-                .collect::<BTreeMap<_, _>>();
+                .collect::<IndexMap<_, _>>();
             if value.is_empty() {
                 None
             } else {
@@ -16,11 +16,16 @@ pub trait Guard {
 /// An extension trait for `Guard`.
 pub trait GuardExt: Guard + Sized {
     /// Perform `and` operator on two rules
+    ///
+    /// The second rule is only checked if the first one passes.
     fn and<R: Guard>(self, other: R) -> And<Self, R> {
         And(self, other)
     }
 
     /// Perform `or` operator on two rules
+    ///
+    /// The second rule is only checked if the first one fails, and its error is returned if it
+    /// fails too.
     fn or<R: Guard>(self, other: R) -> Or<Self, R> {
         Or(self, other)
     }
@@ -45,7 +50,9 @@ pub struct Or<A: Guard, B: Guard>(A, B);
 #[async_trait::async_trait]
 impl<A: Guard + Send + Sync, B: Guard + Send + Sync> Guard for Or<A, B> {
     async fn check(&self, ctx: &Context<'_>) -> Result<()> {
-        let second_result = self.1.check(ctx).await;
-        self.0.check(ctx).await.or(second_result)
+        match self.0.check(ctx).await {
+            Ok(()) => Ok(()),
+            Err(_) => self.1.check(ctx).await,
+        }
     }
 }
@@ -0,0 +1,255 @@
+use std::io;
+use std::slice;
+
+use futures_util::io::{AsyncWrite, AsyncWriteExt};
+use futures_util::stream::Stream;
+use indexmap::map;
+
+use crate::{BatchResponse, Name, Response, ServerError, Value};
+
+/// The size of the chunks the responses are serialized to.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+enum Frame<'a> {
+    List(slice::Iter<'a, Value>, bool),
+    Object(map::Iter<'a, Name, Value>, bool),
+}
+
+/// The parts of the JSON of a response, in order.
+enum Part<'a> {
+    Raw(&'static [u8]),
+    Value(&'a Value),
+    Errors(&'a [ServerError]),
+}
+
+/// Iterates over the chunks of the JSON of some complete responses, the values are serialized
+/// while they are traversed instead of being serialized to a single buffer first.
+struct JsonChunks<'a> {
+    parts: std::vec::IntoIter<Part<'a>>,
+    next_value: Option<&'a Value>,
+    stack: Vec<Frame<'a>>,
+}
+
+impl<'a> JsonChunks<'a> {
+    /// The responses are written as a list if `batch` is true, otherwise there must be only one.
+    fn new(responses: &'a [Response], batch: bool) -> Self {
+        let mut parts = Vec::new();
+        if batch {
+            parts.push(Part::Raw(b"["));
+        }
+        for (idx, response) in responses.iter().enumerate() {
+            if idx > 0 {
+                parts.push(Part::Raw(b","));
+            }
+            Self::push_response(&mut parts, response);
+        }
+        if batch {
+            parts.push(Part::Raw(b"]"));
+        }
+        Self {
+            parts: parts.into_iter(),
+            next_value: None,
+            stack: Vec::new(),
+        }
+    }
+
+    fn from_batch_response(response: &'a BatchResponse) -> Self {
+        match response {
+            BatchResponse::Single(response) => Self::new(slice::from_ref(response), false),
+            BatchResponse::Batch(responses) => Self::new(responses, true),
+        }
+    }
+
+    fn push_response(parts: &mut Vec<Part<'a>>, response: &'a Response) {
+        parts.push(Part::Raw(b"{\"data\":"));
+        parts.push(Part::Value(&response.data));
+        if let Some(extensions) = &response.extensions {
+            parts.push(Part::Raw(b",\"extensions\":"));
+            parts.push(Part::Value(extensions));
+        }
+        if !response.errors.is_empty() {
+            parts.push(Part::Raw(b",\"errors\":"));
+            parts.push(Part::Errors(&response.errors));
+        }
+        parts.push(Part::Raw(b"}"));
+    }
+
+    /// Serializes the current value until it is done or the chunk is full.
+    fn write_value(&mut self, buf: &mut Vec<u8>) -> io::Result<()> {
+        while buf.len() < CHUNK_SIZE {
+            if let Some(value) = self.next_value.take() {
+                match value {
+                    Value::List(values) => {
+                        buf.push(b'[');
+                        self.stack.push(Frame::List(values.iter(), true));
+                    }
+                    Value::Object(fields) => {
+                        buf.push(b'{');
+                        self.stack.push(Frame::Object(fields.iter(), true));
+                    }
+                    value => serde_json::to_writer(&mut *buf, value)?,
+                }
+                continue;
+            }
+
+            match self.stack.last_mut() {
+                Some(Frame::List(values, first)) => match values.next() {
+                    Some(value) => {
+                        if !std::mem::replace(first, false) {
+                            buf.push(b',');
+                        }
+                        self.next_value = Some(value);
+                    }
+                    None => {
+                        buf.push(b']');
+                        self.stack.pop();
+                    }
+                },
+                Some(Frame::Object(fields, first)) => match fields.next() {
+                    Some((name, value)) => {
+                        if !std::mem::replace(first, false) {
+                            buf.push(b',');
+                        }
+                        serde_json::to_writer(&mut *buf, name.as_str())?;
+                        buf.push(b':');
+                        self.next_value = Some(value);
+                    }
+                    None => {
+                        buf.push(b'}');
+                        self.stack.pop();
+                    }
+                },
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn next_chunk(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut buf = Vec::with_capacity(CHUNK_SIZE);
+        loop {
+            self.write_value(&mut buf)?;
+            if buf.len() >= CHUNK_SIZE {
+                return Ok(Some(buf));
+            }
+            match self.parts.next() {
+                Some(Part::Raw(bytes)) => buf.extend_from_slice(bytes),
+                Some(Part::Value(value)) => self.next_value = Some(value),
+                Some(Part::Errors(errors)) => serde_json::to_writer(&mut buf, errors)?,
+                None if buf.is_empty() => return Ok(None),
+                None => return Ok(Some(buf)),
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for JsonChunks<'a> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_chunk().transpose()
+    }
+}
+
+async fn write_chunks<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    chunks: JsonChunks<'_>,
+) -> io::Result<()> {
+    for chunk in chunks {
+        writer.write_all(&chunk?).await?;
+    }
+    writer.flush().await
+}
+
+/// Serialize a complete GraphQL response as JSON to an `AsyncWrite`, in chunks of about 8 KiB.
+///
+/// This is chunked output, not streaming: the response is fully executed before its first chunk
+/// is written. Serializing it chunk by chunk only avoids holding the whole JSON document in a
+/// single buffer besides the response. The output is the same as serializing the response with
+/// `serde_json`.
+pub async fn write_response_chunked<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    response: &Response,
+) -> io::Result<()> {
+    write_chunks(writer, JsonChunks::new(slice::from_ref(response), false)).await
+}
+
+/// Serialize a complete GraphQL batch response as JSON to an `AsyncWrite`, in chunks of about
+/// 8 KiB.
+///
+/// See [`write_response_chunked`](fn.write_response_chunked.html).
+pub async fn write_batch_response_chunked<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    response: &BatchResponse,
+) -> io::Result<()> {
+    write_chunks(writer, JsonChunks::from_batch_response(response)).await
+}
+
+/// Serialize a complete GraphQL batch response as JSON to chunks of about 8 KiB, for HTTP
+/// libraries whose response bodies are a `Stream` of chunks.
+///
+/// See [`write_response_chunked`](fn.write_response_chunked.html).
+pub fn batch_response_chunks(
+    response: BatchResponse,
+) -> impl Stream<Item = io::Result<Vec<u8>>> + Send + 'static {
+    async_stream::stream! {
+        for chunk in JsonChunks::from_batch_response(&response) {
+            yield chunk;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use futures_util::stream::StreamExt;
+
+    #[async_std::test]
+    async fn test_chunked_output() {
+        let mut big = Vec::new();
+        for i in 0..2000 {
+            big.push(value!({
+                "id": i,
+                "name": format!("item \"{}\"", i),
+                "tags": ["a", "b\n"],
+                "empty": {},
+                "nested": { "list": [], "value": null, "float": 1.5, "bool": true },
+            }));
+        }
+
+        let responses = vec![
+            Response::new(value!({ "a": 1, "b": [1, 2, { "c": "d" }] })),
+            Response::new(Value::List(big))
+                .extensions(Some(value!({ "tracing": { "duration": 10 } }))),
+            Response::from_errors(vec![ServerError::new("error")]),
+        ];
+
+        for response in &responses {
+            let mut output = Vec::new();
+            write_response_chunked(&mut output, response).await.unwrap();
+            assert_eq!(
+                String::from_utf8(output).unwrap(),
+                serde_json::to_string(response).unwrap()
+            );
+        }
+
+        let batch = BatchResponse::Batch(responses);
+        let mut output = Vec::new();
+        write_batch_response_chunked(&mut output, &batch)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            serde_json::to_string(&batch).unwrap()
+        );
+
+        let expected = serde_json::to_string(&batch).unwrap();
+        let chunks = batch_response_chunks(batch)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+        assert!(chunks.len() > 1);
+        assert_eq!(String::from_utf8(chunks.concat()).unwrap(), expected);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::ParseRequestError;
+
+/// The content types that a browser can send cross-site without a preflight request.
+const SIMPLE_CONTENT_TYPES: &[&str] = &[
+    "application/x-www-form-urlencoded",
+    "multipart/form-data",
+    "text/plain",
+];
+
+/// Options for preventing Cross-Site Request Forgery (CSRF).
+///
+/// Browsers can send `GET` requests and `POST` requests with a "simple" content type
+/// (`application/x-www-form-urlencoded`, `multipart/form-data` or `text/plain`) to another site
+/// without a preflight request, so these requests are only accepted if they have a non-empty
+/// value for one of the required headers, which can only be set after a preflight request.
+///
+/// The prevention is disabled by default, each integration has its own way to enable it with
+/// these options for all its `GET` and `POST` requests.
+#[derive(Debug, Clone)]
+pub struct CsrfOptions {
+    /// The names of the headers, one of which must be present in a request that a browser could
+    /// have sent without a preflight request.
+    ///
+    /// The default is `x-apollo-operation-name`, `apollo-require-preflight` and
+    /// `graphql-preflight`.
+    pub required_headers: Vec<String>,
+}
+
+impl Default for CsrfOptions {
+    fn default() -> Self {
+        Self {
+            required_headers: vec![
+                "x-apollo-operation-name".to_string(),
+                "apollo-require-preflight".to_string(),
+                "graphql-preflight".to_string(),
+            ],
+        }
+    }
+}
+
+impl CsrfOptions {
+    /// Set the names of the required headers.
+    pub fn required_headers<I, T>(self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self {
+            required_headers: headers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Check that a request could not have been sent cross-site by a browser without a preflight
+/// request.
+///
+/// `content_type` is the `Content-Type` header of the request, and `header` returns the value of
+/// the header with the given name.
+///
+/// # Errors
+///
+/// Fails with [`ParseRequestError::CsrfPrevented`](../enum.ParseRequestError.html) if the
+/// request has no content type or a simple content type, and has none of the required headers.
+pub fn check_csrf<'a>(
+    opts: &CsrfOptions,
+    content_type: Option<&str>,
+    header: impl Fn(&str) -> Option<&'a str>,
+) -> Result<(), ParseRequestError> {
+    let is_simple = match content_type {
+        Some(content_type) => {
+            let mime = content_type.split(';').next().unwrap_or_default().trim();
+            SIMPLE_CONTENT_TYPES
+                .iter()
+                .any(|ty| ty.eq_ignore_ascii_case(mime))
+        }
+        None => true,
+    };
+
+    if !is_simple
+        || opts.required_headers.iter().any(|name| {
+            header(name)
+                .map(|value| !value.is_empty())
+                .unwrap_or_default()
+        })
+    {
+        Ok(())
+    } else {
+        Err(ParseRequestError::CsrfPrevented(
+            opts.required_headers.join(", "),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_csrf() {
+        let opts = CsrfOptions::default();
+        let no_headers = |_: &str| None;
+        let preflight = |name: &str| {
+            if name == "graphql-preflight" {
+                Some("1")
+            } else {
+                None
+            }
+        };
+
+        assert!(check_csrf(&opts, Some("application/json"), no_headers).is_ok());
+        assert!(check_csrf(&opts, None, preflight).is_ok());
+        assert!(check_csrf(&opts, Some("multipart/form-data; boundary=xyz"), preflight).is_ok());
+
+        assert!(matches!(
+            check_csrf(&opts, None, no_headers),
+            Err(ParseRequestError::CsrfPrevented(_))
+        ));
+        assert!(matches!(
+            check_csrf(&opts, Some("text/plain"), no_headers),
+            Err(ParseRequestError::CsrfPrevented(_))
+        ));
+        assert!(matches!(
+            check_csrf(&opts, Some("Multipart/Form-Data; boundary=xyz"), |_| Some(
+                ""
+            )),
+            Err(ParseRequestError::CsrfPrevented(_))
+        ));
+        assert!(check_csrf(
+            &opts.required_headers(vec!["x-requested-with"]),
+            Some("text/plain"),
+            |name| if name == "x-requested-with" {
+                Some("XMLHttpRequest")
+            } else {
+                None
+            }
+        )
+        .is_ok());
+    }
+}
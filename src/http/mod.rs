@@ -1,19 +1,31 @@
 //! A helper module that supports HTTP
 
+mod chunked_json;
+mod csrf;
 mod graphiql_source;
 #[cfg(feature = "multipart")]
 mod multipart;
 mod playground_source;
+mod response_format;
+#[cfg(feature = "multipart")]
+mod upload_storage;
 mod websocket;
 
 use futures_util::io::{AsyncRead, AsyncReadExt};
 
 use crate::{BatchRequest, ParseRequestError, Request};
 
+pub use chunked_json::{
+    batch_response_chunks, write_batch_response_chunked, write_response_chunked,
+};
+pub use csrf::{check_csrf, CsrfOptions};
 pub use graphiql_source::graphiql_source;
 #[cfg(feature = "multipart")]
 pub use multipart::MultipartOptions;
 pub use playground_source::{playground_source, GraphQLPlaygroundConfig};
+pub use response_format::ResponseFormat;
+#[cfg(feature = "multipart")]
+pub use upload_storage::{MemoryStorage, TempFileStorage, UploadStorage};
 pub use websocket::{Protocols as WebSocketProtocols, WebSocket};
 
 #[cfg(feature = "multipart")]
@@ -1,17 +1,21 @@
-use std::collections::HashMap;
-use std::io::{self, Seek, SeekFrom, Write};
+use std::collections::{HashMap, HashSet};
+use std::io;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use futures_util::io::AsyncRead;
-use futures_util::stream::Stream;
-use multer::{Constraints, Multipart, SizeLimit};
+use futures_util::stream::{Stream, TryStreamExt};
+use multer::{Constraints, Field, Multipart, SizeLimit};
 use pin_project_lite::pin_project;
+use sha2::{Digest, Sha256};
 
-use crate::{BatchRequest, ParseRequestError, UploadValue};
+use super::{TempFileStorage, UploadStorage};
+use crate::types::content_type_matches;
+use crate::{BatchRequest, ParseRequestError, UploadContent, UploadStream, UploadValue};
 
 /// Options for `receive_multipart`.
-#[derive(Default, Clone, Copy)]
+#[derive(Clone)]
 #[non_exhaustive]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
 pub struct MultipartOptions {
@@ -19,6 +23,30 @@ pub struct MultipartOptions {
     pub max_file_size: Option<usize>,
     /// The maximum number of files.
     pub max_num_files: Option<usize>,
+    /// The maximum size of the whole request.
+    pub max_request_size: Option<usize>,
+    /// The allowed content types of the files, the default is to allow all content types.
+    pub allowed_content_types: Option<Vec<String>>,
+    /// Compute the SHA-256 digest of each file.
+    pub sha256: bool,
+    /// The storage of the uploaded files, the default is [`TempFileStorage`](struct.TempFileStorage.html).
+    pub storage: Arc<dyn UploadStorage>,
+    /// Stream the last file of the request to the resolvers instead of storing it.
+    pub stream_uploads: bool,
+}
+
+impl Default for MultipartOptions {
+    fn default() -> Self {
+        Self {
+            max_file_size: None,
+            max_num_files: None,
+            max_request_size: None,
+            allowed_content_types: None,
+            sha256: false,
+            storage: Arc::new(TempFileStorage::new()),
+            stream_uploads: false,
+        }
+    }
 }
 
 impl MultipartOptions {
@@ -37,6 +65,66 @@ impl MultipartOptions {
             ..self
         }
     }
+
+    /// Set maximum size of the whole request.
+    ///
+    /// If it is not set but both the maximum file size and the maximum number of files are, the
+    /// request is limited to their product.
+    pub fn max_request_size(self, size: usize) -> Self {
+        MultipartOptions {
+            max_request_size: Some(size),
+            ..self
+        }
+    }
+
+    /// Set the allowed content types of the files.
+    ///
+    /// A content type may be a wildcard such as `image/*`. A file without a content type is treated
+    /// as `application/octet-stream`, and a file with another content type is rejected with
+    /// [`ParseRequestError::UnsupportedUploadType`](../enum.ParseRequestError.html).
+    pub fn allowed_content_types<I, T>(self, content_types: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        MultipartOptions {
+            allowed_content_types: Some(content_types.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+
+    /// Compute the SHA-256 digest of each file while it is received, and set it to
+    /// [`UploadValue::sha256`](../struct.UploadValue.html#structfield.sha256).
+    ///
+    /// The digest of a streamed file is not computed.
+    pub fn sha256(self, sha256: bool) -> Self {
+        MultipartOptions { sha256, ..self }
+    }
+
+    /// Set the storage of the uploaded files.
+    pub fn storage(self, storage: impl UploadStorage) -> Self {
+        MultipartOptions {
+            storage: Arc::new(storage),
+            ..self
+        }
+    }
+
+    /// Stream the last file of the request to the resolvers while the request body is still
+    /// arriving, instead of storing it.
+    ///
+    /// The files before it are stored with the storage, and the request is returned as soon as
+    /// the headers of the last file are received. Its content is an
+    /// [`UploadContent::Stream`](../enum.UploadContent.html), which is read from the request body
+    /// when the resolver consumes it.
+    ///
+    /// The last file is the file part which completes the files listed in the map, reading it
+    /// fails if another part follows it.
+    pub fn stream_uploads(self, stream_uploads: bool) -> Self {
+        MultipartOptions {
+            stream_uploads,
+            ..self
+        }
+    }
 }
 
 pub(super) async fn receive_batch_multipart(
@@ -49,8 +137,10 @@ pub(super) async fn receive_batch_multipart(
         boundary,
         Constraints::new().size_limit({
             let mut limit = SizeLimit::new();
-            if let (Some(max_file_size), Some(max_num_files)) =
-                (opts.max_file_size, opts.max_file_size)
+            if let Some(max_request_size) = opts.max_request_size {
+                limit = limit.whole_stream(max_request_size as u64);
+            } else if let (Some(max_file_size), Some(max_num_files)) =
+                (opts.max_file_size, opts.max_num_files)
             {
                 limit = limit.whole_stream((max_file_size * max_num_files) as u64);
             }
@@ -64,8 +154,10 @@ pub(super) async fn receive_batch_multipart(
     let mut request = None;
     let mut map = None;
     let mut files = Vec::new();
+    // The names of the received files which are listed in the map.
+    let mut mapped_files = HashSet::new();
 
-    while let Some(mut field) = multipart.next_field().await? {
+    while let Some(field) = multipart.next_field().await? {
         match field.name() {
             Some("operations") => {
                 let request_str = field.text().await?;
@@ -85,12 +177,73 @@ pub(super) async fn receive_batch_multipart(
                 if let Some(name) = field.name().map(ToString::to_string) {
                     if let Some(filename) = field.file_name().map(ToString::to_string) {
                         let content_type = field.content_type().map(ToString::to_string);
-                        let mut file = tempfile::tempfile().map_err(ParseRequestError::Io)?;
-                        while let Some(chunk) = field.chunk().await.unwrap() {
-                            file.write(&chunk).map_err(ParseRequestError::Io)?;
+                        if let Some(allowed_content_types) = &opts.allowed_content_types {
+                            let ty = content_type
+                                .as_deref()
+                                .unwrap_or("application/octet-stream");
+                            if !allowed_content_types
+                                .iter()
+                                .any(|pattern| content_type_matches(pattern, ty))
+                            {
+                                return Err(ParseRequestError::UnsupportedUploadType(
+                                    ty.to_string(),
+                                ));
+                            }
                         }
-                        file.seek(SeekFrom::Start(0))?;
-                        files.push((name, filename, content_type, file));
+                        let is_last_file = match &map {
+                            Some(map) if map.contains_key(&name) => {
+                                mapped_files.insert(name.clone());
+                                mapped_files.len() == map.len()
+                            }
+                            _ => false,
+                        };
+
+                        if opts.stream_uploads && is_last_file {
+                            let stream = async_stream::try_stream! {
+                                // Keep the body alive until the file is read, then check that
+                                // no part follows it.
+                                let mut multipart = multipart;
+                                {
+                                    let mut field = field;
+                                    while let Some(chunk) = field.chunk().await.map_err(into_io_error)? {
+                                        yield chunk.to_vec();
+                                    }
+                                }
+                                if multipart.next_field().await.map_err(into_io_error)?.is_some() {
+                                    Err(io::Error::other(
+                                        "the streamed file must be the last part of the request",
+                                    ))?;
+                                }
+                            };
+                            files.push((
+                                name,
+                                filename,
+                                content_type,
+                                UploadContent::Stream(UploadStream::new(stream)),
+                                None,
+                            ));
+                            break;
+                        }
+
+                        let mut hasher = if opts.sha256 {
+                            Some(Sha256::new())
+                        } else {
+                            None
+                        };
+                        let content = {
+                            let mut reader = field_stream(field)
+                                .inspect_ok(|chunk| {
+                                    if let Some(hasher) = &mut hasher {
+                                        hasher.update(chunk);
+                                    }
+                                })
+                                .into_async_read();
+                            opts.storage
+                                .store(&filename, content_type.as_deref(), &mut reader)
+                                .await?
+                        };
+                        let sha256 = hasher.map(|hasher| hasher.finalize().into());
+                        files.push((name, filename, content_type, content, sha256));
                     }
                 }
             }
@@ -100,12 +253,13 @@ pub(super) async fn receive_batch_multipart(
     let mut request: BatchRequest = request.ok_or(ParseRequestError::MissingOperatorsPart)?;
     let map = map.as_mut().ok_or(ParseRequestError::MissingMapPart)?;
 
-    for (name, filename, content_type, file) in files {
+    for (name, filename, content_type, content, sha256) in files {
         if let Some(var_paths) = map.remove(&name) {
             let upload = UploadValue {
                 filename,
                 content_type,
-                content: file,
+                content,
+                sha256,
             };
 
             for var_path in var_paths {
@@ -136,6 +290,18 @@ pub(super) async fn receive_batch_multipart(
     Ok(request)
 }
 
+fn field_stream(mut field: Field) -> Pin<Box<dyn Stream<Item = io::Result<Vec<u8>>> + Send>> {
+    Box::pin(async_stream::try_stream! {
+        while let Some(chunk) = field.chunk().await.map_err(into_io_error)? {
+            yield chunk.to_vec();
+        }
+    })
+}
+
+fn into_io_error(err: multer::Error) -> io::Error {
+    io::Error::other(err)
+}
+
 pin_project! {
     pub(crate) struct ReaderStream<T> {
         buf: [u8; 2048],
@@ -167,3 +333,163 @@ impl<T: AsyncRead> Stream for ReaderStream<T> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(files: &[(&str, &str, &str)]) -> Vec<u8> {
+        let mut body = String::new();
+        body.push_str("--xyz\r\nContent-Disposition: form-data; name=\"operations\"\r\n\r\n");
+        body.push_str(&format!(
+            r#"{{"query": "mutation($files: [Upload!]!) {{ upload(files: $files) }}", "variables": {{"files": [{}]}}}}"#,
+            vec!["null"; files.len()].join(",")
+        ));
+        body.push_str("\r\n--xyz\r\nContent-Disposition: form-data; name=\"map\"\r\n\r\n{");
+        for idx in 0..files.len() {
+            if idx > 0 {
+                body.push(',');
+            }
+            body.push_str(&format!(r#""{0}": ["variables.files.{0}"]"#, idx));
+        }
+        body.push_str("}\r\n");
+        for (idx, (filename, content_type, data)) in files.iter().enumerate() {
+            body.push_str(&format!(
+                "--xyz\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n{}\r\n",
+                idx, filename, content_type, data
+            ));
+        }
+        body.push_str("--xyz--\r\n");
+        body.into_bytes()
+    }
+
+    async fn receive(
+        files: &[(&str, &str, &str)],
+        opts: MultipartOptions,
+    ) -> Result<BatchRequest, ParseRequestError> {
+        receive_batch_multipart(futures_util::io::Cursor::new(body(files)), "xyz", opts).await
+    }
+
+    #[async_std::test]
+    async fn test_allowed_content_types() {
+        let opts = MultipartOptions::default().allowed_content_types(vec!["image/*", "text/plain"]);
+        assert!(receive(
+            &[
+                ("a.png", "image/png", "abc"),
+                ("b.txt", "text/plain", "abc")
+            ],
+            opts.clone()
+        )
+        .await
+        .is_ok());
+        assert!(matches!(
+            receive(&[("a.pdf", "application/pdf", "abc")], opts).await,
+            Err(ParseRequestError::UnsupportedUploadType(ty)) if ty == "application/pdf"
+        ));
+    }
+
+    #[async_std::test]
+    async fn test_size_limits() {
+        let files = [
+            ("a.txt", "text/plain", "hello"),
+            ("b.txt", "text/plain", "hello"),
+        ];
+        let opts = MultipartOptions::default().max_file_size(200);
+        assert!(receive(&files, opts.clone().max_num_files(10))
+            .await
+            .is_ok());
+        assert!(receive(&files, opts.max_num_files(1)).await.is_err());
+        assert!(
+            receive(&files, MultipartOptions::default().max_request_size(1000))
+                .await
+                .is_ok()
+        );
+        assert!(
+            receive(&files, MultipartOptions::default().max_request_size(100))
+                .await
+                .is_err()
+        );
+    }
+
+    #[async_std::test]
+    async fn test_sha256() {
+        let request = receive(
+            &[("a.txt", "text/plain", "hello")],
+            MultipartOptions::default().sha256(true),
+        )
+        .await
+        .unwrap()
+        .into_single()
+        .unwrap();
+        assert_eq!(
+            request.uploads[0].sha256,
+            Some(Sha256::digest(b"hello").into())
+        );
+
+        let request = receive(&[("a.txt", "text/plain", "hello")], Default::default())
+            .await
+            .unwrap()
+            .into_single()
+            .unwrap();
+        assert_eq!(request.uploads[0].sha256, None);
+    }
+
+    #[async_std::test]
+    async fn test_stream_uploads() {
+        use futures_util::io::AsyncReadExt;
+
+        let part = |name: &str, data: &str| {
+            format!(
+                "--xyz\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}.txt\"\r\n\r\n{}\r\n",
+                name, name, data
+            )
+        };
+        let body = |parts: &[String]| {
+            let mut body = String::new();
+            body.push_str("--xyz\r\nContent-Disposition: form-data; name=\"operations\"\r\n\r\n");
+            body.push_str(
+                r#"{"query": "mutation($files: [Upload!]!) { upload(files: $files) }", "variables": {"files": [null, null]}}"#,
+            );
+            body.push_str("\r\n--xyz\r\nContent-Disposition: form-data; name=\"map\"\r\n\r\n");
+            body.push_str(r#"{"0": ["variables.files.0"], "1": ["variables.files.1"]}"#);
+            body.push_str("\r\n");
+            for part in parts {
+                body.push_str(part);
+            }
+            body.push_str("--xyz--\r\n");
+            futures_util::io::Cursor::new(body.into_bytes())
+        };
+        let read_last_file = |request: BatchRequest| async move {
+            let mut request = request.into_single().unwrap();
+            let mut content = String::new();
+            request
+                .uploads
+                .remove(1)
+                .into_async_read()?
+                .read_to_string(&mut content)
+                .await?;
+            io::Result::Ok(content)
+        };
+        let opts = MultipartOptions::default().stream_uploads(true);
+
+        // A part which is not listed in the map is not counted.
+        let request = receive_batch_multipart(
+            body(&[part("x", "x"), part("0", "a"), part("1", "b")]),
+            "xyz",
+            opts.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(read_last_file(request).await.unwrap(), "b");
+
+        // A part following the streamed file is an error.
+        let request = receive_batch_multipart(
+            body(&[part("0", "a"), part("1", "b"), part("x", "x")]),
+            "xyz",
+            opts,
+        )
+        .await
+        .unwrap();
+        assert!(read_last_file(request).await.is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::BatchResponse;
+
+/// The media type of a GraphQL response sent over HTTP.
+///
+/// **Reference:** <https://github.com/graphql/graphql-over-http/blob/main/spec/GraphQLOverHTTP.md>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    /// `application/json`, which is always answered with `200 OK`.
+    Json,
+    /// `application/graphql-response+json`, which is answered with `400 Bad Request` if the
+    /// request failed before the operation was executed.
+    GraphQLResponseJson,
+}
+
+impl ResponseFormat {
+    /// Select the response format from the `Accept` header of a request.
+    ///
+    /// `application/graphql-response+json` is only used if the client explicitly accepts it with
+    /// a quality at least as high as `application/json`. If the header is missing or accepts
+    /// neither media type, `application/json` is used.
+    pub fn from_accept(accept: Option<&str>) -> Self {
+        let mut best: Option<(f32, ResponseFormat)> = None;
+
+        for media_range in accept.unwrap_or_default().split(',') {
+            let mut params = media_range.split(';');
+            let format = match params
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase()
+                .as_str()
+            {
+                "application/graphql-response+json" => ResponseFormat::GraphQLResponseJson,
+                "application/json" | "application/*" | "*/*" => ResponseFormat::Json,
+                _ => continue,
+            };
+            let quality = params
+                .filter_map(|param| {
+                    let mut s = param.splitn(2, '=');
+                    match (s.next().map(str::trim), s.next()) {
+                        (Some("q"), Some(value)) => value.trim().parse::<f32>().ok(),
+                        _ => None,
+                    }
+                })
+                .next()
+                .unwrap_or(1.0);
+            if quality <= 0.0 {
+                continue;
+            }
+
+            let is_better = match best {
+                Some((best_quality, _)) => {
+                    quality > best_quality
+                        || (quality == best_quality
+                            && format == ResponseFormat::GraphQLResponseJson)
+                }
+                None => true,
+            };
+            if is_better {
+                best = Some((quality, format));
+            }
+        }
+
+        best.map(|(_, format)| format)
+            .unwrap_or(ResponseFormat::Json)
+    }
+
+    /// Returns the value of the `Content-Type` header of the response.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ResponseFormat::Json => "application/json",
+            ResponseFormat::GraphQLResponseJson => "application/graphql-response+json",
+        }
+    }
+
+    /// Returns the HTTP status code of the response.
+    pub fn status_code(self, response: &BatchResponse) -> u16 {
+        if response.is_method_not_allowed() {
+            405
+        } else if self == ResponseFormat::GraphQLResponseJson && response.is_request_error() {
+            400
+        } else {
+            200
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Response, ServerError};
+
+    #[test]
+    fn test_from_accept() {
+        assert_eq!(ResponseFormat::from_accept(None), ResponseFormat::Json);
+        assert_eq!(ResponseFormat::from_accept(Some("")), ResponseFormat::Json);
+        assert_eq!(
+            ResponseFormat::from_accept(Some("*/*")),
+            ResponseFormat::Json
+        );
+        assert_eq!(
+            ResponseFormat::from_accept(Some("text/html")),
+            ResponseFormat::Json
+        );
+        assert_eq!(
+            ResponseFormat::from_accept(Some("application/graphql-response+json")),
+            ResponseFormat::GraphQLResponseJson
+        );
+        assert_eq!(
+            ResponseFormat::from_accept(Some(
+                "application/json, application/graphql-response+json"
+            )),
+            ResponseFormat::GraphQLResponseJson
+        );
+        assert_eq!(
+            ResponseFormat::from_accept(Some(
+                "application/graphql-response+json;q=0.9, application/json"
+            )),
+            ResponseFormat::Json
+        );
+        assert_eq!(
+            ResponseFormat::from_accept(Some(
+                "application/graphql-response+json; charset=utf-8, application/json;q=0.9"
+            )),
+            ResponseFormat::GraphQLResponseJson
+        );
+        assert_eq!(
+            ResponseFormat::from_accept(Some("application/graphql-response+json;q=0")),
+            ResponseFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_status_code() {
+        let ok = BatchResponse::Single(Response::new(crate::Value::Null));
+        let field_error =
+            BatchResponse::Single(Response::from_errors(vec![ServerError::new("field error")]));
+        let request_error = BatchResponse::Single(Response {
+            request_error: true,
+            ..Response::from_errors(vec![ServerError::new("syntax error")])
+        });
+
+        for format in &[ResponseFormat::Json, ResponseFormat::GraphQLResponseJson] {
+            assert_eq!(format.status_code(&ok), 200);
+            assert_eq!(format.status_code(&field_error), 200);
+        }
+        assert_eq!(ResponseFormat::Json.status_code(&request_error), 200);
+        assert_eq!(
+            ResponseFormat::GraphQLResponseJson.status_code(&request_error),
+            400
+        );
+    }
+}
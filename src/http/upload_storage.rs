@@ -0,0 +1,233 @@
+use std::fs::File;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use futures_util::io::{AsyncRead, AsyncReadExt};
+
+use crate::UploadContent;
+
+/// A storage for the files uploaded with a multipart request.
+///
+/// The storage receives the content of each file while the request body is read, and returns
+/// where it has been stored. Implement this trait to send the files to another place, such as an
+/// object store, and return a [`UploadContent::Custom`](../enum.UploadContent.html) describing
+/// where they are.
+#[async_trait::async_trait]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+pub trait UploadStorage: Send + Sync + 'static {
+    /// Store the content of an uploaded file.
+    async fn store(
+        &self,
+        filename: &str,
+        content_type: Option<&str>,
+        content: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> io::Result<UploadContent>;
+}
+
+/// An upload storage that writes the files to temporary files.
+///
+/// This is the default storage.
+#[derive(Default, Clone)]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+pub struct TempFileStorage {
+    dir: Option<PathBuf>,
+}
+
+impl TempFileStorage {
+    /// Create a storage that writes the files to the default temporary directory.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a storage that writes the files to the given directory.
+    pub fn in_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+
+    fn create_file(&self) -> io::Result<File> {
+        match &self.dir {
+            Some(dir) => tempfile::tempfile_in(dir),
+            None => tempfile::tempfile(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UploadStorage for TempFileStorage {
+    async fn store(
+        &self,
+        _filename: &str,
+        _content_type: Option<&str>,
+        content: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> io::Result<UploadContent> {
+        let mut file = self.create_file()?;
+        copy_to_file(content, &mut file).await?;
+        file.seek(SeekFrom::Start(0))?;
+        Ok(UploadContent::File(file))
+    }
+}
+
+/// An upload storage that keeps the files in memory, and writes the files larger than a
+/// threshold to temporary files.
+#[derive(Clone)]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+pub struct MemoryStorage {
+    threshold: usize,
+    fallback: TempFileStorage,
+}
+
+impl MemoryStorage {
+    /// Create a storage that keeps the files up to `threshold` bytes in memory.
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            fallback: TempFileStorage::new(),
+        }
+    }
+
+    /// Set the directory of the temporary files used for the files larger than the threshold.
+    pub fn spill_dir(self, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            fallback: TempFileStorage::in_dir(dir),
+            ..self
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UploadStorage for MemoryStorage {
+    async fn store(
+        &self,
+        _filename: &str,
+        _content_type: Option<&str>,
+        content: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> io::Result<UploadContent> {
+        let mut data = Vec::new();
+        (&mut *content)
+            .take(self.threshold as u64 + 1)
+            .read_to_end(&mut data)
+            .await?;
+        if data.len() <= self.threshold {
+            return Ok(UploadContent::Memory(data.into()));
+        }
+
+        let mut file = self.fallback.create_file()?;
+        file.write_all(&data)?;
+        copy_to_file(content, &mut file).await?;
+        file.seek(SeekFrom::Start(0))?;
+        Ok(UploadContent::File(file))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: UploadStorage> UploadStorage for Arc<T> {
+    async fn store(
+        &self,
+        filename: &str,
+        content_type: Option<&str>,
+        content: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> io::Result<UploadContent> {
+        T::store(&**self, filename, content_type, content).await
+    }
+}
+
+async fn copy_to_file(
+    content: &mut (dyn AsyncRead + Send + Unpin),
+    file: &mut File,
+) -> io::Result<()> {
+    let mut buf = [0; 8192];
+    loop {
+        match content.read(&mut buf).await? {
+            0 => return Ok(()),
+            size => file.write_all(&buf[..size])?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use futures_util::io::AsyncReadExt;
+
+    use super::*;
+    use crate::{UploadStream, UploadValue};
+
+    fn upload(content: UploadContent) -> UploadValue {
+        UploadValue {
+            filename: "a.txt".to_string(),
+            content_type: Some("text/plain".to_string()),
+            content,
+            sha256: None,
+        }
+    }
+
+    #[async_std::test]
+    async fn test_temp_file_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        for storage in vec![TempFileStorage::new(), TempFileStorage::in_dir(dir.path())] {
+            let content = storage
+                .store("a.txt", None, &mut &b"hello"[..])
+                .await
+                .unwrap();
+            assert!(matches!(content, UploadContent::File(_)));
+
+            let upload = upload(content);
+            assert_eq!(upload.size().unwrap(), 5);
+            let mut data = String::new();
+            upload
+                .into_read()
+                .unwrap()
+                .read_to_string(&mut data)
+                .unwrap();
+            assert_eq!(data, "hello");
+        }
+    }
+
+    #[async_std::test]
+    async fn test_memory_storage() {
+        let storage = MemoryStorage::new(5);
+
+        let content = storage
+            .store("a.txt", None, &mut &b"hello"[..])
+            .await
+            .unwrap();
+        assert!(matches!(&content, UploadContent::Memory(data) if &**data == b"hello"));
+
+        let content = storage
+            .store("a.txt", None, &mut &b"hello world"[..])
+            .await
+            .unwrap();
+        assert!(matches!(content, UploadContent::File(_)));
+        let mut data = String::new();
+        upload(content)
+            .into_read()
+            .unwrap()
+            .read_to_string(&mut data)
+            .unwrap();
+        assert_eq!(data, "hello world");
+    }
+
+    #[async_std::test]
+    async fn test_upload_stream() {
+        let stream = UploadStream::new(futures_util::stream::iter(vec![
+            Ok(b"hello ".to_vec()),
+            Ok(b"world".to_vec()),
+        ]));
+        let upload = upload(UploadContent::Stream(stream));
+        assert!(upload.size().is_err());
+        assert!(upload.try_clone().unwrap().into_read().is_err());
+
+        let mut data = String::new();
+        upload
+            .into_async_read()
+            .unwrap()
+            .read_to_string(&mut data)
+            .await
+            .unwrap();
+        assert_eq!(data, "hello world");
+    }
+}
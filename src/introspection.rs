@@ -0,0 +1,775 @@
+//! Conversion of introspection results
+//!
+//! The result of the [`INTROSPECTION_QUERY`](constant.INTROSPECTION_QUERY.html), e.g. fetched from
+//! a remote service or saved as a file, can be converted to a `ServiceDocument` or to a SDL
+//! document. These can be compared with another version of the schema with the
+//! [`diff`](../diff/index.html) module, or used to build a [dynamic schema](../dynamic/index.html).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::*;
+//! use async_graphql::introspection::{introspection_to_sdl, INTROSPECTION_QUERY};
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn value(&self, #[graphql(default = 10)] limit: i32) -> Vec<i32> {
+//!         (0..limit).collect()
+//!     }
+//! }
+//!
+//! async_std::task::block_on(async move {
+//!     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+//!     let response = schema.execute(INTROSPECTION_QUERY).await;
+//!     let json = serde_json::to_string(&response).unwrap();
+//!
+//!     let sdl = introspection_to_sdl(&json).unwrap();
+//!     assert!(sdl.contains("value(limit: Int! = 10): [Int!]!"));
+//!     assert!(schema.diff_from_sdl(&sdl).unwrap().is_empty());
+//! });
+//! ```
+
+use std::fmt::Write;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::model::__DirectiveLocation;
+use crate::parser::types::{
+    BaseType, ConstDirective, DirectiveDefinition, DirectiveLocation, DocumentOperations, EnumType,
+    EnumValueDefinition, FieldDefinition, InputObjectType, InputValueDefinition, InterfaceType,
+    ObjectType, SchemaDefinition, ServiceDocument, Type, TypeDefinition, TypeKind,
+    TypeSystemDefinition, UnionType,
+};
+use crate::parser::{parse_query, Pos, Positioned};
+use crate::{Name, Value};
+
+/// The introspection query whose result can be converted by this module.
+///
+/// It includes the deprecated elements, the `specifiedByURL` of the scalars, the `isOneOf` of the
+/// input objects and the `isRepeatable` of the directives.
+pub const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives {
+      name
+      description
+      locations
+      args(includeDeprecated: true) { ...InputValue }
+      isRepeatable
+    }
+  }
+}
+
+fragment FullType on __Type {
+  kind
+  name
+  description
+  specifiedByURL
+  isOneOf
+  fields(includeDeprecated: true) {
+    name
+    description
+    args(includeDeprecated: true) { ...InputValue }
+    type { ...TypeRef }
+    isDeprecated
+    deprecationReason
+  }
+  inputFields(includeDeprecated: true) { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) {
+    name
+    description
+    isDeprecated
+    deprecationReason
+  }
+  possibleTypes { ...TypeRef }
+}
+
+fragment InputValue on __InputValue {
+  name
+  description
+  type { ...TypeRef }
+  defaultValue
+  isDeprecated
+  deprecationReason
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+/// An error converting an introspection result.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum IntrospectionError {
+    /// The JSON is invalid, or is not an introspection result.
+    #[error("Invalid introspection result: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    /// The result has no `__schema` field, e.g. because the query failed.
+    #[error("Missing \"__schema\" field")]
+    MissingSchema,
+
+    /// A type reference is incomplete, e.g. because the `ofType` fields are not nested deeply
+    /// enough.
+    #[error("Invalid type of \"{0}\"")]
+    InvalidType(String),
+
+    /// A default value is not a valid GraphQL value.
+    #[error("Invalid default value of \"{coordinate}\": {value}")]
+    InvalidDefaultValue {
+        /// The schema coordinate of the argument or input field.
+        coordinate: String,
+        /// The default value.
+        value: String,
+    },
+
+    /// A directive location is unknown.
+    #[error("Unknown location \"{location}\" of directive \"@{directive}\"")]
+    UnknownDirectiveLocation {
+        /// The name of the directive.
+        directive: String,
+        /// The location.
+        location: String,
+    },
+}
+
+/// Converts the JSON result of the introspection query to a service document.
+///
+/// The JSON can be either the whole response, or its `data` field. The introspection types, the
+/// built-in scalars and the built-in directives are omitted.
+pub fn introspection_to_service_document(
+    introspection: &str,
+) -> Result<ServiceDocument, IntrospectionError> {
+    let value: serde_json::Value = serde_json::from_str(introspection)?;
+    let schema = value
+        .get("data")
+        .unwrap_or(&value)
+        .get("__schema")
+        .filter(|schema| !schema.is_null())
+        .ok_or(IntrospectionError::MissingSchema)?;
+    IntrospectionSchema::deserialize(schema)?.into_service_document()
+}
+
+/// Converts the JSON result of the introspection query to a SDL document.
+///
+/// See [`introspection_to_service_document`](fn.introspection_to_service_document.html).
+pub fn introspection_to_sdl(introspection: &str) -> Result<String, IntrospectionError> {
+    introspection_to_service_document(introspection).map(|document| export_sdl(&document))
+}
+
+const BUILTIN_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+const BUILTIN_DIRECTIVES: &[&str] = &[
+    "include",
+    "skip",
+    "ifdef",
+    "deprecated",
+    "specifiedBy",
+    "oneOf",
+];
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionSchema {
+    query_type: NamedType,
+    mutation_type: Option<NamedType>,
+    subscription_type: Option<NamedType>,
+    types: Vec<FullType>,
+    #[serde(default)]
+    directives: Vec<IntrospectionDirective>,
+}
+
+#[derive(Deserialize)]
+struct NamedType {
+    name: String,
+}
+
+#[derive(Deserialize, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Kind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FullType {
+    kind: Kind,
+    name: String,
+    description: Option<String>,
+    #[serde(rename = "specifiedByURL", alias = "specifiedByUrl")]
+    specified_by_url: Option<String>,
+    is_one_of: Option<bool>,
+    fields: Option<Vec<IntrospectionField>>,
+    input_fields: Option<Vec<IntrospectionInputValue>>,
+    interfaces: Option<Vec<TypeRef>>,
+    enum_values: Option<Vec<IntrospectionEnumValue>>,
+    possible_types: Option<Vec<TypeRef>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionField {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    args: Vec<IntrospectionInputValue>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionInputValue {
+    name: String,
+    description: Option<String>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    default_value: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionEnumValue {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionDirective {
+    name: String,
+    description: Option<String>,
+    locations: Vec<String>,
+    #[serde(default)]
+    args: Vec<IntrospectionInputValue>,
+    #[serde(default)]
+    is_repeatable: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypeRef {
+    kind: Kind,
+    name: Option<String>,
+    of_type: Option<Box<TypeRef>>,
+}
+
+fn positioned<T>(node: T) -> Positioned<T> {
+    Positioned::new(node, Pos::default())
+}
+
+fn name(name: &str) -> Positioned<Name> {
+    positioned(Name::new(name))
+}
+
+fn description(description: Option<String>) -> Option<Positioned<String>> {
+    description.map(positioned)
+}
+
+fn deprecated(is_deprecated: bool, reason: Option<String>) -> Vec<Positioned<ConstDirective>> {
+    if !is_deprecated {
+        return Vec::new();
+    }
+    vec![positioned(ConstDirective {
+        name: name("deprecated"),
+        arguments: reason
+            .map(|reason| (name("reason"), positioned(Value::String(reason))))
+            .into_iter()
+            .collect(),
+    })]
+}
+
+impl TypeRef {
+    fn to_type(&self) -> Option<Type> {
+        match self.kind {
+            Kind::NonNull => {
+                let ty = self.of_type.as_ref()?.to_type()?;
+                if !ty.nullable {
+                    return None;
+                }
+                Some(Type {
+                    nullable: false,
+                    ..ty
+                })
+            }
+            Kind::List => Some(Type {
+                base: BaseType::List(Box::new(self.of_type.as_ref()?.to_type()?)),
+                nullable: true,
+            }),
+            _ => Some(Type {
+                base: BaseType::Named(Name::new(self.name.as_ref()?)),
+                nullable: true,
+            }),
+        }
+    }
+
+    fn to_name(&self, coordinate: &str) -> Result<Positioned<Name>, IntrospectionError> {
+        match &self.name {
+            Some(type_name) => Ok(name(type_name)),
+            None => Err(IntrospectionError::InvalidType(coordinate.to_string())),
+        }
+    }
+}
+
+fn convert_type(ty: &TypeRef, coordinate: &str) -> Result<Positioned<Type>, IntrospectionError> {
+    ty.to_type()
+        .map(positioned)
+        .ok_or_else(|| IntrospectionError::InvalidType(coordinate.to_string()))
+}
+
+/// Parses a default value, which is a GraphQL value in the introspection.
+fn parse_default_value(value: &str) -> Option<Value> {
+    let document = parse_query(format!("query($value: Boolean = {}) {{ a }}", value)).ok()?;
+    match document.operations {
+        DocumentOperations::Single(operation) => operation
+            .node
+            .variable_definitions
+            .into_iter()
+            .next()?
+            .node
+            .default_value
+            .map(|value| value.node),
+        DocumentOperations::Multiple(_) => None,
+    }
+}
+
+fn convert_input_values(
+    values: Vec<IntrospectionInputValue>,
+    coordinate: impl Fn(&str) -> String,
+) -> Result<Vec<Positioned<InputValueDefinition>>, IntrospectionError> {
+    values
+        .into_iter()
+        .map(|value| {
+            let coordinate = coordinate(&value.name);
+            let default_value = match value.default_value {
+                Some(default_value) => match parse_default_value(&default_value) {
+                    Some(default_value) => Some(positioned(default_value)),
+                    None => {
+                        return Err(IntrospectionError::InvalidDefaultValue {
+                            coordinate,
+                            value: default_value,
+                        })
+                    }
+                },
+                None => None,
+            };
+            Ok(positioned(InputValueDefinition {
+                description: description(value.description),
+                name: name(&value.name),
+                ty: convert_type(&value.ty, &coordinate)?,
+                default_value,
+                directives: deprecated(value.is_deprecated, value.deprecation_reason),
+            }))
+        })
+        .collect()
+}
+
+fn convert_fields(
+    type_name: &str,
+    fields: Option<Vec<IntrospectionField>>,
+) -> Result<Vec<Positioned<FieldDefinition>>, IntrospectionError> {
+    fields
+        .unwrap_or_default()
+        .into_iter()
+        .map(|field| {
+            let coordinate = format!("{}.{}", type_name, field.name);
+            Ok(positioned(FieldDefinition {
+                description: description(field.description),
+                name: name(&field.name),
+                arguments: convert_input_values(field.args, |arg| {
+                    format!("{}({}:)", coordinate, arg)
+                })?,
+                ty: convert_type(&field.ty, &coordinate)?,
+                directives: deprecated(field.is_deprecated, field.deprecation_reason),
+            }))
+        })
+        .collect()
+}
+
+fn convert_names(
+    type_name: &str,
+    types: Option<Vec<TypeRef>>,
+) -> Result<Vec<Positioned<Name>>, IntrospectionError> {
+    types
+        .unwrap_or_default()
+        .iter()
+        .map(|ty| ty.to_name(type_name))
+        .collect()
+}
+
+fn directive_location(location: &str) -> Option<DirectiveLocation> {
+    Some(match location {
+        "QUERY" => DirectiveLocation::Query,
+        "MUTATION" => DirectiveLocation::Mutation,
+        "SUBSCRIPTION" => DirectiveLocation::Subscription,
+        "FIELD" => DirectiveLocation::Field,
+        "FRAGMENT_DEFINITION" => DirectiveLocation::FragmentDefinition,
+        "FRAGMENT_SPREAD" => DirectiveLocation::FragmentSpread,
+        "INLINE_FRAGMENT" => DirectiveLocation::InlineFragment,
+        "SCHEMA" => DirectiveLocation::Schema,
+        "SCALAR" => DirectiveLocation::Scalar,
+        "OBJECT" => DirectiveLocation::Object,
+        "FIELD_DEFINITION" => DirectiveLocation::FieldDefinition,
+        "ARGUMENT_DEFINITION" => DirectiveLocation::ArgumentDefinition,
+        "INTERFACE" => DirectiveLocation::Interface,
+        "UNION" => DirectiveLocation::Union,
+        "ENUM" => DirectiveLocation::Enum,
+        "ENUM_VALUE" => DirectiveLocation::EnumValue,
+        "INPUT_OBJECT" => DirectiveLocation::InputObject,
+        "INPUT_FIELD_DEFINITION" => DirectiveLocation::InputFieldDefinition,
+        _ => return None,
+    })
+}
+
+impl IntrospectionSchema {
+    fn into_service_document(self) -> Result<ServiceDocument, IntrospectionError> {
+        let mut definitions = Vec::new();
+
+        for ty in self.types {
+            if ty.name.starts_with("__") || BUILTIN_SCALARS.contains(&ty.name.as_str()) {
+                continue;
+            }
+
+            let mut directives = Vec::new();
+            let kind = match ty.kind {
+                Kind::Scalar => {
+                    if let Some(url) = ty.specified_by_url {
+                        directives.push(positioned(ConstDirective {
+                            name: name("specifiedBy"),
+                            arguments: vec![(name("url"), positioned(Value::String(url)))],
+                        }));
+                    }
+                    TypeKind::Scalar
+                }
+                Kind::Object => TypeKind::Object(ObjectType {
+                    implements: convert_names(&ty.name, ty.interfaces)?,
+                    fields: convert_fields(&ty.name, ty.fields)?,
+                }),
+                Kind::Interface => TypeKind::Interface(InterfaceType {
+                    implements: convert_names(&ty.name, ty.interfaces)?,
+                    fields: convert_fields(&ty.name, ty.fields)?,
+                }),
+                Kind::Union => TypeKind::Union(UnionType {
+                    members: convert_names(&ty.name, ty.possible_types)?,
+                }),
+                Kind::Enum => TypeKind::Enum(EnumType {
+                    values: ty
+                        .enum_values
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|value| {
+                            positioned(EnumValueDefinition {
+                                description: description(value.description),
+                                value: name(&value.name),
+                                directives: deprecated(
+                                    value.is_deprecated,
+                                    value.deprecation_reason,
+                                ),
+                            })
+                        })
+                        .collect(),
+                }),
+                Kind::InputObject => {
+                    if ty.is_one_of == Some(true) {
+                        directives.push(positioned(ConstDirective {
+                            name: name("oneOf"),
+                            arguments: Vec::new(),
+                        }));
+                    }
+                    let type_name = &ty.name;
+                    TypeKind::InputObject(InputObjectType {
+                        fields: convert_input_values(
+                            ty.input_fields.unwrap_or_default(),
+                            |field| format!("{}.{}", type_name, field),
+                        )?,
+                    })
+                }
+                Kind::List | Kind::NonNull => {
+                    return Err(IntrospectionError::InvalidType(ty.name));
+                }
+            };
+
+            definitions.push(TypeSystemDefinition::Type(positioned(TypeDefinition {
+                extend: false,
+                description: description(ty.description),
+                name: name(&ty.name),
+                directives,
+                kind,
+            })));
+        }
+
+        for directive in self.directives {
+            if BUILTIN_DIRECTIVES.contains(&directive.name.as_str()) {
+                continue;
+            }
+
+            let locations = directive
+                .locations
+                .iter()
+                .map(|location| {
+                    directive_location(location).map(positioned).ok_or_else(|| {
+                        IntrospectionError::UnknownDirectiveLocation {
+                            directive: directive.name.clone(),
+                            location: location.clone(),
+                        }
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            let directive_name = &directive.name;
+            definitions.push(TypeSystemDefinition::Directive(positioned(
+                DirectiveDefinition {
+                    description: description(directive.description),
+                    name: name(&directive.name),
+                    arguments: convert_input_values(directive.args, |arg| {
+                        format!("@{}({}:)", directive_name, arg)
+                    })?,
+                    is_repeatable: directive.is_repeatable,
+                    locations,
+                },
+            )));
+        }
+
+        definitions.push(TypeSystemDefinition::Schema(positioned(SchemaDefinition {
+            extend: false,
+            directives: Vec::new(),
+            query: Some(name(&self.query_type.name)),
+            mutation: self.mutation_type.map(|ty| name(&ty.name)),
+            subscription: self.subscription_type.map(|ty| name(&ty.name)),
+        })));
+
+        Ok(ServiceDocument { definitions })
+    }
+}
+
+fn export_description(sdl: &mut String, description: &Option<Positioned<String>>, indent: &str) {
+    if let Some(description) = description {
+        let description = description
+            .node
+            .replace("\"\"\"", "\\\"\"\"")
+            .replace("\n", &format!("\n{}", indent));
+        writeln!(
+            sdl,
+            "{}\"\"\"\n{}{}\n{}\"\"\"",
+            indent, indent, description, indent
+        )
+        .ok();
+    }
+}
+
+fn export_directives(sdl: &mut String, directives: &[Positioned<ConstDirective>]) {
+    for directive in directives {
+        write!(sdl, " @{}", directive.node.name.node).ok();
+        if !directive.node.arguments.is_empty() {
+            let arguments = directive
+                .node
+                .arguments
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name.node, value.node))
+                .collect::<Vec<_>>();
+            write!(sdl, "({})", arguments.join(", ")).ok();
+        }
+    }
+}
+
+fn export_input_values(sdl: &mut String, values: &[Positioned<InputValueDefinition>]) {
+    for (i, value) in values.iter().enumerate() {
+        if i != 0 {
+            sdl.push_str(", ");
+        }
+        write!(sdl, "{}: {}", value.node.name.node, value.node.ty.node).ok();
+        if let Some(default_value) = &value.node.default_value {
+            write!(sdl, " = {}", default_value.node).ok();
+        }
+        export_directives(sdl, &value.node.directives);
+    }
+}
+
+fn export_fields(sdl: &mut String, fields: &[Positioned<FieldDefinition>]) {
+    writeln!(sdl, "{{").ok();
+    for field in fields {
+        export_description(sdl, &field.node.description, "\t");
+        write!(sdl, "\t{}", field.node.name.node).ok();
+        if !field.node.arguments.is_empty() {
+            sdl.push('(');
+            export_input_values(sdl, &field.node.arguments);
+            sdl.push(')');
+        }
+        write!(sdl, ": {}", field.node.ty.node).ok();
+        export_directives(sdl, &field.node.directives);
+        writeln!(sdl).ok();
+    }
+    writeln!(sdl, "}}").ok();
+}
+
+fn export_implements(sdl: &mut String, implements: &[Positioned<Name>]) {
+    if !implements.is_empty() {
+        let implements = implements
+            .iter()
+            .map(|name| name.node.as_str())
+            .collect::<Vec<_>>();
+        write!(sdl, " implements {}", implements.join(" & ")).ok();
+    }
+}
+
+/// Exports a service document to SDL, in the same layout as the SDL of a schema.
+fn export_sdl(document: &ServiceDocument) -> String {
+    let mut sdl = String::new();
+
+    for definition in &document.definitions {
+        match definition {
+            TypeSystemDefinition::Type(ty) => {
+                let ty = &ty.node;
+                export_description(&mut sdl, &ty.description, "");
+                if ty.extend {
+                    sdl.push_str("extend ");
+                }
+                let keyword = match &ty.kind {
+                    TypeKind::Scalar => "scalar",
+                    TypeKind::Object(_) => "type",
+                    TypeKind::Interface(_) => "interface",
+                    TypeKind::Union(_) => "union",
+                    TypeKind::Enum(_) => "enum",
+                    TypeKind::InputObject(_) => "input",
+                };
+                write!(sdl, "{} {}", keyword, ty.name.node).ok();
+                match &ty.kind {
+                    TypeKind::Object(ObjectType { implements, .. })
+                    | TypeKind::Interface(InterfaceType { implements, .. }) => {
+                        export_implements(&mut sdl, implements)
+                    }
+                    _ => {}
+                }
+                export_directives(&mut sdl, &ty.directives);
+                match &ty.kind {
+                    TypeKind::Scalar => {
+                        writeln!(sdl).ok();
+                    }
+                    TypeKind::Object(ObjectType { fields, .. })
+                    | TypeKind::Interface(InterfaceType { fields, .. }) => {
+                        sdl.push(' ');
+                        export_fields(&mut sdl, fields);
+                    }
+                    TypeKind::Union(union) => {
+                        let members = union
+                            .members
+                            .iter()
+                            .map(|member| member.node.as_str())
+                            .collect::<Vec<_>>();
+                        writeln!(sdl, " = {}", members.join(" | ")).ok();
+                    }
+                    TypeKind::Enum(enum_type) => {
+                        writeln!(sdl, " {{").ok();
+                        for value in &enum_type.values {
+                            export_description(&mut sdl, &value.node.description, "\t");
+                            write!(sdl, "\t{}", value.node.value.node).ok();
+                            export_directives(&mut sdl, &value.node.directives);
+                            writeln!(sdl).ok();
+                        }
+                        writeln!(sdl, "}}").ok();
+                    }
+                    TypeKind::InputObject(input_object) => {
+                        writeln!(sdl, " {{").ok();
+                        for field in &input_object.fields {
+                            export_description(&mut sdl, &field.node.description, "\t");
+                            sdl.push('\t');
+                            export_input_values(&mut sdl, std::slice::from_ref(field));
+                            writeln!(sdl).ok();
+                        }
+                        writeln!(sdl, "}}").ok();
+                    }
+                }
+            }
+            TypeSystemDefinition::Directive(directive) => {
+                let directive = &directive.node;
+                export_description(&mut sdl, &directive.description, "");
+                write!(sdl, "directive @{}", directive.name.node).ok();
+                if !directive.arguments.is_empty() {
+                    sdl.push('(');
+                    export_input_values(&mut sdl, &directive.arguments);
+                    sdl.push(')');
+                }
+                if directive.is_repeatable {
+                    sdl.push_str(" repeatable");
+                }
+                let locations = directive
+                    .locations
+                    .iter()
+                    .map(|location| format!("{:?}", __DirectiveLocation::from(location.node)))
+                    .collect::<Vec<_>>();
+                writeln!(sdl, " on {}", locations.join(" | ")).ok();
+            }
+            TypeSystemDefinition::Schema(schema) => {
+                let schema = &schema.node;
+                if schema.extend {
+                    sdl.push_str("extend ");
+                }
+                sdl.push_str("schema");
+                export_directives(&mut sdl, &schema.directives);
+                writeln!(sdl, " {{").ok();
+                if let Some(query) = &schema.query {
+                    writeln!(sdl, "\tquery: {}", query.node).ok();
+                }
+                if let Some(mutation) = &schema.mutation {
+                    writeln!(sdl, "\tmutation: {}", mutation.node).ok();
+                }
+                if let Some(subscription) = &schema.subscription {
+                    writeln!(sdl, "\tsubscription: {}", subscription.node).ok();
+                }
+                writeln!(sdl, "}}").ok();
+            }
+        }
+    }
+
+    sdl
+}
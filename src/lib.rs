@@ -78,6 +78,8 @@
 //! - `uuid`: Integrate with the [`uuid` crate](https://crates.io/crates/uuid).
 //! - `string_number`: Enable the [StringNumber](types/struct.StringNumber.html).
 //! - `dataloader`: Support [DataLoader](dataloader/struct.DataLoader.html).
+//! - `document_cache`: Support [caching parsed and validated documents](struct.DocumentCache.html).
+//! - `dynamic_schema`: Support [executable schemas built from SDL](dynamic/index.html).
 //!
 //! ## Integrations
 //!
@@ -167,6 +169,8 @@
 #![cfg_attr(feature = "nightly", feature(doc_cfg))]
 
 mod base;
+#[cfg(feature = "document_cache")]
+mod document_cache;
 mod error;
 mod look_ahead;
 mod model;
@@ -180,9 +184,14 @@ pub mod context;
 #[cfg(feature = "dataloader")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "dataloader")))]
 pub mod dataloader;
+pub mod diff;
+#[cfg(feature = "dynamic_schema")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "dynamic_schema")))]
+pub mod dynamic;
 pub mod extensions;
 pub mod guard;
 pub mod http;
+pub mod introspection;
 pub mod resolver_utils;
 pub mod types;
 pub mod validators;
@@ -211,20 +220,26 @@ pub use async_graphql_value::{
     SerializerError,
 };
 pub use base::{
-    Description, InputObjectType, InputType, InterfaceType, ObjectType, OutputType, Type, UnionType,
+    Description, InputObjectType, InputType, InterfaceType, ObjectType, OneofObjectType,
+    OutputType, Type, UnionType,
 };
+#[cfg(feature = "document_cache")]
+pub use document_cache::DocumentCache;
 pub use error::{
     Error, ErrorExtensionValues, ErrorExtensions, InputValueError, InputValueResult,
     ParseRequestError, PathSegment, Result, ResultExt, ServerError, ServerResult,
 };
 pub use look_ahead::Lookahead;
-pub use registry::CacheControl;
+pub use registry::{CacheControl, SDLExportOptions};
 pub use request::{BatchRequest, Request};
 #[doc(no_inline)]
 pub use resolver_utils::{ContainerType, EnumType, ScalarType};
 pub use response::{BatchResponse, Response};
 pub use schema::{Schema, SchemaBuilder, SchemaEnv};
-pub use validation::{ValidationMode, ValidationResult, VisitorContext};
+pub use validation::{
+    BuiltinRule, DeprecatedUsage, ValidationMode, ValidationResult, ValidationRuleFactory,
+    VisitMode, Visitor, VisitorContext,
+};
 
 pub use context::*;
 #[doc(no_inline)]
@@ -254,7 +269,13 @@ pub type FieldResult<T> = Result<T>;
 /// | rename_args   | Rename all the arguments according to the given case convention. The possible values are "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE".| string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | shareable     | Indicate that the object can be resolved by multiple subgraphs (Federation 2) | bool | Y |
+/// | inaccessible  | Hide the object from the supergraph API (Federation 2) | bool | Y |
+/// | interface_object | Mark the object as an interface object (Federation 2) | bool | Y |
+/// | tag           | Apply a `@tag` with the given name, can be specified multiple times (Federation 2) | string | Y |
+/// | unresolvable  | Declare a key with `resolvable: false` for an entity this subgraph cannot resolve, can be specified multiple times (Federation 2) | string | Y |
 /// | use_type_description | Specifies that the description of the type is on the type declaration. [`Description`]()(derive.Description.html) | bool | Y |
+/// | guard         | Guard of all the fields, checked before the guard of each field | [`Guard`](guard/trait.Guard.html) | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 ///
@@ -270,7 +291,13 @@ pub type FieldResult<T> = Result<T>;
 /// | external      | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
+/// | shareable     | Indicate that the field can be resolved by multiple subgraphs (Federation 2) | bool | Y |
+/// | inaccessible  | Hide the field from the supergraph API (Federation 2) | bool | Y |
+/// | override_from | Take over the resolution of this field from the given subgraph (Federation 2) | string | Y |
+/// | tag           | Apply a `@tag` with the given name, can be specified multiple times (Federation 2) | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | guard_fallback | Resolve the field to `Default::default()`, e.g. `null` for an `Option`, instead of returning an error when a guard fails | bool | Y |
+/// | guard_fallback | Resolve the field to the value of the specified expression instead of returning an error when a guard fails | string | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 ///
@@ -284,6 +311,7 @@ pub type FieldResult<T> = Result<T>;
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | deprecation  | Mark the argument as deprecated, with the reason | string      | Y        |
 /// | complexity   | Custom field complexity. *[See also the Book](https://async-graphql.github.io/async-graphql/en/depth_and_complexity.html).*                 | bool        | Y        |
 /// | complexity   | Custom field complexity.                 | string      | Y        |
 /// | visible      | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
@@ -379,6 +407,12 @@ pub use async_graphql_derive::Object;
 /// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE".| string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | shareable     | Indicate that the object can be resolved by multiple subgraphs (Federation 2) | bool | Y |
+/// | inaccessible  | Hide the object from the supergraph API (Federation 2) | bool | Y |
+/// | interface_object | Mark the object as an interface object (Federation 2) | bool | Y |
+/// | tag           | Apply a `@tag` with the given name, can be specified multiple times (Federation 2) | string | Y |
+/// | unresolvable  | Declare a key with `resolvable: false` for an entity this subgraph cannot resolve, can be specified multiple times (Federation 2) | string | Y |
+/// | guard         | Guard of all the fields, checked before the guard of each field | [`Guard`](guard/trait.Guard.html) | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 ///
@@ -394,7 +428,13 @@ pub use async_graphql_derive::Object;
 /// | external      | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
+/// | shareable     | Indicate that the field can be resolved by multiple subgraphs (Federation 2) | bool | Y |
+/// | inaccessible  | Hide the field from the supergraph API (Federation 2) | bool | Y |
+/// | override_from | Take over the resolution of this field from the given subgraph (Federation 2) | string | Y |
+/// | tag           | Apply a `@tag` with the given name, can be specified multiple times (Federation 2) | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | guard_fallback | Resolve the field to `Default::default()`, e.g. `null` for an `Option`, instead of returning an error when a guard fails | bool | Y |
+/// | guard_fallback | Resolve the field to the value of the specified expression instead of returning an error when a guard fails | string | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 ///
@@ -500,6 +540,7 @@ pub use async_graphql_derive::Enum;
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | deprecation  | Mark the field as deprecated, with the reason | string      | Y        |
 /// | flatten      | Similar to serde (flatten)               | boolean     | Y        |
 /// | skip         | Skip this field, use `Default::default` to get a default value for this field. | bool     | Y        |
 /// | visible      | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
@@ -539,6 +580,63 @@ pub use async_graphql_derive::Enum;
 /// ```
 pub use async_graphql_derive::InputObject;
 
+/// Define a GraphQL oneof input object
+///
+/// A oneof input object is an enum whose variants each carry the value of one field, and exactly
+/// one of its fields must be set. It is marked with `@oneOf` in the SDL.
+///
+/// # Macro parameters
+///
+/// | Attribute     | description               | Type     | Optional |
+/// |---------------|---------------------------|----------|----------|
+/// | name          | Object name               | string   | Y        |
+/// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE".| string   | Y        |
+/// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
+/// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+///
+/// # Field parameters
+///
+/// | Attribute    | description                              | Type        | Optional |
+/// |--------------|------------------------------------------|-------------|----------|
+/// | name         | Field name                               | string      | Y        |
+/// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | deprecation  | Mark the field as deprecated, with the reason | string      | Y        |
+/// | visible      | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
+/// | visible      | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(OneofObject)]
+/// enum UserBy {
+///     Id(ID),
+///     Email(String),
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn user(&self, by: UserBy) -> String {
+///         match by {
+///             UserBy::Id(id) => format!("id: {}", *id),
+///             UserBy::Email(email) => format!("email: {}", email),
+///         }
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let res = schema.execute(r#"{ user(by: { email: "a@b.c" }) }"#).await.into_result().unwrap().data;
+///     assert_eq!(res, value!({ "user": "email: a@b.c" }));
+///
+///     assert!(schema.execute(r#"{ user(by: { id: "1", email: "a@b.c" }) }"#).await.is_err());
+/// });
+/// ```
+pub use async_graphql_derive::OneofObject;
+
 /// Define a GraphQL interface
 ///
 /// *[See also the Book](https://async-graphql.github.io/async-graphql/en/define_interface.html).*
@@ -551,7 +649,11 @@ pub use async_graphql_derive::InputObject;
 /// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE".| string   | Y        |
 /// | rename_args   | Rename all the arguments according to the given case convention. The possible values are "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE".| string   | Y        |
 /// | field         | Fields of this Interface  | [InterfaceField] | N |
+/// | implements    | The name of an interface implemented by this interface, can be specified multiple times | string | Y |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | inaccessible  | Hide the interface from the supergraph API (Federation 2) | bool | Y |
+/// | tag           | Apply a `@tag` with the given name, can be specified multiple times (Federation 2) | string | Y |
+/// | guard         | Guard of all the fields, checked before the guard of each field | [`Guard`](guard/trait.Guard.html) | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 ///
@@ -568,6 +670,10 @@ pub use async_graphql_derive::InputObject;
 /// | external    | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides    | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires    | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
+/// | shareable     | Indicate that the field can be resolved by multiple subgraphs (Federation 2) | bool | Y |
+/// | inaccessible  | Hide the field from the supergraph API (Federation 2) | bool | Y |
+/// | override_from | Take over the resolution of this field from the given subgraph (Federation 2) | string | Y |
+/// | tag           | Apply a `@tag` with the given name, can be specified multiple times (Federation 2) | string | Y |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 ///
@@ -581,6 +687,7 @@ pub use async_graphql_derive::InputObject;
 /// | default      | Use `Default::default` for default value | none        | Y        |
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
+/// | deprecation  | Mark the argument as deprecated, with the reason | string      | Y        |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 ///
@@ -790,6 +897,7 @@ pub use async_graphql_derive::Union;
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | deprecation  | Mark the argument as deprecated, with the reason | string      | Y        |
 /// | visible       | If `false`, it will not be displayed in introspection. *[See also the Book](https://async-graphql.github.io/async-graphql/en/visibility.html).* | bool | Y |
 /// | visible       | Call the specified function. If the return value is `false`, it will not be displayed in introspection. | string | Y |
 ///
@@ -818,6 +926,7 @@ pub use async_graphql_derive::Subscription;
 /// | Attribute   | description               | Type     | Optional |
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Scalar name               | string   | Y        |
+/// | specified_by_url | URL of the specification of the scalar, exposed as `specifiedByURL` in the introspection | string | Y |
 ///
 pub use async_graphql_derive::Scalar;
 
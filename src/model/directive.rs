@@ -1,4 +1,5 @@
 use crate::model::__InputValue;
+use crate::parser::types::DirectiveLocation;
 use crate::{registry, Enum, Object};
 
 /// A Directive can be adjacent to many parts of the GraphQL language, a __DirectiveLocation describes one such possible adjacencies.
@@ -64,6 +65,31 @@ pub enum __DirectiveLocation {
     INPUT_FIELD_DEFINITION,
 }
 
+impl From<DirectiveLocation> for __DirectiveLocation {
+    fn from(location: DirectiveLocation) -> Self {
+        match location {
+            DirectiveLocation::Query => __DirectiveLocation::QUERY,
+            DirectiveLocation::Mutation => __DirectiveLocation::MUTATION,
+            DirectiveLocation::Subscription => __DirectiveLocation::SUBSCRIPTION,
+            DirectiveLocation::Field => __DirectiveLocation::FIELD,
+            DirectiveLocation::FragmentDefinition => __DirectiveLocation::FRAGMENT_DEFINITION,
+            DirectiveLocation::FragmentSpread => __DirectiveLocation::FRAGMENT_SPREAD,
+            DirectiveLocation::InlineFragment => __DirectiveLocation::INLINE_FRAGMENT,
+            DirectiveLocation::Schema => __DirectiveLocation::SCHEMA,
+            DirectiveLocation::Scalar => __DirectiveLocation::SCALAR,
+            DirectiveLocation::Object => __DirectiveLocation::OBJECT,
+            DirectiveLocation::FieldDefinition => __DirectiveLocation::FIELD_DEFINITION,
+            DirectiveLocation::ArgumentDefinition => __DirectiveLocation::ARGUMENT_DEFINITION,
+            DirectiveLocation::Interface => __DirectiveLocation::INTERFACE,
+            DirectiveLocation::Union => __DirectiveLocation::UNION,
+            DirectiveLocation::Enum => __DirectiveLocation::ENUM,
+            DirectiveLocation::EnumValue => __DirectiveLocation::ENUM_VALUE,
+            DirectiveLocation::InputObject => __DirectiveLocation::INPUT_OBJECT,
+            DirectiveLocation::InputFieldDefinition => __DirectiveLocation::INPUT_FIELD_DEFINITION,
+        }
+    }
+}
+
 pub struct __Directive<'a> {
     pub registry: &'a registry::Registry,
     pub directive: &'a registry::MetaDirective,
@@ -79,21 +105,29 @@ impl<'a> __Directive<'a> {
     }
 
     async fn description(&self) -> Option<String> {
-        self.directive.description.map(ToString::to_string)
+        self.directive.description.clone()
     }
 
     async fn locations(&self) -> &Vec<__DirectiveLocation> {
         &self.directive.locations
     }
 
-    async fn args(&self) -> Vec<__InputValue<'a>> {
+    async fn args(
+        &self,
+        #[graphql(default = false)] include_deprecated: bool,
+    ) -> Vec<__InputValue<'a>> {
         self.directive
             .args
             .values()
+            .filter(|input_value| include_deprecated || input_value.deprecation.is_none())
             .map(|input_value| __InputValue {
                 registry: self.registry,
                 input_value,
             })
             .collect()
     }
+
+    async fn is_repeatable(&self) -> bool {
+        self.directive.is_repeatable
+    }
 }
@@ -14,10 +14,14 @@ impl<'a> __Field<'a> {
     }
 
     async fn description(&self) -> Option<String> {
-        self.field.description.map(ToString::to_string)
+        self.field.description.clone()
     }
 
-    async fn args(&self, ctx: &Context<'_>) -> Vec<__InputValue<'a>> {
+    async fn args(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = false)] include_deprecated: bool,
+    ) -> Vec<__InputValue<'a>> {
         self.field
             .args
             .values()
@@ -25,6 +29,7 @@ impl<'a> __Field<'a> {
                 Some(f) => f(ctx),
                 None => true,
             })
+            .filter(|input_value| include_deprecated || input_value.deprecation.is_none())
             .map(|input_value| __InputValue {
                 registry: self.registry,
                 input_value,
@@ -42,6 +47,6 @@ impl<'a> __Field<'a> {
     }
 
     async fn deprecation_reason(&self) -> Option<String> {
-        self.field.deprecation.map(ToString::to_string)
+        self.field.deprecation.clone()
     }
 }
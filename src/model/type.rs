@@ -69,24 +69,12 @@ impl<'a> __Type<'a> {
     async fn description(&self) -> Option<String> {
         match &self.detail {
             TypeDetail::Named(ty) => match ty {
-                registry::MetaType::Scalar { description, .. } => {
-                    description.map(ToString::to_string)
-                }
-                registry::MetaType::Object { description, .. } => {
-                    description.map(ToString::to_string)
-                }
-                registry::MetaType::Interface { description, .. } => {
-                    description.map(ToString::to_string)
-                }
-                registry::MetaType::Union { description, .. } => {
-                    description.map(ToString::to_string)
-                }
-                registry::MetaType::Enum { description, .. } => {
-                    description.map(ToString::to_string)
-                }
-                registry::MetaType::InputObject { description, .. } => {
-                    description.map(ToString::to_string)
-                }
+                registry::MetaType::Scalar { description, .. } => description.clone(),
+                registry::MetaType::Object { description, .. } => description.clone(),
+                registry::MetaType::Interface { description, .. } => description.clone(),
+                registry::MetaType::Union { description, .. } => description.clone(),
+                registry::MetaType::Enum { description, .. } => description.clone(),
+                registry::MetaType::InputObject { description, .. } => description.clone(),
             },
             TypeDetail::NonNull(_) => None,
             TypeDetail::List(_) => None,
@@ -122,8 +110,9 @@ impl<'a> __Type<'a> {
     }
 
     async fn interfaces(&self) -> Option<Vec<__Type<'a>>> {
-        if let TypeDetail::Named(registry::MetaType::Object { name, .. }) = &self.detail {
-            Some(
+        match &self.detail {
+            TypeDetail::Named(registry::MetaType::Object { name, .. })
+            | TypeDetail::Named(registry::MetaType::Interface { name, .. }) => Some(
                 self.registry
                     .implements
                     .get(name)
@@ -131,9 +120,8 @@ impl<'a> __Type<'a> {
                     .iter()
                     .map(|ty| __Type::new(self.registry, ty))
                     .collect(),
-            )
-        } else {
-            None
+            ),
+            _ => None,
         }
     }
 
@@ -186,7 +174,11 @@ impl<'a> __Type<'a> {
         }
     }
 
-    async fn input_fields(&self, ctx: &Context<'_>) -> Option<Vec<__InputValue<'a>>> {
+    async fn input_fields(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = false)] include_deprecated: bool,
+    ) -> Option<Vec<__InputValue<'a>>> {
         if let TypeDetail::Named(registry::MetaType::InputObject { input_fields, .. }) =
             &self.detail
         {
@@ -197,6 +189,7 @@ impl<'a> __Type<'a> {
                         Some(f) => f(ctx),
                         None => true,
                     })
+                    .filter(|input_value| include_deprecated || input_value.deprecation.is_none())
                     .map(|input_value| __InputValue {
                         registry: self.registry,
                         input_value,
@@ -217,4 +210,24 @@ impl<'a> __Type<'a> {
             None
         }
     }
+
+    #[graphql(name = "specifiedByURL")]
+    async fn specified_by_url(&self) -> Option<String> {
+        if let TypeDetail::Named(registry::MetaType::Scalar {
+            specified_by_url, ..
+        }) = &self.detail
+        {
+            specified_by_url.clone()
+        } else {
+            None
+        }
+    }
+
+    async fn is_one_of(&self) -> Option<bool> {
+        if let TypeDetail::Named(registry::MetaType::InputObject { oneof, .. }) = &self.detail {
+            Some(*oneof)
+        } else {
+            None
+        }
+    }
 }
@@ -1,13 +1,163 @@
+use std::any::Any;
 use std::fmt::Write;
 
-use crate::registry::{MetaField, MetaInputValue, MetaType, Registry};
+use crate::registry::{MetaField, MetaInputValue, MetaKey, MetaType, MetaVisibleFn, Registry};
+use crate::{Context, Data, Value};
+
+/// Options for exporting the SDL of a schema, see
+/// [`Schema::sdl_with_options`](struct.Schema.html#method.sdl_with_options).
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct IsPartner(bool);
+///
+/// fn is_partner(ctx: &Context<'_>) -> bool {
+///     ctx.data::<IsPartner>().map(|is_partner| is_partner.0).unwrap_or_default()
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+///
+///     #[graphql(visible = "is_partner")]
+///     async fn partner_value(&self) -> i32 {
+///         20
+///     }
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let public_sdl = schema.sdl_with_options(SDLExportOptions::new().visible_only());
+/// assert!(!public_sdl.contains("partnerValue"));
+/// let partner_sdl = schema.sdl_with_options(SDLExportOptions::new().data(IsPartner(true)));
+/// assert!(partner_sdl.contains("partnerValue"));
+/// ```
+#[derive(Default)]
+pub struct SDLExportOptions {
+    pub(crate) federation: bool,
+    pub(crate) federation_descriptions: bool,
+    pub(crate) sorted_types: bool,
+    pub(crate) sorted_fields: bool,
+    pub(crate) directive_definitions: bool,
+    pub(crate) visibility_data: Option<Data>,
+}
+
+impl SDLExportOptions {
+    /// Create the default options, which export every element of the schema without the
+    /// federation directives.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Export the SDL for Apollo Federation, like `Schema::federation_sdl`.
+    pub fn federation(mut self) -> Self {
+        self.federation = true;
+        self
+    }
+
+    /// Include the descriptions in the federation SDL, which omits them by default.
+    pub fn federation_descriptions(mut self) -> Self {
+        self.federation_descriptions = true;
+        self
+    }
+
+    /// Sort the types by name, instead of following their registration order.
+    pub fn sorted_types(mut self) -> Self {
+        self.sorted_types = true;
+        self
+    }
+
+    /// Sort the fields of the objects, interfaces and input objects by name.
+    pub fn sorted_fields(mut self) -> Self {
+        self.sorted_fields = true;
+        self
+    }
+
+    /// Print the definitions of the custom directives, and the `@specifiedBy` and `@oneOf`
+    /// directives applied to the scalars and input objects.
+    pub fn directive_definitions(mut self) -> Self {
+        self.directive_definitions = true;
+        self
+    }
+
+    /// Only export the types, fields, arguments, input fields and enum values which are visible
+    /// to a request without data.
+    pub fn visible_only(mut self) -> Self {
+        self.visibility_data.get_or_insert_with(Data::default);
+        self
+    }
+
+    /// Only export the elements which are visible to a request with the specified data, e.g. to
+    /// export the schema seen by an audience. It can be called several times to add several
+    /// data.
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.visibility_data
+            .get_or_insert_with(Data::default)
+            .insert(data);
+        self
+    }
+}
+
+/// The options of an export, and the context deciding which elements are visible.
+struct ExportContext<'a> {
+    options: &'a SDLExportOptions,
+    ctx: Option<&'a Context<'a>>,
+}
+
+impl<'a> ExportContext<'a> {
+    fn descriptions(&self) -> bool {
+        !self.options.federation || self.options.federation_descriptions
+    }
+
+    fn is_visible(&self, visible: &Option<MetaVisibleFn>) -> bool {
+        match (self.ctx, visible) {
+            (Some(ctx), Some(f)) => f(ctx),
+            _ => true,
+        }
+    }
+
+    fn is_type_visible(&self, registry: &Registry, name: &str) -> bool {
+        match (self.ctx, registry.types.get(name)) {
+            (Some(ctx), Some(ty)) => ty.is_visible(ctx),
+            _ => true,
+        }
+    }
+}
 
 impl Registry {
-    pub fn export_sdl(&self, federation: bool) -> String {
+    /// Exports the SDL of the schema, where the elements hidden from `ctx` are omitted if it is
+    /// specified.
+    pub fn export_sdl(&self, options: &SDLExportOptions, ctx: Option<&Context<'_>>) -> String {
+        let export_ctx = ExportContext { options, ctx };
+        let federation = options.federation;
         let mut sdl = String::new();
 
-        for ty in self.types.values() {
-            if ty.name().starts_with("__") {
+        if federation && self.federation_2 {
+            writeln!(
+                sdl,
+                "extend schema @link(url: \"https://specs.apollo.dev/federation/v2.3\", import: [{}])\n",
+                FEDERATION_2_IMPORTS
+                    .iter()
+                    .map(|name| format!("\"{}\"", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .ok();
+        }
+
+        let mut types = self.types.values().collect::<Vec<_>>();
+        if options.sorted_types {
+            types.sort_by(|a, b| a.name().cmp(b.name()));
+        }
+
+        for ty in types {
+            if ty.name().starts_with("__") || !export_ctx.is_type_visible(self, ty.name()) {
                 continue;
             }
 
@@ -18,7 +168,11 @@ impl Registry {
                 }
             }
 
-            self.export_type(ty, &mut sdl, federation);
+            self.export_type(ty, &mut sdl, &export_ctx);
+        }
+
+        if options.directive_definitions {
+            self.export_directives(&mut sdl, &export_ctx);
         }
 
         if !federation {
@@ -37,28 +191,41 @@ impl Registry {
     }
 
     fn export_fields<'a, I: Iterator<Item = &'a MetaField>>(
+        &self,
         sdl: &mut String,
         it: I,
-        federation: bool,
+        export_ctx: &ExportContext<'_>,
     ) {
-        for field in it {
+        let federation = export_ctx.options.federation;
+        let mut fields = it.collect::<Vec<_>>();
+        if export_ctx.options.sorted_fields {
+            fields.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        for field in fields {
             if field.name.starts_with("__")
                 || (federation && matches!(&*field.name, "_service" | "_entities"))
+                || !export_ctx.is_visible(&field.visible)
             {
                 continue;
             }
 
-            if field.description.is_some() && !federation {
+            if field.description.is_some() && export_ctx.descriptions() {
                 writeln!(
                     sdl,
                     "\t\"\"\"\n\t{}\n\t\"\"\"",
-                    field.description.unwrap().replace("\n", "\n\t")
+                    field.description.as_deref().unwrap().replace("\n", "\n\t")
                 )
                 .ok();
             }
-            if !field.args.is_empty() {
+            let args = field
+                .args
+                .values()
+                .filter(|arg| export_ctx.is_visible(&arg.visible))
+                .collect::<Vec<_>>();
+            if !args.is_empty() {
                 write!(sdl, "\t{}(", field.name).ok();
-                for (i, arg) in field.args.values().enumerate() {
+                for (i, arg) in args.into_iter().enumerate() {
                     if i != 0 {
                         sdl.push_str(", ");
                     }
@@ -68,27 +235,46 @@ impl Registry {
             } else {
                 write!(sdl, "\t{}: {}", field.name, field.ty).ok();
             }
+            sdl.push_str(&export_deprecation(field.deprecation.as_deref()));
 
             if federation {
                 if field.external {
                     write!(sdl, " @external").ok();
                 }
-                if let Some(requires) = field.requires {
+                if let Some(requires) = &field.requires {
                     write!(sdl, " @requires(fields: \"{}\")", requires).ok();
                 }
-                if let Some(provides) = field.provides {
+                if let Some(provides) = &field.provides {
                     write!(sdl, " @provides(fields: \"{}\")", provides).ok();
                 }
+                if self.federation_2 {
+                    if field.shareable {
+                        write!(sdl, " @shareable").ok();
+                    }
+                    if field.inaccessible {
+                        write!(sdl, " @inaccessible").ok();
+                    }
+                    if let Some(from) = &field.override_from {
+                        write!(sdl, " @override(from: {})", Value::String(from.to_string())).ok();
+                    }
+                    for tag in &field.tags {
+                        write!(sdl, " @tag(name: {})", Value::String(tag.to_string())).ok();
+                    }
+                }
             }
 
             writeln!(sdl).ok();
         }
     }
 
-    fn export_type(&self, ty: &MetaType, sdl: &mut String, federation: bool) {
+    fn export_type(&self, ty: &MetaType, sdl: &mut String, export_ctx: &ExportContext<'_>) {
+        let federation = export_ctx.options.federation;
         match ty {
             MetaType::Scalar {
-                name, description, ..
+                name,
+                description,
+                specified_by_url,
+                ..
             } => {
                 const SYSTEM_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
                 const FEDERATION_SCALARS: &[&str] = &["Any"];
@@ -97,10 +283,21 @@ impl Registry {
                     export_scalar = false;
                 }
                 if export_scalar {
-                    if description.is_some() && !federation {
-                        writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
+                    if description.is_some() && export_ctx.descriptions() {
+                        writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.as_deref().unwrap()).ok();
                     }
-                    writeln!(sdl, "scalar {}", name).ok();
+                    write!(sdl, "scalar {}", name).ok();
+                    if let (Some(url), true) =
+                        (specified_by_url, export_ctx.options.directive_definitions)
+                    {
+                        write!(
+                            sdl,
+                            " @specifiedBy(url: {})",
+                            Value::String(url.to_string())
+                        )
+                        .ok();
+                    }
+                    writeln!(sdl).ok();
                 }
             }
             MetaType::Object {
@@ -108,6 +305,10 @@ impl Registry {
                 fields,
                 extends,
                 keys,
+                shareable,
+                inaccessible,
+                interface_object,
+                tags,
                 description,
                 ..
             } => {
@@ -122,25 +323,35 @@ impl Registry {
                     }
                 }
 
-                if description.is_some() && !federation {
-                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
+                if description.is_some() && export_ctx.descriptions() {
+                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.as_deref().unwrap()).ok();
                 }
                 if federation && *extends {
                     write!(sdl, "extend ").ok();
                 }
                 write!(sdl, "type {} ", name).ok();
-                self.write_implements(sdl, name);
+                self.write_implements(sdl, name, export_ctx);
 
                 if federation {
-                    if let Some(keys) = keys {
-                        for key in keys {
-                            write!(sdl, "@key(fields: \"{}\") ", key).ok();
+                    self.write_keys(sdl, keys);
+                    if self.federation_2 {
+                        if *shareable {
+                            write!(sdl, "@shareable ").ok();
+                        }
+                        if *inaccessible {
+                            write!(sdl, "@inaccessible ").ok();
+                        }
+                        if *interface_object {
+                            write!(sdl, "@interfaceObject ").ok();
+                        }
+                        for tag in tags {
+                            write!(sdl, "@tag(name: {}) ", Value::String(tag.to_string())).ok();
                         }
                     }
                 }
 
                 writeln!(sdl, "{{").ok();
-                Self::export_fields(sdl, fields.values(), federation);
+                self.export_fields(sdl, fields.values(), export_ctx);
                 writeln!(sdl, "}}").ok();
             }
             MetaType::Interface {
@@ -148,27 +359,34 @@ impl Registry {
                 fields,
                 extends,
                 keys,
+                inaccessible,
+                tags,
                 description,
                 ..
             } => {
-                if description.is_some() && !federation {
-                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
+                if description.is_some() && export_ctx.descriptions() {
+                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.as_deref().unwrap()).ok();
                 }
                 if federation && *extends {
                     write!(sdl, "extend ").ok();
                 }
                 write!(sdl, "interface {} ", name).ok();
+                self.write_implements(sdl, name, export_ctx);
+
                 if federation {
-                    if let Some(keys) = keys {
-                        for key in keys {
-                            write!(sdl, "@key(fields: \"{}\") ", key).ok();
+                    self.write_keys(sdl, keys);
+                    if self.federation_2 {
+                        if *inaccessible {
+                            write!(sdl, "@inaccessible ").ok();
+                        }
+                        for tag in tags {
+                            write!(sdl, "@tag(name: {}) ", Value::String(tag.to_string())).ok();
                         }
                     }
                 }
-                self.write_implements(sdl, name);
 
                 writeln!(sdl, "{{").ok();
-                Self::export_fields(sdl, fields.values(), federation);
+                self.export_fields(sdl, fields.values(), export_ctx);
                 writeln!(sdl, "}}").ok();
             }
             MetaType::Enum {
@@ -177,13 +395,22 @@ impl Registry {
                 description,
                 ..
             } => {
-                if description.is_some() && !federation {
-                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
+                if description.is_some() && export_ctx.descriptions() {
+                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.as_deref().unwrap()).ok();
                 }
                 write!(sdl, "enum {} ", name).ok();
                 writeln!(sdl, "{{").ok();
-                for value in enum_values.values() {
-                    writeln!(sdl, "\t{}", value.name).ok();
+                for value in enum_values
+                    .values()
+                    .filter(|value| export_ctx.is_visible(&value.visible))
+                {
+                    writeln!(
+                        sdl,
+                        "\t{}{}",
+                        value.name,
+                        export_deprecation(value.deprecation.as_deref())
+                    )
+                    .ok();
                 }
                 writeln!(sdl, "}}").ok();
             }
@@ -191,15 +418,26 @@ impl Registry {
                 name,
                 input_fields,
                 description,
+                oneof,
                 ..
             } => {
-                if description.is_some() && !federation {
-                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
+                if description.is_some() && export_ctx.descriptions() {
+                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.as_deref().unwrap()).ok();
                 }
                 write!(sdl, "input {} ", name).ok();
+                if *oneof && export_ctx.options.directive_definitions {
+                    write!(sdl, "@oneOf ").ok();
+                }
                 writeln!(sdl, "{{").ok();
-                for field in input_fields.values() {
-                    if let Some(description) = field.description {
+                let mut fields = input_fields
+                    .values()
+                    .filter(|field| export_ctx.is_visible(&field.visible))
+                    .collect::<Vec<_>>();
+                if export_ctx.options.sorted_fields {
+                    fields.sort_by(|a, b| a.name.cmp(&b.name));
+                }
+                for field in fields {
+                    if let Some(description) = &field.description {
                         writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description).ok();
                     }
                     writeln!(sdl, "{}", export_input_value(&field)).ok();
@@ -212,11 +450,14 @@ impl Registry {
                 description,
                 ..
             } => {
-                if description.is_some() && !federation {
-                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
+                if description.is_some() && export_ctx.descriptions() {
+                    writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.as_deref().unwrap()).ok();
                 }
                 write!(sdl, "union {} =", name).ok();
-                for ty in possible_types {
+                for ty in possible_types
+                    .iter()
+                    .filter(|ty| export_ctx.is_type_visible(self, ty))
+                {
                     write!(sdl, " | {}", ty).ok();
                 }
                 writeln!(sdl).ok();
@@ -224,31 +465,99 @@ impl Registry {
         }
     }
 
-    fn write_implements(&self, sdl: &mut String, name: &str) {
+    fn export_directives(&self, sdl: &mut String, export_ctx: &ExportContext<'_>) {
+        const BUILTIN_DIRECTIVES: &[&str] = &["include", "skip", "ifdef"];
+        let mut directives = self
+            .directives
+            .values()
+            .filter(|directive| !BUILTIN_DIRECTIVES.contains(&directive.name.as_str()))
+            .collect::<Vec<_>>();
+        directives.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for directive in directives {
+            if let Some(description) = &directive.description {
+                writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description).ok();
+            }
+            write!(sdl, "directive @{}", directive.name).ok();
+            let args = directive
+                .args
+                .values()
+                .filter(|arg| export_ctx.is_visible(&arg.visible))
+                .map(export_input_value)
+                .collect::<Vec<_>>();
+            if !args.is_empty() {
+                write!(sdl, "({})", args.join(", ")).ok();
+            }
+            if directive.is_repeatable {
+                write!(sdl, " repeatable").ok();
+            }
+            let locations = directive
+                .locations
+                .iter()
+                .map(|location| format!("{:?}", location))
+                .collect::<Vec<_>>();
+            writeln!(sdl, " on {}", locations.join(" | ")).ok();
+        }
+    }
+
+    fn write_keys(&self, sdl: &mut String, keys: &Option<Vec<MetaKey>>) {
+        for key in keys.iter().flatten() {
+            if key.resolvable {
+                write!(sdl, "@key(fields: \"{}\") ", key.fields).ok();
+            } else if self.federation_2 {
+                // Federation 1 cannot declare a key this subgraph does not resolve.
+                write!(sdl, "@key(fields: \"{}\", resolvable: false) ", key.fields).ok();
+            }
+        }
+    }
+
+    fn write_implements(&self, sdl: &mut String, name: &str, export_ctx: &ExportContext<'_>) {
         if let Some(implements) = self.implements.get(name) {
+            let mut implements = implements
+                .iter()
+                .map(AsRef::as_ref)
+                .filter(|interface| export_ctx.is_type_visible(self, interface))
+                .collect::<Vec<&str>>();
+            if export_ctx.options.sorted_types {
+                implements.sort_unstable();
+            }
             if !implements.is_empty() {
-                write!(
-                    sdl,
-                    "implements {} ",
-                    implements
-                        .iter()
-                        .map(AsRef::as_ref)
-                        .collect::<Vec<&str>>()
-                        .join(" & ")
-                )
-                .ok();
+                write!(sdl, "implements {} ", implements.join(" & ")).ok();
             }
         }
     }
 }
 
+const FEDERATION_2_IMPORTS: &[&str] = &[
+    "@key",
+    "@external",
+    "@requires",
+    "@provides",
+    "@shareable",
+    "@inaccessible",
+    "@override",
+    "@tag",
+    "@interfaceObject",
+];
+
 fn export_input_value(input_value: &MetaInputValue) -> String {
+    let deprecation = export_deprecation(input_value.deprecation.as_deref());
     if let Some(default_value) = &input_value.default_value {
         format!(
-            "{}: {} = {}",
-            input_value.name, input_value.ty, default_value
+            "{}: {} = {}{}",
+            input_value.name, input_value.ty, default_value, deprecation
         )
     } else {
-        format!("{}: {}", input_value.name, input_value.ty)
+        format!("{}: {}{}", input_value.name, input_value.ty, deprecation)
+    }
+}
+
+fn export_deprecation(deprecation: Option<&str>) -> String {
+    match deprecation {
+        Some(reason) => format!(
+            " @deprecated(reason: {})",
+            Value::String(reason.to_string())
+        ),
+        None => String::new(),
     }
 }
@@ -14,6 +14,7 @@ use crate::validators::InputValueValidator;
 use crate::{model, Any, Context, Positioned, ServerResult, Type, Value, VisitorContext};
 
 pub use cache_control::CacheControl;
+pub use export_sdl::SDLExportOptions;
 
 fn strip_brackets(type_name: &str) -> Option<&str> {
     if let Some(rest) = type_name.strip_prefix('[') {
@@ -97,10 +98,11 @@ impl<'a> MetaTypeName<'a> {
 
 #[derive(Clone)]
 pub struct MetaInputValue {
-    pub name: &'static str,
-    pub description: Option<&'static str>,
+    pub name: String,
+    pub description: Option<String>,
     pub ty: String,
     pub default_value: Option<String>,
+    pub deprecation: Option<String>,
     pub validator: Option<Arc<dyn InputValueValidator>>,
     pub visible: Option<MetaVisibleFn>,
 }
@@ -121,70 +123,92 @@ pub enum ComplexityType {
 #[derive(Clone)]
 pub struct MetaField {
     pub name: String,
-    pub description: Option<&'static str>,
-    pub args: IndexMap<&'static str, MetaInputValue>,
+    pub description: Option<String>,
+    pub args: IndexMap<String, MetaInputValue>,
     pub ty: String,
-    pub deprecation: Option<&'static str>,
+    pub deprecation: Option<String>,
     pub cache_control: CacheControl,
     pub external: bool,
-    pub requires: Option<&'static str>,
-    pub provides: Option<&'static str>,
+    pub requires: Option<String>,
+    pub provides: Option<String>,
+    pub shareable: bool,
+    pub inaccessible: bool,
+    pub tags: Vec<String>,
+    pub override_from: Option<String>,
     pub visible: Option<MetaVisibleFn>,
     pub compute_complexity: Option<ComplexityType>,
 }
 
 #[derive(Clone)]
 pub struct MetaEnumValue {
-    pub name: &'static str,
-    pub description: Option<&'static str>,
-    pub deprecation: Option<&'static str>,
+    pub name: String,
+    pub description: Option<String>,
+    pub deprecation: Option<String>,
     pub visible: Option<MetaVisibleFn>,
 }
 
-type MetaVisibleFn = fn(&Context<'_>) -> bool;
+/// A function deciding whether an element of the schema is visible to a request.
+pub type MetaVisibleFn = Arc<dyn Fn(&Context<'_>) -> bool + Send + Sync>;
+
+/// A `@key` of a federated entity.
+#[derive(Debug, Clone)]
+pub struct MetaKey {
+    pub fields: String,
+    /// `false` if this subgraph cannot resolve the entity with the key (Federation 2).
+    pub resolvable: bool,
+}
 
 pub enum MetaType {
     Scalar {
         name: String,
-        description: Option<&'static str>,
+        description: Option<String>,
         is_valid: fn(value: &Value) -> bool,
         visible: Option<MetaVisibleFn>,
+        specified_by_url: Option<String>,
     },
     Object {
         name: String,
-        description: Option<&'static str>,
+        description: Option<String>,
         fields: IndexMap<String, MetaField>,
         cache_control: CacheControl,
         extends: bool,
-        keys: Option<Vec<String>>,
+        keys: Option<Vec<MetaKey>>,
+        shareable: bool,
+        inaccessible: bool,
+        interface_object: bool,
+        tags: Vec<String>,
         visible: Option<MetaVisibleFn>,
     },
     Interface {
         name: String,
-        description: Option<&'static str>,
+        description: Option<String>,
         fields: IndexMap<String, MetaField>,
         possible_types: IndexSet<String>,
         extends: bool,
-        keys: Option<Vec<String>>,
+        keys: Option<Vec<MetaKey>>,
+        inaccessible: bool,
+        tags: Vec<String>,
         visible: Option<MetaVisibleFn>,
     },
     Union {
         name: String,
-        description: Option<&'static str>,
+        description: Option<String>,
         possible_types: IndexSet<String>,
         visible: Option<MetaVisibleFn>,
     },
     Enum {
         name: String,
-        description: Option<&'static str>,
-        enum_values: IndexMap<&'static str, MetaEnumValue>,
+        description: Option<String>,
+        enum_values: IndexMap<String, MetaEnumValue>,
         visible: Option<MetaVisibleFn>,
     },
     InputObject {
         name: String,
-        description: Option<&'static str>,
+        description: Option<String>,
         input_fields: IndexMap<String, MetaInputValue>,
         visible: Option<MetaVisibleFn>,
+        /// Whether exactly one of the fields must be set, see `OneofObject`.
+        oneof: bool,
     },
 }
 
@@ -280,10 +304,11 @@ impl MetaType {
 }
 
 pub struct MetaDirective {
-    pub name: &'static str,
-    pub description: Option<&'static str>,
+    pub name: String,
+    pub description: Option<String>,
     pub locations: Vec<model::__DirectiveLocation>,
-    pub args: IndexMap<&'static str, MetaInputValue>,
+    pub args: IndexMap<String, MetaInputValue>,
+    pub is_repeatable: bool,
 }
 
 #[derive(Default)]
@@ -294,6 +319,7 @@ pub struct Registry {
     pub query_type: String,
     pub mutation_type: Option<String>,
     pub subscription_type: Option<String>,
+    pub federation_2: bool,
 }
 
 impl Registry {
@@ -313,6 +339,10 @@ impl Registry {
                     cache_control: Default::default(),
                     extends: false,
                     keys: None,
+                    shareable: false,
+                    inaccessible: false,
+                    interface_object: false,
+                    tags: Default::default(),
                     visible: None,
                 },
             );
@@ -366,16 +396,82 @@ impl Registry {
             });
     }
 
+    /// Returns the interfaces implemented by a type, directly or through the interfaces it
+    /// implements.
+    fn inherited_interfaces(&self, ty: &str) -> HashSet<String> {
+        let mut interfaces = HashSet::new();
+        let mut pending = vec![ty];
+        while let Some(ty) = pending.pop() {
+            for interface in self.implements.get(ty).into_iter().flatten() {
+                if interfaces.insert(interface.clone()) {
+                    pending.push(interface);
+                }
+            }
+        }
+        interfaces
+    }
+
+    /// Returns whether a type implements an interface, directly or through the interfaces it
+    /// implements.
+    pub fn implements_interface(&self, ty: &str, interface: &str) -> bool {
+        match self.implements.get(ty) {
+            Some(interfaces) if interfaces.contains(interface) => true,
+            Some(_) => self.inherited_interfaces(ty).contains(interface),
+            None => false,
+        }
+    }
+
+    /// Adds the interfaces implemented by the interfaces of a type to the interfaces of the type,
+    /// and the object types to the possible types of all the interfaces they implement.
+    pub(crate) fn add_inherited_interfaces(&mut self) {
+        let all_implements = self
+            .implements
+            .keys()
+            .map(|ty| (ty.clone(), self.inherited_interfaces(ty)))
+            .collect::<Vec<_>>();
+
+        for (ty, interfaces) in all_implements {
+            if matches!(self.types.get(&ty), Some(MetaType::Object { .. })) {
+                for interface in &interfaces {
+                    if let Some(MetaType::Interface { possible_types, .. }) =
+                        self.types.get_mut(interface)
+                    {
+                        possible_types.insert(ty.clone());
+                    }
+                }
+            }
+            self.implements.insert(ty, interfaces);
+        }
+
+        // An interface can have the interfaces implementing it as variants, but only object types
+        // are possible types.
+        let interfaces = self
+            .types
+            .values()
+            .filter(|ty| matches!(ty, MetaType::Interface { .. }))
+            .map(|ty| ty.name().to_string())
+            .collect::<HashSet<_>>();
+        for ty in self.types.values_mut() {
+            if let MetaType::Interface { possible_types, .. } = ty {
+                possible_types.retain(|ty| !interfaces.contains(ty));
+            }
+        }
+    }
+
     pub fn add_keys(&mut self, ty: &str, keys: &str) {
         let all_keys = match self.types.get_mut(ty) {
             Some(MetaType::Object { keys: all_keys, .. }) => all_keys,
             Some(MetaType::Interface { keys: all_keys, .. }) => all_keys,
             _ => return,
         };
+        let key = MetaKey {
+            fields: keys.to_string(),
+            resolvable: true,
+        };
         if let Some(all_keys) = all_keys {
-            all_keys.push(keys.to_string());
+            all_keys.push(key);
         } else {
-            *all_keys = Some(vec![keys.to_string()]);
+            *all_keys = Some(vec![key]);
         }
     }
 
@@ -454,6 +550,10 @@ impl Registry {
                             external: false,
                             requires: None,
                             provides: None,
+                            shareable: false,
+                            inaccessible: false,
+                            tags: Default::default(),
+                            override_from: None,
                             visible: None,
                             compute_complexity: None,
                         },
@@ -463,6 +563,10 @@ impl Registry {
                 cache_control: Default::default(),
                 extends: false,
                 keys: None,
+                shareable: false,
+                inaccessible: false,
+                interface_object: false,
+                tags: Default::default(),
                 visible: None,
             },
         );
@@ -483,6 +587,10 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    shareable: false,
+                    inaccessible: false,
+                    tags: Default::default(),
+                    override_from: None,
                     visible: None,
                     compute_complexity: None,
                 },
@@ -496,12 +604,13 @@ impl Registry {
                     args: {
                         let mut args = IndexMap::new();
                         args.insert(
-                            "representations",
+                            "representations".to_string(),
                             MetaInputValue {
-                                name: "representations",
+                                name: "representations".to_string(),
                                 description: None,
                                 ty: "[_Any!]!".to_string(),
                                 default_value: None,
+                                deprecation: None,
                                 validator: None,
                                 visible: None,
                             },
@@ -514,6 +623,10 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    shareable: false,
+                    inaccessible: false,
+                    tags: Default::default(),
+                    override_from: None,
                     visible: None,
                     compute_complexity: None,
                 },
@@ -565,7 +678,8 @@ impl Registry {
         names.into_iter().collect()
     }
 
-    pub fn set_description<T: Type>(&mut self, desc: &'static str) {
+    pub fn set_description<T: Type>(&mut self, desc: impl Into<String>) {
+        let desc = desc.into();
         match self.types.get_mut(&*T::type_name()) {
             Some(MetaType::Scalar { description, .. }) => *description = Some(desc),
             Some(MetaType::Object { description, .. }) => *description = Some(desc),
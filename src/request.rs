@@ -4,6 +4,7 @@ use std::fmt::{self, Debug, Formatter};
 
 use serde::{Deserialize, Deserializer, Serialize};
 
+use crate::parser::types::OperationType;
 use crate::{Data, ParseRequestError, UploadValue, Value, Variables};
 
 /// GraphQL request.
@@ -38,6 +39,11 @@ pub struct Request {
     /// The extensions config of the request.
     #[serde(default)]
     pub extensions: HashMap<String, Value>,
+
+    /// The operation types that this request is allowed to execute, all operation types are
+    /// allowed if it is `None`.
+    #[serde(skip)]
+    pub(crate) allowed_operation_types: Option<Vec<OperationType>>,
 }
 
 impl Request {
@@ -50,6 +56,7 @@ impl Request {
             uploads: Vec::default(),
             data: Data::default(),
             extensions: Default::default(),
+            allowed_operation_types: None,
         }
     }
 
@@ -66,6 +73,31 @@ impl Request {
         Self { variables, ..self }
     }
 
+    /// Only allow the operations of the given types to be executed by this request.
+    ///
+    /// This is set by the transport, for example the integrations only allow queries in the
+    /// requests received with HTTP `GET`. If the selected operation has another type, the response
+    /// contains an error and
+    /// [`Response::is_method_not_allowed`](struct.Response.html#method.is_method_not_allowed)
+    /// returns `true`.
+    pub fn allowed_operation_types(
+        self,
+        operation_types: impl IntoIterator<Item = OperationType>,
+    ) -> Self {
+        Self {
+            allowed_operation_types: Some(operation_types.into_iter().collect()),
+            ..self
+        }
+    }
+
+    /// Returns `true` if this request is allowed to execute an operation of the given type.
+    pub fn is_operation_type_allowed(&self, operation_type: OperationType) -> bool {
+        match &self.allowed_operation_types {
+            Some(allowed_operation_types) => allowed_operation_types.contains(&operation_type),
+            None => true,
+        }
+    }
+
     /// Insert some data for this request.
     pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
         self.data.insert(data);
@@ -101,6 +133,7 @@ impl Debug for Request {
             .field("operation_name", &self.operation_name)
             .field("variables", &self.variables)
             .field("extensions", &self.extensions)
+            .field("allowed_operation_types", &self.allowed_operation_types)
             .finish()
     }
 }
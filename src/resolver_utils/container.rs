@@ -1,7 +1,8 @@
-use std::collections::BTreeMap;
 use std::future::Future;
 use std::pin::Pin;
 
+use indexmap::IndexMap;
+
 use crate::extensions::{ErrorLogger, ExtensionContext, ResolveInfo};
 use crate::parser::types::Selection;
 use crate::registry::MetaType;
@@ -47,6 +48,21 @@ pub trait ContainerType: OutputType {
     async fn find_entity(&self, _: &Context<'_>, _params: &Value) -> ServerResult<Option<Value>> {
         Ok(None)
     }
+
+    /// Find all the GraphQL entities of type `typename` from the given representations at once.
+    ///
+    /// Returns `None` if there is no batched entity resolver for this type, otherwise one result
+    /// per representation, in the same order.
+    ///
+    /// Objects should override this in case they are the query root.
+    async fn find_entities(
+        &self,
+        _: &Context<'_>,
+        _typename: &str,
+        _representations: &[&Value],
+    ) -> Option<Vec<ServerResult<Value>>> {
+        None
+    }
 }
 
 #[async_trait::async_trait]
@@ -58,6 +74,15 @@ impl<T: ContainerType + Send + Sync> ContainerType for &T {
     async fn find_entity(&self, ctx: &Context<'_>, params: &Value) -> ServerResult<Option<Value>> {
         T::find_entity(*self, ctx, params).await
     }
+
+    async fn find_entities(
+        &self,
+        ctx: &Context<'_>,
+        typename: &str,
+        representations: &[&Value],
+    ) -> Option<Vec<ServerResult<Value>>> {
+        T::find_entities(*self, ctx, typename, representations).await
+    }
 }
 
 /// Resolve an container by executing each of the fields concurrently.
@@ -76,7 +101,7 @@ pub async fn resolve_container_serial<'a, T: ContainerType + Send + Sync>(
     resolve_container_inner(ctx, root, false).await
 }
 
-fn insert_value(target: &mut BTreeMap<Name, Value>, name: Name, value: Value) {
+pub(crate) fn insert_value(target: &mut IndexMap<Name, Value>, name: Name, value: Value) {
     if let Some(prev_value) = target.get_mut(&name) {
         if let Value::Object(target_map) = prev_value {
             if let Value::Object(obj) = value {
@@ -120,7 +145,7 @@ async fn resolve_container_inner<'a, T: ContainerType + Send + Sync>(
         results
     };
 
-    let mut map = BTreeMap::new();
+    let mut map = IndexMap::new();
     for (name, value) in res {
         insert_value(&mut map, name, value);
     }
@@ -244,8 +269,10 @@ impl<'a> Fields<'a> {
                     let (type_condition, selection_set) = match selection {
                         Selection::Field(_) => unreachable!(),
                         Selection::FragmentSpread(spread) => {
-                            let fragment =
-                                ctx.query_env.fragments.get(&spread.node.fragment_name.node);
+                            let fragment = ctx
+                                .query_env
+                                .fragments()
+                                .get(&spread.node.fragment_name.node);
                             let fragment = match fragment {
                                 Some(fragment) => fragment,
                                 None => {
@@ -276,9 +303,7 @@ impl<'a> Fields<'a> {
                             || ctx
                                 .schema_env
                                 .registry
-                                .implements
-                                .get(&*introspection_type_name)
-                                .map_or(false, |interfaces| interfaces.contains(condition))
+                                .implements_interface(&introspection_type_name, condition)
                     });
                     if applies_concrete_object {
                         // The fragment applies to the concrete object type.
@@ -20,6 +20,14 @@ pub struct Response {
     /// Errors
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub errors: Vec<ServerError>,
+
+    /// The request failed before the operation was executed.
+    #[serde(skip)]
+    pub(crate) request_error: bool,
+
+    /// The operation was not executed because its type is not allowed by the request.
+    #[serde(skip)]
+    pub(crate) method_not_allowed: bool,
 }
 
 impl Response {
@@ -62,6 +70,26 @@ impl Response {
         self.errors.is_empty()
     }
 
+    /// Returns `true` if the request failed before the operation was executed, for example
+    /// because the query could not be parsed or validated.
+    ///
+    /// Such responses are answered with `400 Bad Request` when the
+    /// `application/graphql-response+json` media type is used, see
+    /// [`ResponseFormat`](http/enum.ResponseFormat.html).
+    #[inline]
+    pub fn is_request_error(&self) -> bool {
+        self.request_error
+    }
+
+    /// Returns `true` if the operation was not executed because its type is not allowed by
+    /// [`Request::allowed_operation_types`](struct.Request.html#method.allowed_operation_types).
+    ///
+    /// The integrations respond with `405 Method Not Allowed` if this is set.
+    #[inline]
+    pub fn is_method_not_allowed(&self) -> bool {
+        self.method_not_allowed
+    }
+
     /// Returns `true` if the response is error.
     #[inline]
     pub fn is_err(&self) -> bool {
@@ -109,6 +137,23 @@ impl BatchResponse {
             BatchResponse::Batch(resp) => resp.iter().all(Response::is_ok),
         }
     }
+
+    /// Returns `true` if all requests failed before their operations were executed.
+    pub fn is_request_error(&self) -> bool {
+        match self {
+            BatchResponse::Single(resp) => resp.is_request_error(),
+            BatchResponse::Batch(resp) => resp.iter().all(Response::is_request_error),
+        }
+    }
+
+    /// Returns `true` if any operation was not executed because its type is not allowed by the
+    /// request.
+    pub fn is_method_not_allowed(&self) -> bool {
+        match self {
+            BatchResponse::Single(resp) => resp.is_method_not_allowed(),
+            BatchResponse::Batch(resp) => resp.iter().any(Response::is_method_not_allowed),
+        }
+    }
 }
 
 impl From<Response> for BatchResponse {
@@ -1,5 +1,6 @@
 use std::any::Any;
-use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::future::Future;
 use std::ops::Deref;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
@@ -8,31 +9,35 @@ use futures_util::stream::{self, Stream, StreamExt};
 use indexmap::map::IndexMap;
 
 use crate::context::{Data, QueryEnvInner, ResolveId};
+use crate::diff::{diff_sdl_registry, SchemaChange};
+#[cfg(feature = "document_cache")]
+use crate::document_cache::DocumentCache;
 use crate::extensions::{ErrorLogger, ExtensionContext, ExtensionFactory, Extensions};
 use crate::model::__DirectiveLocation;
-use crate::parser::parse_query;
-use crate::parser::types::{DocumentOperations, OperationType};
-use crate::registry::{MetaDirective, MetaInputValue, Registry};
+use crate::parser::types::{
+    DocumentOperations, ExecutableDocument, Field, OperationDefinition, OperationType, SelectionSet,
+};
+use crate::parser::{parse_query, parse_query_with_max_tokens};
+use crate::registry::{MetaDirective, MetaInputValue, Registry, SDLExportOptions};
 use crate::resolver_utils::{resolve_container, resolve_container_serial};
 use crate::subscription::collect_subscription_streams;
 use crate::types::QueryRoot;
-use crate::validation::{check_rules, ValidationMode};
+use crate::validation::{
+    check_rules, check_variables, BuiltinRule, ValidationMode, ValidationRuleFactory,
+};
 use crate::{
-    BatchRequest, BatchResponse, CacheControl, ContextBase, ObjectType, QueryEnv, Request,
-    Response, ServerError, SubscriptionType, Type, Value, ID,
+    BatchRequest, BatchResponse, CacheControl, ContextBase, Name, ObjectType, Pos, Positioned,
+    QueryEnv, Request, Response, ServerError, SubscriptionType, Type, ValidationResult, Value, ID,
 };
 
 /// Schema builder
 pub struct SchemaBuilder<Query, Mutation, Subscription> {
-    validation_mode: ValidationMode,
     query: QueryRoot<Query>,
     mutation: Mutation,
     subscription: Subscription,
     registry: Registry,
     data: Data,
-    complexity: Option<usize>,
-    depth: Option<usize>,
-    extensions: Vec<Box<dyn ExtensionFactory>>,
+    options: SchemaOptions,
     enable_federation: bool,
 }
 
@@ -53,13 +58,69 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
 
     /// Set the maximum complexity a query can have. By default, there is no limit.
     pub fn limit_complexity(mut self, complexity: usize) -> Self {
-        self.complexity = Some(complexity);
+        self.options.complexity = Some(complexity);
         self
     }
 
     /// Set the maximum depth a query can have. By default, there is no limit.
     pub fn limit_depth(mut self, depth: usize) -> Self {
-        self.depth = Some(depth);
+        self.options.depth = Some(depth);
+        self
+    }
+
+    /// Set the maximum number of aliases a query can have. By default, there is no limit.
+    pub fn limit_aliases(mut self, aliases: usize) -> Self {
+        self.options.aliases = Some(aliases);
+        self
+    }
+
+    /// Set the maximum number of root fields an operation can select. By default, there is no limit.
+    pub fn limit_root_fields(mut self, root_fields: usize) -> Self {
+        self.options.root_fields = Some(root_fields);
+        self
+    }
+
+    /// Set the maximum number of times the same directive can be applied to a single location,
+    /// that is a field, fragment spread, inline fragment, fragment definition, operation or
+    /// variable definition. By default, there is no limit.
+    pub fn limit_directives(mut self, directives: usize) -> Self {
+        self.options.directives = Some(directives);
+        self
+    }
+
+    /// Set the maximum number of selections a query can have. By default, there is no limit.
+    pub fn limit_selections(mut self, selections: usize) -> Self {
+        self.options.selections = Some(selections);
+        self
+    }
+
+    /// Set the maximum number of tokens a query document can have. By default, there is no limit.
+    ///
+    /// The limit is checked before the document is parsed.
+    pub fn limit_tokens(mut self, tokens: usize) -> Self {
+        self.options.tokens = Some(tokens);
+        self
+    }
+
+    /// Set the maximum number of requests a batch can contain. By default, there is no limit.
+    pub fn limit_batch_size(mut self, batch_size: usize) -> Self {
+        self.options.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Set the maximum number of requests of a batch that are executed concurrently. By default,
+    /// at most 8 requests of a batch are executed concurrently. A batch containing a mutation is
+    /// always executed sequentially.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_concurrency` is zero.
+    pub fn limit_batch_concurrency(mut self, batch_concurrency: usize) -> Self {
+        assert!(
+            batch_concurrency > 0,
+            "The batch concurrency must be greater than 0."
+        );
+        self.options.batch_concurrency = batch_concurrency;
         self
     }
 
@@ -84,7 +145,7 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
     ///     .finish();
     /// ```
     pub fn extension(mut self, extension: impl ExtensionFactory) -> Self {
-        self.extensions.push(Box::new(extension));
+        self.options.extensions.push(Box::new(extension));
         self
     }
 
@@ -94,9 +155,42 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Add a custom validation rule to the schema.
+    ///
+    /// Custom rules are checked in both validation modes, after the built-in rules.
+    pub fn validation_rule(mut self, rule: impl ValidationRuleFactory) -> Self {
+        self.options.validation_rules.push(Box::new(rule));
+        self
+    }
+
+    /// Disable a built-in validation rule.
+    pub fn disable_validation_rule(mut self, rule: BuiltinRule) -> Self {
+        self.options.disabled_rules.insert(rule);
+        self
+    }
+
+    /// Cache parsed and validated documents in the specified cache.
+    #[cfg(feature = "document_cache")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "document_cache")))]
+    pub fn document_cache(mut self, cache: DocumentCache) -> Self {
+        self.options.document_cache = Some(cache.scoped());
+        self
+    }
+
     /// Set the validation mode, default is `ValidationMode::Strict`.
     pub fn validation_mode(mut self, validation_mode: ValidationMode) -> Self {
-        self.validation_mode = validation_mode;
+        self.options.validation_mode = validation_mode;
+        self
+    }
+
+    /// Validate the queries against the visibility of the schema.
+    ///
+    /// By default the `visible` functions only hide types, fields, arguments and enum values from
+    /// the introspection. With this option, the hidden types, fields and arguments are also
+    /// treated as unknown by the validation, so a query using them fails even if the client knows
+    /// their names.
+    pub fn validate_visibility(mut self) -> Self {
+        self.options.validate_visibility = true;
         self
     }
 
@@ -106,6 +200,16 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Enable Apollo Federation 2.
+    ///
+    /// The federation SDL will start with a `@link` schema extension and include the Federation 2
+    /// directives (`@shareable`, `@inaccessible`, `@override`, `@tag` and `@interfaceObject`).
+    pub fn enable_federation_2(mut self) -> Self {
+        self.enable_federation = true;
+        self.registry.federation_2 = true;
+        self
+    }
+
     /// Override the description of the specified type.
     pub fn override_description<T: Type>(mut self, desc: &'static str) -> Self {
         self.registry.set_description::<T>(desc);
@@ -114,19 +218,18 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
 
     /// Build schema.
     pub fn finish(mut self) -> Schema<Query, Mutation, Subscription> {
+        self.registry.add_inherited_interfaces();
+
         // federation
         if self.enable_federation || self.registry.has_entities() {
             self.registry.create_federation_types();
         }
 
         Schema(Arc::new(SchemaInner {
-            validation_mode: self.validation_mode,
             query: self.query,
             mutation: self.mutation,
             subscription: self.subscription,
-            complexity: self.complexity,
-            depth: self.depth,
-            extensions: self.extensions,
+            options: self.options,
             env: SchemaEnv(Arc::new(SchemaEnvInner {
                 registry: self.registry,
                 data: self.data,
@@ -135,6 +238,478 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
     }
 }
 
+/// The number of requests of a batch that are executed concurrently by default.
+const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+
+/// The options of a schema that do not depend on its root types.
+pub(crate) struct SchemaOptions {
+    pub(crate) validation_mode: ValidationMode,
+    pub(crate) validate_visibility: bool,
+    pub(crate) complexity: Option<usize>,
+    pub(crate) depth: Option<usize>,
+    pub(crate) aliases: Option<usize>,
+    pub(crate) root_fields: Option<usize>,
+    pub(crate) directives: Option<usize>,
+    pub(crate) selections: Option<usize>,
+    pub(crate) tokens: Option<usize>,
+    pub(crate) batch_size: Option<usize>,
+    pub(crate) batch_concurrency: usize,
+    pub(crate) extensions: Vec<Box<dyn ExtensionFactory>>,
+    pub(crate) disabled_rules: HashSet<BuiltinRule>,
+    pub(crate) validation_rules: Vec<Box<dyn ValidationRuleFactory>>,
+    #[cfg(feature = "document_cache")]
+    pub(crate) document_cache: Option<DocumentCache>,
+}
+
+impl Default for SchemaOptions {
+    fn default() -> Self {
+        Self {
+            validation_mode: ValidationMode::Strict,
+            validate_visibility: false,
+            complexity: None,
+            depth: None,
+            aliases: None,
+            root_fields: None,
+            directives: None,
+            selections: None,
+            tokens: None,
+            batch_size: None,
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
+            extensions: Default::default(),
+            disabled_rules: Default::default(),
+            validation_rules: Default::default(),
+            #[cfg(feature = "document_cache")]
+            document_cache: None,
+        }
+    }
+}
+
+impl SchemaOptions {
+    fn parse(&self, query: &str) -> Result<ExecutableDocument, ServerError> {
+        match self.tokens {
+            Some(limit_tokens) => parse_query_with_max_tokens(query, limit_tokens),
+            None => parse_query(query),
+        }
+        .map_err(Into::into)
+    }
+
+    pub(crate) async fn prepare_request(
+        &self,
+        schema_env: &SchemaEnv,
+        request: Request,
+        ctx_data: Option<Arc<Data>>,
+        batch_data: Option<Arc<Data>>,
+    ) -> Result<(QueryEnvInner, CacheControl), Response> {
+        let allowed_operation_types = request.allowed_operation_types.clone();
+        let (env, cache_control) = self
+            .create_query_env(schema_env, request, ctx_data, batch_data)
+            .await
+            .map_err(|errors| Response {
+                request_error: true,
+                ..Response::from_errors(errors)
+            })?;
+
+        if let Some(allowed_operation_types) = allowed_operation_types {
+            let ty = env.operation().node.ty;
+            if !allowed_operation_types.contains(&ty) {
+                let err = ServerError::new(format!(
+                    r#"The "{}" operation is not allowed in this request."#,
+                    ty
+                ));
+                let ctx_extension = ExtensionContext {
+                    schema_data: &schema_env.data,
+                    query_data: &env.ctx_data,
+                };
+                env.extensions.error(&ctx_extension, &err);
+                return Err(Response {
+                    request_error: true,
+                    method_not_allowed: true,
+                    ..Response::from_errors(vec![err])
+                });
+            }
+        }
+
+        Ok((env, cache_control))
+    }
+
+    /// Prepares the environment of a request. The data of the request is replaced by `ctx_data`
+    /// if it is set.
+    async fn create_query_env(
+        &self,
+        schema_env: &SchemaEnv,
+        request: Request,
+        ctx_data: Option<Arc<Data>>,
+        batch_data: Option<Arc<Data>>,
+    ) -> Result<(QueryEnvInner, CacheControl), Vec<ServerError>> {
+        // create extension instances
+        let mut extensions: Extensions = self
+            .extensions
+            .iter()
+            .map(|factory| factory.create())
+            .collect::<Vec<_>>()
+            .into();
+
+        let mut request = request;
+        let data = std::mem::take(&mut request.data);
+        let data = ctx_data.unwrap_or_else(|| Arc::new(data));
+        let ctx_extension = ExtensionContext {
+            schema_data: &schema_env.data,
+            query_data: &data,
+        };
+
+        let request = extensions.prepare_request(&ctx_extension, request).await?;
+
+        extensions.parse_start(&ctx_extension, &request.query, &request.variables);
+        #[cfg(feature = "document_cache")]
+        let cached = self
+            .document_cache
+            .as_ref()
+            .and_then(|cache| cache.get(&request.query));
+        #[cfg(feature = "document_cache")]
+        let (document, cached_validation_result) = match cached {
+            Some(cached) => (
+                cached.document.clone(),
+                Some(cached.validation_result.clone()),
+            ),
+            None => (
+                Arc::new(
+                    self.parse(&request.query)
+                        .log_error(&ctx_extension, &extensions)?,
+                ),
+                None,
+            ),
+        };
+        #[cfg(not(feature = "document_cache"))]
+        let (document, cached_validation_result) = (
+            Arc::new(
+                self.parse(&request.query)
+                    .log_error(&ctx_extension, &extensions)?,
+            ),
+            None,
+        );
+        extensions.parse_end(&ctx_extension, &document);
+
+        let operation_name = if let Some(operation_name) = &request.operation_name {
+            match &document.operations {
+                DocumentOperations::Single(_) => None,
+                DocumentOperations::Multiple(operations) => operations
+                    .get_key_value(operation_name.as_str())
+                    .map(|(name, _)| Some(name.clone())),
+            }
+            .ok_or_else(|| {
+                ServerError::new(format!(r#"Unknown operation named "{}""#, operation_name))
+            })
+        } else {
+            match &document.operations {
+                DocumentOperations::Single(_) => Ok(None),
+                DocumentOperations::Multiple(map) if map.len() == 1 => {
+                    Ok(map.keys().next().cloned())
+                }
+                DocumentOperations::Multiple(_) => {
+                    Err(ServerError::new("Operation name required in request."))
+                }
+            }
+        };
+        let operation_name = match operation_name {
+            Ok(operation_name) => operation_name,
+            Err(e) => {
+                extensions.error(&ctx_extension, &e);
+                return Err(vec![e]);
+            }
+        };
+
+        // The visibility functions are called with the context of the request, so the rules which
+        // check the visibility run with the checks of the variables.
+        let visibility_env = if self.validate_visibility {
+            Some(QueryEnv::new(QueryEnvInner {
+                extensions: Vec::new().into(),
+                variables: request.variables.clone(),
+                document: document.clone(),
+                operation_name: operation_name.clone(),
+                uploads: Vec::new(),
+                ctx_data: data.clone(),
+                batch_data: batch_data.clone(),
+                errors: Default::default(),
+            }))
+        } else {
+            None
+        };
+        let operation_field;
+        let inc_resolve_id = AtomicUsize::default();
+        let visibility_ctx = match &visibility_env {
+            Some(env) => {
+                operation_field =
+                    visibility_field(&env.operation().node.ty.to_string(), env.operation().pos);
+                Some(env.create_context(
+                    schema_env,
+                    None,
+                    &operation_field,
+                    ResolveId::root(),
+                    &inc_resolve_id,
+                ))
+            }
+            None => None,
+        };
+
+        // check rules
+        extensions.validation_start(&ctx_extension);
+        let has_variables = document
+            .operations
+            .iter()
+            .any(|(_, operation)| !operation.node.variable_definitions.is_empty());
+        // The name of the operation only matters if the document has several of them.
+        let named_operation = match document.operations.iter().nth(1) {
+            Some(_) => request.operation_name.as_deref(),
+            None => None,
+        };
+        // The checks which depend on the request give the same result for every request using a
+        // document with a single operation and without variables, unless the visibility of the
+        // schema depends on the data of the request.
+        let per_request = has_variables || named_operation.is_some() || visibility_ctx.is_some();
+        let with_variables = |validation_result: Result<ValidationResult, Vec<ServerError>>| {
+            let mut validation_result = validation_result?;
+            check_variables(
+                &schema_env.registry,
+                &document,
+                named_operation,
+                &request.variables,
+                &request.uploads,
+                visibility_ctx.as_ref(),
+                self,
+                &mut validation_result,
+            )?;
+            Ok(validation_result)
+        };
+        let validation_result = match cached_validation_result {
+            Some(validation_result) if !per_request => validation_result,
+            Some(validation_result) => with_variables(validation_result),
+            None => {
+                let validation_result = check_rules(&schema_env.registry, &document, self);
+                #[cfg(feature = "document_cache")]
+                if let (Some(cache), true) = (&self.document_cache, per_request) {
+                    cache.insert(
+                        request.query.clone(),
+                        document.clone(),
+                        validation_result.clone(),
+                    );
+                }
+                let validation_result = with_variables(validation_result);
+                #[cfg(feature = "document_cache")]
+                if let (Some(cache), false) = (&self.document_cache, per_request) {
+                    cache.insert(
+                        request.query.clone(),
+                        document.clone(),
+                        validation_result.clone(),
+                    );
+                }
+                validation_result
+            }
+        }
+        .log_error(&ctx_extension, &extensions)?;
+        extensions.validation_end(&ctx_extension, &validation_result);
+
+        // check limit
+        if let Some(limit_complexity) = self.complexity {
+            if validation_result.complexity > limit_complexity {
+                return Err(vec![ServerError::new("Query is too complex.")])
+                    .log_error(&ctx_extension, &extensions);
+            }
+        }
+
+        if let Some(limit_depth) = self.depth {
+            if validation_result.depth > limit_depth {
+                return Err(vec![ServerError::new("Query is nested too deep.")])
+                    .log_error(&ctx_extension, &extensions);
+            }
+        }
+
+        let env = QueryEnvInner {
+            extensions,
+            variables: request.variables,
+            document,
+            operation_name,
+            uploads: request.uploads,
+            ctx_data: data,
+            batch_data,
+            errors: Default::default(),
+        };
+        Ok((env, validation_result.cache_control))
+    }
+
+    /// Prepare the requests of a batch and execute them with `execute`, honoring the batch limits.
+    pub(crate) async fn execute_batch<F, Fut>(
+        &self,
+        schema_env: &SchemaEnv,
+        batch_request: BatchRequest,
+        data: Data,
+        execute: F,
+    ) -> BatchResponse
+    where
+        F: Fn(QueryEnvInner, CacheControl) -> Fut,
+        Fut: Future<Output = Response>,
+    {
+        let batch_data = Arc::new(data);
+        let prepare =
+            |request| self.prepare_request(schema_env, request, None, Some(batch_data.clone()));
+        let execute = |prepared: Result<(QueryEnvInner, CacheControl), Response>| {
+            let execute = &execute;
+            async move {
+                match prepared {
+                    Ok((env, cache_control)) => execute(env, cache_control).await,
+                    Err(response) => response,
+                }
+            }
+        };
+
+        match batch_request {
+            BatchRequest::Single(request) => {
+                BatchResponse::Single(execute(prepare(request).await).await)
+            }
+            BatchRequest::Batch(requests) => {
+                if let Some(batch_size) = self.batch_size {
+                    if requests.len() > batch_size {
+                        // Every request gets the error, so the responses still match the
+                        // requests by position.
+                        let error = ServerError::new(format!(
+                            "The batch contains {} requests, which exceeds the limit of {}.",
+                            requests.len(),
+                            batch_size
+                        ));
+                        return BatchResponse::Batch(
+                            requests
+                                .iter()
+                                .map(|_| Response {
+                                    request_error: true,
+                                    ..Response::from_errors(vec![error.clone()])
+                                })
+                                .collect(),
+                        );
+                    }
+                }
+
+                let prepared = self
+                    .join_batch(requests.into_iter().map(prepare), false)
+                    .await;
+
+                // Mutations may depend on the effects of the previous requests of the batch, so a
+                // batch containing one is executed sequentially.
+                let sequential = prepared.iter().any(|prepared| {
+                    matches!(prepared, Ok((env, _)) if env.operation().node.ty == OperationType::Mutation)
+                });
+                BatchResponse::Batch(
+                    self.join_batch(prepared.into_iter().map(execute), sequential)
+                        .await,
+                )
+            }
+        }
+    }
+
+    /// Waits for the futures of the requests of a batch, running at most `batch_concurrency` of
+    /// them at a time, or one at a time if `sequential` is true.
+    async fn join_batch<T, Fut>(
+        &self,
+        futures: impl Iterator<Item = Fut>,
+        sequential: bool,
+    ) -> Vec<T>
+    where
+        Fut: Future<Output = T>,
+    {
+        let concurrency = if sequential {
+            1
+        } else {
+            self.batch_concurrency
+        };
+        stream::iter(futures).buffered(concurrency).collect().await
+    }
+}
+
+/// Registers the built-in directives and scalars every schema has.
+pub(crate) fn register_builtins(registry: &mut Registry) {
+    registry.add_directive(MetaDirective {
+        name: "include".to_string(),
+        description: Some("Directs the executor to include this field or fragment only when the `if` argument is true.".to_string()),
+        locations: vec![
+            __DirectiveLocation::FIELD,
+            __DirectiveLocation::FRAGMENT_SPREAD,
+            __DirectiveLocation::INLINE_FRAGMENT
+        ],
+        args: {
+            let mut args = IndexMap::new();
+            args.insert("if".to_string(), MetaInputValue {
+                name: "if".to_string(),
+                description: Some("Included when true.".to_string()),
+                ty: "Boolean!".to_string(),
+                default_value: None,
+                deprecation: None,
+                validator: None,
+                visible: None,
+            });
+            args
+        },
+        is_repeatable: false,
+    });
+
+    registry.add_directive(MetaDirective {
+        name: "skip".to_string(),
+        description: Some(
+            "Directs the executor to skip this field or fragment when the `if` argument is true."
+                .to_string(),
+        ),
+        locations: vec![
+            __DirectiveLocation::FIELD,
+            __DirectiveLocation::FRAGMENT_SPREAD,
+            __DirectiveLocation::INLINE_FRAGMENT,
+        ],
+        args: {
+            let mut args = IndexMap::new();
+            args.insert(
+                "if".to_string(),
+                MetaInputValue {
+                    name: "if".to_string(),
+                    description: Some("Skipped when true.".to_string()),
+                    ty: "Boolean!".to_string(),
+                    default_value: None,
+                    deprecation: None,
+                    validator: None,
+                    visible: None,
+                },
+            );
+            args
+        },
+        is_repeatable: false,
+    });
+
+    registry.add_directive(MetaDirective {
+        name: "ifdef".to_string(),
+        description: Some("Directs the executor to query only when the field exists.".to_string()),
+        locations: vec![__DirectiveLocation::FIELD],
+        args: Default::default(),
+        is_repeatable: false,
+    });
+
+    // register scalars
+    bool::create_type_info(registry);
+    i32::create_type_info(registry);
+    f32::create_type_info(registry);
+    String::create_type_info(registry);
+    ID::create_type_info(registry);
+}
+
+/// Creates the field in whose context the `visible` functions are called outside of the
+/// execution of a field, as they take the context of a field.
+fn visibility_field(name: &str, pos: Pos) -> Positioned<Field> {
+    Positioned::new(
+        Field {
+            alias: None,
+            name: Positioned::new(Name::new(name), pos),
+            arguments: Vec::new(),
+            directives: Vec::new(),
+            selection_set: Positioned::new(SelectionSet::default(), pos),
+        },
+        pos,
+    )
+}
+
 #[doc(hidden)]
 pub struct SchemaEnvInner {
     pub registry: Registry,
@@ -143,7 +718,7 @@ pub struct SchemaEnvInner {
 
 #[doc(hidden)]
 #[derive(Clone)]
-pub struct SchemaEnv(Arc<SchemaEnvInner>);
+pub struct SchemaEnv(pub(crate) Arc<SchemaEnvInner>);
 
 impl Deref for SchemaEnv {
     type Target = SchemaEnvInner;
@@ -153,15 +728,50 @@ impl Deref for SchemaEnv {
     }
 }
 
+impl SchemaEnv {
+    /// Exports the SDL of the schema, hiding the elements which are invisible to a request with
+    /// the data of the options if they have some.
+    pub(crate) fn export_sdl(&self, mut options: SDLExportOptions) -> String {
+        let data = match options.visibility_data.take() {
+            Some(data) => data,
+            None => return self.registry.export_sdl(&options, None),
+        };
+
+        let pos = Pos::default();
+        let env = QueryEnv::new(QueryEnvInner {
+            extensions: Vec::new().into(),
+            variables: Default::default(),
+            document: Arc::new(ExecutableDocument {
+                operations: DocumentOperations::Single(Positioned::new(
+                    OperationDefinition {
+                        ty: OperationType::Query,
+                        variable_definitions: Vec::new(),
+                        directives: Vec::new(),
+                        selection_set: Positioned::new(SelectionSet::default(), pos),
+                    },
+                    pos,
+                )),
+                fragments: Default::default(),
+            }),
+            operation_name: None,
+            uploads: Vec::new(),
+            ctx_data: Arc::new(data),
+            batch_data: None,
+            errors: Default::default(),
+        });
+        let field = visibility_field("sdl", pos);
+        let inc_resolve_id = AtomicUsize::default();
+        let ctx = env.create_context(self, None, &field, ResolveId::root(), &inc_resolve_id);
+        self.registry.export_sdl(&options, Some(&ctx))
+    }
+}
+
 #[doc(hidden)]
 pub struct SchemaInner<Query, Mutation, Subscription> {
-    pub(crate) validation_mode: ValidationMode,
     pub(crate) query: QueryRoot<Query>,
     pub(crate) mutation: Mutation,
     pub(crate) subscription: Subscription,
-    pub(crate) complexity: Option<usize>,
-    pub(crate) depth: Option<usize>,
-    pub(crate) extensions: Vec<Box<dyn ExtensionFactory>>,
+    pub(crate) options: SchemaOptions,
     pub(crate) env: SchemaEnv,
 }
 
@@ -216,7 +826,6 @@ where
         subscription: Subscription,
     ) -> SchemaBuilder<Query, Mutation, Subscription> {
         SchemaBuilder {
-            validation_mode: ValidationMode::Strict,
             query: QueryRoot {
                 inner: query,
                 disable_introspection: false,
@@ -225,9 +834,7 @@ where
             subscription,
             registry: Self::create_registry(),
             data: Default::default(),
-            complexity: None,
-            depth: None,
-            extensions: Default::default(),
+            options: Default::default(),
             enable_federation: false,
         }
     }
@@ -248,65 +855,10 @@ where
             } else {
                 Some(Subscription::type_name().to_string())
             },
+            federation_2: false,
         };
 
-        registry.add_directive(MetaDirective {
-            name: "include",
-            description: Some("Directs the executor to include this field or fragment only when the `if` argument is true."),
-            locations: vec![
-                __DirectiveLocation::FIELD,
-                __DirectiveLocation::FRAGMENT_SPREAD,
-                __DirectiveLocation::INLINE_FRAGMENT
-            ],
-            args: {
-                let mut args = IndexMap::new();
-                args.insert("if", MetaInputValue {
-                    name: "if",
-                    description: Some("Included when true."),
-                    ty: "Boolean!".to_string(),
-                    default_value: None,
-                    validator: None,
-                    visible: None,
-                });
-                args
-            }
-        });
-
-        registry.add_directive(MetaDirective {
-            name: "skip",
-            description: Some("Directs the executor to skip this field or fragment when the `if` argument is true."),
-            locations: vec![
-                __DirectiveLocation::FIELD,
-                __DirectiveLocation::FRAGMENT_SPREAD,
-                __DirectiveLocation::INLINE_FRAGMENT
-            ],
-            args: {
-                let mut args = IndexMap::new();
-                args.insert("if", MetaInputValue {
-                    name: "if",
-                    description: Some("Skipped when true."),
-                    ty: "Boolean!".to_string(),
-                    default_value: None,
-                    validator: None,
-                    visible: None,
-                });
-                args
-            }
-        });
-
-        registry.add_directive(MetaDirective {
-            name: "ifdef",
-            description: Some("Directs the executor to query only when the field exists."),
-            locations: vec![__DirectiveLocation::FIELD],
-            args: Default::default(),
-        });
-
-        // register scalars
-        bool::create_type_info(&mut registry);
-        i32::create_type_info(&mut registry);
-        f32::create_type_info(&mut registry);
-        String::create_type_info(&mut registry);
-        ID::create_type_info(&mut registry);
+        register_builtins(&mut registry);
 
         QueryRoot::<Query>::create_type_info(&mut registry);
         if !Mutation::is_empty() {
@@ -330,12 +882,26 @@ where
 
     /// Returns SDL(Schema Definition Language) of this schema.
     pub fn sdl(&self) -> String {
-        self.0.env.registry.export_sdl(false)
+        self.sdl_with_options(SDLExportOptions::new())
     }
 
     /// Returns Federation SDL(Schema Definition Language) of this schema.
     pub fn federation_sdl(&self) -> String {
-        self.0.env.registry.export_sdl(true)
+        self.sdl_with_options(SDLExportOptions::new().federation())
+    }
+
+    /// Returns SDL(Schema Definition Language) of this schema, exported with the specified
+    /// options.
+    pub fn sdl_with_options(&self, options: SDLExportOptions) -> String {
+        self.0.env.export_sdl(options)
+    }
+
+    /// Compares this schema with a previous version of it described by a SDL document, returning
+    /// the changes classified as breaking, dangerous or safe.
+    ///
+    /// See the [`diff`](diff/index.html) module.
+    pub fn diff_from_sdl(&self, old_sdl: &str) -> crate::parser::Result<Vec<SchemaChange>> {
+        diff_sdl_registry(old_sdl, &self.0.env.registry)
     }
 
     /// Get all names in this schema
@@ -347,100 +913,6 @@ where
         self.0.env.registry.names()
     }
 
-    async fn prepare_request(
-        &self,
-        request: Request,
-    ) -> Result<(QueryEnvInner, CacheControl), Vec<ServerError>> {
-        // create extension instances
-        let mut extensions: Extensions = self
-            .0
-            .extensions
-            .iter()
-            .map(|factory| factory.create())
-            .collect::<Vec<_>>()
-            .into();
-
-        let mut request = request;
-        let data = std::mem::take(&mut request.data);
-        let ctx_extension = ExtensionContext {
-            schema_data: &self.env.data,
-            query_data: &data,
-        };
-
-        let request = extensions.prepare_request(&ctx_extension, request).await?;
-
-        extensions.parse_start(&ctx_extension, &request.query, &request.variables);
-        let document = parse_query(&request.query)
-            .map_err(Into::<ServerError>::into)
-            .log_error(&ctx_extension, &extensions)?;
-        extensions.parse_end(&ctx_extension, &document);
-
-        // check rules
-        extensions.validation_start(&ctx_extension);
-        let validation_result = check_rules(
-            &self.env.registry,
-            &document,
-            Some(&request.variables),
-            self.validation_mode,
-        )
-        .log_error(&ctx_extension, &extensions)?;
-        extensions.validation_end(&ctx_extension, &validation_result);
-
-        // check limit
-        if let Some(limit_complexity) = self.complexity {
-            if validation_result.complexity > limit_complexity {
-                return Err(vec![ServerError::new("Query is too complex.")])
-                    .log_error(&ctx_extension, &extensions);
-            }
-        }
-
-        if let Some(limit_depth) = self.depth {
-            if validation_result.depth > limit_depth {
-                return Err(vec![ServerError::new("Query is nested too deep.")])
-                    .log_error(&ctx_extension, &extensions);
-            }
-        }
-
-        let operation = if let Some(operation_name) = &request.operation_name {
-            match document.operations {
-                DocumentOperations::Single(_) => None,
-                DocumentOperations::Multiple(mut operations) => {
-                    operations.remove(operation_name.as_str())
-                }
-            }
-            .ok_or_else(|| {
-                ServerError::new(format!(r#"Unknown operation named "{}""#, operation_name))
-            })
-        } else {
-            match document.operations {
-                DocumentOperations::Single(operation) => Ok(operation),
-                DocumentOperations::Multiple(map) if map.len() == 1 => {
-                    Ok(map.into_iter().next().unwrap().1)
-                }
-                DocumentOperations::Multiple(_) => {
-                    Err(ServerError::new("Operation name required in request."))
-                }
-            }
-        };
-        let operation = match operation {
-            Ok(operation) => operation,
-            Err(e) => {
-                extensions.error(&ctx_extension, &e);
-                return Err(vec![e]);
-            }
-        };
-
-        let env = QueryEnvInner {
-            extensions,
-            variables: request.variables,
-            operation,
-            fragments: document.fragments,
-            uploads: request.uploads,
-            ctx_data: Arc::new(data),
-        };
-        Ok((env, validation_result.cache_control))
-    }
-
     async fn execute_once(&self, env: QueryEnv) -> Response {
         // execute
         let inc_resolve_id = AtomicUsize::default();
@@ -448,7 +920,7 @@ where
             path_node: None,
             resolve_id: ResolveId::root(),
             inc_resolve_id: &inc_resolve_id,
-            item: &env.operation.node.selection_set,
+            item: &env.operation().node.selection_set,
             schema_env: &self.env,
             query_env: &env,
         };
@@ -459,7 +931,7 @@ where
 
         env.extensions.execution_start(&ctx_extension);
 
-        let data = match &env.operation.node.ty {
+        let data = match &env.operation().node.ty {
             OperationType::Query => resolve_container(&ctx, &self.query).await,
             OperationType::Mutation => resolve_container_serial(&ctx, &self.mutation).await,
             OperationType::Subscription => {
@@ -472,36 +944,60 @@ where
         env.extensions.execution_end(&ctx_extension);
         let extensions = env.extensions.result(&ctx_extension);
 
-        match data {
+        let mut response = match data {
             Ok(data) => Response::new(data),
             Err(e) => Response::from_errors(vec![e]),
         }
-        .extensions(extensions)
+        .extensions(extensions);
+        response
+            .errors
+            .splice(0..0, env.errors.lock().unwrap().drain(..));
+        response
     }
 
     /// Execute a GraphQL query.
     pub async fn execute(&self, request: impl Into<Request>) -> Response {
-        let request = request.into();
-        match self.prepare_request(request).await {
-            Ok((env, cache_control)) => self
-                .execute_once(QueryEnv::new(env))
-                .await
-                .cache_control(cache_control),
-            Err(errors) => Response::from_errors(errors),
+        match self
+            .options
+            .prepare_request(&self.env, request.into(), None, None)
+            .await
+        {
+            Ok((env, cache_control)) => self.execute_prepared(env, cache_control).await,
+            Err(response) => response,
         }
     }
 
+    async fn execute_prepared(&self, env: QueryEnvInner, cache_control: CacheControl) -> Response {
+        self.execute_once(QueryEnv::new(env))
+            .await
+            .cache_control(cache_control)
+    }
+
     /// Execute a GraphQL batch query.
+    ///
+    /// The requests of a batch are executed concurrently, unless one of them is a mutation, in
+    /// which case they are executed one after another. See also
+    /// [`SchemaBuilder::limit_batch_concurrency`] and [`SchemaBuilder::limit_batch_size`].
     pub async fn execute_batch(&self, batch_request: BatchRequest) -> BatchResponse {
-        match batch_request {
-            BatchRequest::Single(request) => BatchResponse::Single(self.execute(request).await),
-            BatchRequest::Batch(requests) => BatchResponse::Batch(
-                futures_util::stream::iter(requests.into_iter())
-                    .then(|request| self.execute(request))
-                    .collect()
-                    .await,
-            ),
-        }
+        self.execute_batch_with_data(batch_request, Data::default())
+            .await
+    }
+
+    /// Execute a GraphQL batch query with some data shared by all the requests of the batch.
+    ///
+    /// The shared data can be accessed through `Context::data` like the data of each request,
+    /// which takes precedence over it. Putting a `DataLoader` in it allows the loads of all the
+    /// requests of the batch to be coalesced.
+    pub async fn execute_batch_with_data(
+        &self,
+        batch_request: BatchRequest,
+        data: Data,
+    ) -> BatchResponse {
+        self.options
+            .execute_batch(&self.env, batch_request, data, |env, cache_control| {
+                self.execute_prepared(env, cache_control)
+            })
+            .await
     }
 
     pub(crate) fn execute_stream_with_ctx_data(
@@ -513,17 +1009,16 @@ where
 
         async_stream::stream! {
             let request = request.into();
-            let (mut env, cache_control) = match schema.prepare_request(request).await {
+            let (env, cache_control) = match schema.options.prepare_request(&schema.env, request, Some(ctx_data), None).await {
                 Ok(res) => res,
-                Err(errors) => {
-                    yield Response::from_errors(errors);
+                Err(response) => {
+                    yield response;
                     return;
                 }
             };
-            env.ctx_data = ctx_data;
             let env = QueryEnv::new(env);
 
-            if env.operation.node.ty != OperationType::Subscription {
+            if env.operation().node.ty != OperationType::Subscription {
                 yield schema
                     .execute_once(env)
                     .await
@@ -535,7 +1030,7 @@ where
             let ctx = env.create_context(
                 &schema.env,
                 None,
-                &env.operation.node.selection_set,
+                &env.operation().node.selection_set,
                 ResolveId::root(),
                 &resolve_id,
             );
@@ -561,7 +1056,7 @@ where
                 let extensions = env.extensions.result(&ctx_extension);
                 yield match data {
                     Ok((name, value)) => {
-                        let mut map = BTreeMap::new();
+                        let mut map = IndexMap::new();
                         map.insert(name, value);
                         Response::new(Value::Object(map))
                     },
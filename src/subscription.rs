@@ -63,7 +63,7 @@ pub(crate) fn collect_subscription_streams<'a, T: SubscriptionType + Send + Sync
             Selection::FragmentSpread(fragment_spread) => {
                 if let Some(fragment) = ctx
                     .query_env
-                    .fragments
+                    .fragments()
                     .get(&fragment_spread.node.fragment_name.node)
                 {
                     collect_subscription_streams(
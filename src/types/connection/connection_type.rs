@@ -151,7 +151,7 @@ where
                         "pageInfo".to_string(),
                         registry::MetaField {
                             name: "pageInfo".to_string(),
-                            description: Some("Information to aid in pagination."),
+                            description: Some("Information to aid in pagination.".to_string()),
                             args: Default::default(),
                             ty: PageInfo::create_type_info(registry),
                             deprecation: None,
@@ -159,6 +159,10 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            shareable: false,
+                            inaccessible: false,
+                            tags: Default::default(),
+                            override_from: None,
                             visible: None,
                             compute_complexity: None,
                         },
@@ -168,7 +172,7 @@ where
                         "edges".to_string(),
                         registry::MetaField {
                             name: "edges".to_string(),
-                            description: Some("A list of edges."),
+                            description: Some("A list of edges.".to_string()),
                             args: Default::default(),
                             ty: <Option<Vec<Option<Edge<C, T, EE>>>> as Type>::create_type_info(
                                 registry,
@@ -178,6 +182,10 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            shareable: false,
+                            inaccessible: false,
+                            tags: Default::default(),
+                            override_from: None,
                             visible: None,
                             compute_complexity: None,
                         },
@@ -189,6 +197,10 @@ where
                 cache_control: Default::default(),
                 extends: false,
                 keys: None,
+                shareable: false,
+                inaccessible: false,
+                interface_object: false,
+                tags: Default::default(),
                 visible: None,
             }
         })
@@ -62,7 +62,7 @@ where
 
             registry::MetaType::Object {
                 name: Self::type_name().to_string(),
-                description: Some("An edge in a connection."),
+                description: Some("An edge in a connection.".to_string()),
                 fields: {
                     let mut fields = IndexMap::new();
 
@@ -70,7 +70,7 @@ where
                         "node".to_string(),
                         registry::MetaField {
                             name: "node".to_string(),
-                            description: Some("The item at the end of the edge"),
+                            description: Some("The item at the end of the edge".to_string()),
                             args: Default::default(),
                             ty: T::create_type_info(registry),
                             deprecation: None,
@@ -78,6 +78,10 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            shareable: false,
+                            inaccessible: false,
+                            tags: Default::default(),
+                            override_from: None,
                             visible: None,
                             compute_complexity: None,
                         },
@@ -87,7 +91,7 @@ where
                         "cursor".to_string(),
                         registry::MetaField {
                             name: "cursor".to_string(),
-                            description: Some("A cursor for use in pagination"),
+                            description: Some("A cursor for use in pagination".to_string()),
                             args: Default::default(),
                             ty: String::create_type_info(registry),
                             deprecation: None,
@@ -95,6 +99,10 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            shareable: false,
+                            inaccessible: false,
+                            tags: Default::default(),
+                            override_from: None,
                             visible: None,
                             compute_complexity: None,
                         },
@@ -106,6 +114,10 @@ where
                 cache_control: Default::default(),
                 extends: false,
                 keys: None,
+                shareable: false,
+                inaccessible: false,
+                interface_object: false,
+                tags: Default::default(),
                 visible: None,
             }
         })
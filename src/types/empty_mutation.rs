@@ -44,6 +44,10 @@ impl Type for EmptyMutation {
             cache_control: Default::default(),
             extends: false,
             keys: None,
+            shareable: false,
+            inaccessible: false,
+            interface_object: false,
+            tags: Default::default(),
             visible: None,
         })
     }
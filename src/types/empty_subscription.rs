@@ -24,6 +24,10 @@ impl Type for EmptySubscription {
             cache_control: Default::default(),
             extends: false,
             keys: None,
+            shareable: false,
+            inaccessible: false,
+            interface_object: false,
+            tags: Default::default(),
             visible: None,
         })
     }
@@ -21,7 +21,11 @@ impl ScalarType for ObjectId {
 }
 
 #[cfg(feature = "chrono")]
-#[Scalar(internal, name = "DateTime")]
+#[Scalar(
+    internal,
+    name = "DateTime",
+    specified_by_url = "https://datatracker.ietf.org/doc/html/rfc3339"
+)]
 impl ScalarType for UtcDateTime {
     fn parse(value: Value) -> InputValueResult<Self> {
         <DateTime<Utc>>::parse(value)
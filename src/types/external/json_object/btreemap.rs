@@ -1,5 +1,7 @@
 use std::collections::BTreeMap;
 
+use indexmap::IndexMap;
+
 use crate::{
     InputType, InputValueError, InputValueResult, Name, OutputType, Scalar, ScalarType, Value,
 };
@@ -22,7 +24,7 @@ where
     }
 
     fn to_value(&self) -> Value {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         for (name, value) in self {
             map.insert(Name::new(name), value.to_value());
         }
@@ -1,4 +1,6 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
 
 use crate::{
     InputType, InputValueError, InputValueResult, Name, OutputType, Scalar, ScalarType, Value,
@@ -22,8 +24,11 @@ where
     }
 
     fn to_value(&self) -> Value {
-        let mut map = BTreeMap::new();
-        for (name, value) in self {
+        // Sort the keys, so the output doesn't depend on the iteration order of the map.
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_by_key(|(name, _)| *name);
+        let mut map = IndexMap::new();
+        for (name, value) in items {
             map.insert(Name::new(name), value.to_value());
         }
         Value::Object(map)
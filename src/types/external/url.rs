@@ -2,7 +2,10 @@ use url::Url;
 
 use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
 
-#[Scalar(internal)]
+#[Scalar(
+    internal,
+    specified_by_url = "https://datatracker.ietf.org/doc/html/rfc3986"
+)]
 impl ScalarType for Url {
     fn parse(value: Value) -> InputValueResult<Self> {
         match value {
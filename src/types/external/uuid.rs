@@ -2,7 +2,11 @@ use uuid::Uuid;
 
 use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
 
-#[Scalar(internal, name = "UUID")]
+#[Scalar(
+    internal,
+    name = "UUID",
+    specified_by_url = "https://datatracker.ietf.org/doc/html/rfc4122"
+)]
 impl ScalarType for Uuid {
     fn parse(value: Value) -> InputValueResult<Self> {
         match value {
@@ -51,6 +51,10 @@ impl<A: Type, B: Type> Type for MergedObject<A, B> {
                 cache_control: cc,
                 extends: false,
                 keys: None,
+                shareable: false,
+                inaccessible: false,
+                interface_object: false,
+                tags: Default::default(),
                 visible: None,
             }
         })
@@ -82,6 +86,18 @@ where
             Err(err) => Err(err),
         }
     }
+
+    async fn find_entities(
+        &self,
+        ctx: &Context<'_>,
+        typename: &str,
+        representations: &[&ConstValue],
+    ) -> Option<Vec<ServerResult<ConstValue>>> {
+        match self.0.find_entities(ctx, typename, representations).await {
+            Some(values) => Some(values),
+            None => self.1.find_entities(ctx, typename, representations).await,
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -25,6 +25,9 @@ pub use maybe_undefined::MaybeUndefined;
 pub use merged_object::{MergedObject, MergedObjectTail};
 #[cfg(feature = "string_number")]
 pub use string_number::StringNumber;
-pub use upload::{Upload, UploadValue};
+pub(crate) use upload::content_type_matches;
+pub use upload::{Upload, UploadContent, UploadStream, UploadValue};
 
 pub(crate) use query_root::QueryRoot;
+#[cfg(feature = "dynamic_schema")]
+pub(crate) use query_root::{add_introspection_fields, resolve_introspection_field};
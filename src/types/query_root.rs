@@ -6,8 +6,8 @@ use crate::model::{__Schema, __Type};
 use crate::parser::types::Field;
 use crate::resolver_utils::{resolve_container, ContainerType};
 use crate::{
-    registry, Any, Context, ContextSelectionSet, ObjectType, OutputType, Positioned, ServerError,
-    ServerResult, SimpleObject, Type, Value,
+    registry, Any, Context, ContextSelectionSet, ObjectType, OutputType, PathSegment, Positioned,
+    SDLExportOptions, ServerError, ServerResult, SimpleObject, Type, Value,
 };
 
 /// Federation service
@@ -30,111 +30,203 @@ impl<T: Type> Type for QueryRoot<T> {
     fn create_type_info(registry: &mut registry::Registry) -> String {
         let schema_type = __Schema::create_type_info(registry);
         let root = T::create_type_info(registry);
-        if let Some(registry::MetaType::Object { fields, .. }) =
-            registry.types.get_mut(T::type_name().as_ref())
-        {
-            fields.insert(
-                "__schema".to_string(),
-                registry::MetaField {
-                    name: "__schema".to_string(),
-                    description: Some("Access the current type schema of this server."),
-                    args: Default::default(),
-                    ty: schema_type,
-                    deprecation: None,
-                    cache_control: Default::default(),
-                    external: false,
-                    requires: None,
-                    provides: None,
-                    visible: None,
-                    compute_complexity: None,
-                },
-            );
-
-            fields.insert(
-                "__type".to_string(),
-                registry::MetaField {
-                    name: "__type".to_string(),
-                    description: Some("Request the type information of a single type."),
-                    args: {
-                        let mut args = IndexMap::new();
-                        args.insert(
-                            "name",
-                            registry::MetaInputValue {
-                                name: "name",
-                                description: None,
-                                ty: "String!".to_string(),
-                                default_value: None,
-                                validator: None,
-                                visible: None,
-                            },
-                        );
-                        args
-                    },
-                    ty: "__Type".to_string(),
-                    deprecation: None,
-                    cache_control: Default::default(),
-                    external: false,
-                    requires: None,
-                    provides: None,
-                    visible: None,
-                    compute_complexity: None,
+        add_introspection_fields(registry, &T::type_name(), schema_type);
+        root
+    }
+}
+
+/// Adds the `__schema` and `__type` fields to the query root type.
+pub(crate) fn add_introspection_fields(
+    registry: &mut registry::Registry,
+    query_type: &str,
+    schema_type: String,
+) {
+    if let Some(registry::MetaType::Object { fields, .. }) = registry.types.get_mut(query_type) {
+        fields.insert(
+            "__schema".to_string(),
+            registry::MetaField {
+                name: "__schema".to_string(),
+                description: Some("Access the current type schema of this server.".to_string()),
+                args: Default::default(),
+                ty: schema_type,
+                deprecation: None,
+                cache_control: Default::default(),
+                external: false,
+                requires: None,
+                provides: None,
+                shareable: false,
+                inaccessible: false,
+                tags: Default::default(),
+                override_from: None,
+                visible: None,
+                compute_complexity: None,
+            },
+        );
+
+        fields.insert(
+            "__type".to_string(),
+            registry::MetaField {
+                name: "__type".to_string(),
+                description: Some("Request the type information of a single type.".to_string()),
+                args: {
+                    let mut args = IndexMap::new();
+                    args.insert(
+                        "name".to_string(),
+                        registry::MetaInputValue {
+                            name: "name".to_string(),
+                            description: None,
+                            ty: "String!".to_string(),
+                            default_value: None,
+                            deprecation: None,
+                            validator: None,
+                            visible: None,
+                        },
+                    );
+                    args
                 },
-            );
+                ty: "__Type".to_string(),
+                deprecation: None,
+                cache_control: Default::default(),
+                external: false,
+                requires: None,
+                provides: None,
+                shareable: false,
+                inaccessible: false,
+                tags: Default::default(),
+                override_from: None,
+                visible: None,
+                compute_complexity: None,
+            },
+        );
+    }
+}
+
+/// Resolves the `__schema` and `__type` fields of the query root, returns `None` for any other
+/// field.
+pub(crate) async fn resolve_introspection_field(
+    ctx: &Context<'_>,
+    disable_introspection: bool,
+) -> ServerResult<Option<Value>> {
+    if ctx.item.node.name.node == "__schema" {
+        if disable_introspection {
+            return Err(ServerError::new("Query introspection is disabled.").at(ctx.item.pos));
         }
-        root
+
+        let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+        return OutputType::resolve(
+            &__Schema {
+                registry: &ctx.schema_env.registry,
+            },
+            &ctx_obj,
+            ctx.item,
+        )
+        .await
+        .map(Some);
+    } else if ctx.item.node.name.node == "__type" {
+        let type_name: String = ctx.param_value("name", None)?;
+        let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+        return OutputType::resolve(
+            &ctx.schema_env
+                .registry
+                .types
+                .get(&type_name)
+                .filter(|ty| ty.is_visible(ctx))
+                .map(|ty| __Type::new_simple(&ctx.schema_env.registry, ty)),
+            &ctx_obj,
+            ctx.item,
+        )
+        .await
+        .map(Some);
     }
+    Ok(None)
 }
 
 #[async_trait::async_trait]
 impl<T: ObjectType + Send + Sync> ContainerType for QueryRoot<T> {
     async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<Value>> {
-        if ctx.item.node.name.node == "__schema" {
-            if self.disable_introspection {
-                return Err(ServerError::new("Query introspection is disabled.").at(ctx.item.pos));
-            }
+        if let Some(value) = resolve_introspection_field(ctx, self.disable_introspection).await? {
+            return Ok(Some(value));
+        }
 
-            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-            return OutputType::resolve(
-                &__Schema {
-                    registry: &ctx.schema_env.registry,
-                },
-                &ctx_obj,
-                ctx.item,
-            )
-            .await
-            .map(Some);
-        } else if ctx.item.node.name.node == "__type" {
-            let type_name: String = ctx.param_value("name", None)?;
-            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-            return OutputType::resolve(
-                &ctx.schema_env
-                    .registry
-                    .types
-                    .get(&type_name)
-                    .filter(|ty| ty.is_visible(ctx))
-                    .map(|ty| __Type::new_simple(&ctx.schema_env.registry, ty)),
-                &ctx_obj,
-                ctx.item,
-            )
-            .await
-            .map(Some);
-        } else if ctx.item.node.name.node == "_entities" {
+        if ctx.item.node.name.node == "_entities" {
             let representations: Vec<Any> = ctx.param_value("representations", None)?;
-            let mut res = Vec::new();
-            for item in representations {
-                res.push(
-                    self.inner
-                        .find_entity(ctx, &item.0)
-                        .await?
-                        .ok_or_else(|| ServerError::new("Entity not found.").at(ctx.item.pos))?,
-                );
+            let mut results: Vec<Option<ServerResult<Value>>> =
+                representations.iter().map(|_| None).collect();
+
+            // Group the representations by `__typename`, so that batched entity resolvers
+            // receive all the representations of their type at once.
+            let mut groups: IndexMap<&str, Vec<usize>> = IndexMap::new();
+            for (idx, item) in representations.iter().enumerate() {
+                if let Value::Object(params) = &item.0 {
+                    match params.get("__typename") {
+                        Some(Value::String(typename)) => {
+                            groups.entry(typename.as_str()).or_default().push(idx)
+                        }
+                        _ => {
+                            results[idx] = Some(Err(ServerError::new(
+                                r#""__typename" must be an existing string."#,
+                            )
+                            .at(ctx.item.pos)))
+                        }
+                    }
+                }
+            }
+
+            for (typename, indices) in groups {
+                let group = indices
+                    .iter()
+                    .map(|idx| &representations[*idx].0)
+                    .collect::<Vec<_>>();
+                match self.inner.find_entities(ctx, typename, &group).await {
+                    Some(values) => {
+                        for (idx, value) in indices.into_iter().zip(values) {
+                            results[idx] = Some(value);
+                        }
+                    }
+                    None => {
+                        // Resolve the entities concurrently, so that resolvers using a
+                        // `DataLoader` can still batch their loads.
+                        let values = futures_util::future::join_all(
+                            group
+                                .iter()
+                                .map(|params| self.inner.find_entity(ctx, params)),
+                        )
+                        .await;
+                        for (idx, value) in indices.into_iter().zip(values) {
+                            results[idx] = Some(value.and_then(|value| {
+                                value.ok_or_else(|| {
+                                    ServerError::new("Entity not found.").at(ctx.item.pos)
+                                })
+                            }));
+                        }
+                    }
+                }
+            }
+
+            // A representation that can't be resolved becomes `null`, with an error on its path,
+            // without failing the other entities.
+            let mut res = Vec::with_capacity(results.len());
+            for (idx, value) in results.into_iter().enumerate() {
+                match value
+                    .unwrap_or_else(|| Err(ServerError::new("Entity not found.").at(ctx.item.pos)))
+                {
+                    Ok(value) => res.push(value),
+                    Err(err) => {
+                        ctx.add_error(err.path(PathSegment::Index(idx)));
+                        res.push(Value::Null);
+                    }
+                }
             }
             return Ok(Some(Value::List(res)));
         } else if ctx.item.node.name.node == "_service" {
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputType::resolve(
                 &Service {
-                    sdl: Some(ctx.schema_env.registry.export_sdl(true)),
+                    sdl: Some(
+                        ctx.schema_env
+                            .registry
+                            .export_sdl(&SDLExportOptions::new().federation(), None),
+                    ),
                 },
                 &ctx_obj,
                 ctx.item,
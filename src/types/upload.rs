@@ -1,12 +1,57 @@
+use std::any::Any;
 use std::borrow::Cow;
 use std::fs::File;
-use std::io::Read;
+use std::io::{self, Read};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
 
-#[cfg(feature = "unblock")]
 use futures_util::io::AsyncRead;
+use futures_util::stream::{Stream, TryStreamExt};
 
 use crate::{registry, Context, InputType, InputValueError, InputValueResult, Type, Value};
 
+/// The content of a file upload.
+pub enum UploadContent {
+    /// The content is kept in memory.
+    Memory(Arc<[u8]>),
+    /// The content is stored in a file.
+    File(File),
+    /// The content is still being received, see [`UploadStream`](struct.UploadStream.html).
+    Stream(UploadStream),
+    /// The content was handed to a custom [`UploadStorage`](http/trait.UploadStorage.html),
+    /// which describes where it is stored with this value.
+    Custom(Arc<dyn Any + Send + Sync>),
+}
+
+/// The content of a file upload which is read from the request body while it is arriving.
+///
+/// All the clones of an `UploadStream` share the same underlying stream, so the content can only
+/// be consumed once.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct UploadStream(Arc<Mutex<Pin<Box<dyn Stream<Item = io::Result<Vec<u8>>> + Send>>>>);
+
+impl UploadStream {
+    /// Create an upload stream from a stream of chunks.
+    pub fn new(stream: impl Stream<Item = io::Result<Vec<u8>>> + Send + 'static) -> Self {
+        Self(Arc::new(Mutex::new(Box::pin(stream))))
+    }
+}
+
+impl Stream for UploadStream {
+    type Item = io::Result<Vec<u8>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        match self.0.lock() {
+            Ok(mut stream) => stream.as_mut().poll_next(cx),
+            Err(_) => Poll::Ready(Some(Err(io::Error::other(
+                "The upload stream is poisoned.",
+            )))),
+        }
+    }
+}
+
 /// A file upload value.
 pub struct UploadValue {
     /// The name of the file.
@@ -14,7 +59,9 @@ pub struct UploadValue {
     /// The content type of the file.
     pub content_type: Option<String>,
     /// The file data.
-    pub content: File,
+    pub content: UploadContent,
+    /// The SHA-256 digest of the file, if it was computed while the file was received.
+    pub sha256: Option<[u8; 32]>,
 }
 
 impl UploadValue {
@@ -24,34 +71,102 @@ impl UploadValue {
     /// # Errors
     ///
     /// Fails if cloning the inner `File` fails.
-    pub fn try_clone(&self) -> std::io::Result<Self> {
+    pub fn try_clone(&self) -> io::Result<Self> {
         Ok(Self {
             filename: self.filename.clone(),
             content_type: self.content_type.clone(),
-            content: self.content.try_clone()?,
+            content: match &self.content {
+                UploadContent::Memory(data) => UploadContent::Memory(data.clone()),
+                UploadContent::File(file) => UploadContent::File(file.try_clone()?),
+                UploadContent::Stream(stream) => UploadContent::Stream(stream.clone()),
+                UploadContent::Custom(value) => UploadContent::Custom(value.clone()),
+            },
+            sha256: self.sha256,
         })
     }
 
     /// Convert to a `Read`.
     ///
     /// **Note**: this is a *synchronous/blocking* reader.
-    pub fn into_read(self) -> impl Read + Sync + Send + 'static {
-        self.content
+    ///
+    /// # Errors
+    ///
+    /// Fails if the content is a stream or is stored in a custom storage.
+    pub fn into_read(self) -> io::Result<impl Read + Sync + Send + 'static> {
+        let reader: Box<dyn Read + Sync + Send> = match self.content {
+            UploadContent::Memory(data) => Box::new(io::Cursor::new(data)),
+            UploadContent::File(file) => Box::new(file),
+            UploadContent::Stream(_) => {
+                return Err(io::Error::other(
+                    "A streamed upload can only be read asynchronously.",
+                ))
+            }
+            UploadContent::Custom(_) => return Err(custom_storage_error()),
+        };
+        Ok(reader)
     }
 
-    #[cfg(feature = "unblock")]
-    #[cfg_attr(feature = "nightly", doc(cfg(feature = "unblock")))]
     /// Convert to a `AsyncRead`.
-    pub fn into_async_read(self) -> impl AsyncRead + Sync + Send + 'static {
-        blocking::Unblock::new(self.content)
+    ///
+    /// # Errors
+    ///
+    /// Fails if the content is stored in a custom storage, or if it is stored in a file and the
+    /// `unblock` feature is disabled.
+    pub fn into_async_read(self) -> io::Result<impl AsyncRead + Sync + Send + 'static> {
+        let reader: Box<dyn AsyncRead + Sync + Send + Unpin> = match self.content {
+            UploadContent::Memory(data) => Box::new(futures_util::io::Cursor::new(data)),
+            #[cfg(feature = "unblock")]
+            UploadContent::File(file) => Box::new(blocking::Unblock::new(file)),
+            #[cfg(not(feature = "unblock"))]
+            UploadContent::File(_) => {
+                return Err(io::Error::other(
+                    "Reading a file asynchronously requires the `unblock` feature.",
+                ))
+            }
+            UploadContent::Stream(stream) => Box::new(stream.into_async_read()),
+            UploadContent::Custom(_) => return Err(custom_storage_error()),
+        };
+        Ok(reader)
     }
 
     /// Returns the size of the file, in bytes.
-    pub fn size(&self) -> std::io::Result<u64> {
-        self.content.metadata().map(|meta| meta.len())
+    ///
+    /// # Errors
+    ///
+    /// Fails if the content is a stream, whose size is unknown, or is stored in a custom storage.
+    pub fn size(&self) -> io::Result<u64> {
+        match &self.content {
+            UploadContent::Memory(data) => Ok(data.len() as u64),
+            UploadContent::File(file) => file.metadata().map(|meta| meta.len()),
+            UploadContent::Stream(_) => Err(io::Error::other(
+                "The size of a streamed upload is unknown.",
+            )),
+            UploadContent::Custom(_) => Err(custom_storage_error()),
+        }
     }
 }
 
+/// Returns true if the content type matches the pattern, which may be a wildcard such as `image/*`
+/// or `*/*`.
+pub(crate) fn content_type_matches(pattern: &str, content_type: &str) -> bool {
+    let content_type = content_type.split(';').next().unwrap_or_default().trim();
+    match pattern.trim() {
+        "*" | "*/*" => true,
+        pattern => match pattern.strip_suffix("/*") {
+            Some(ty) => content_type
+                .split('/')
+                .next()
+                .map(|s| s.eq_ignore_ascii_case(ty))
+                .unwrap_or_default(),
+            None => pattern.eq_ignore_ascii_case(content_type),
+        },
+    }
+}
+
+fn custom_storage_error() -> io::Error {
+    io::Error::other("The upload is stored in a custom storage.")
+}
+
 /// Uploaded file
 ///
 /// **Reference:** <https://github.com/jaydenseric/graphql-multipart-request-spec>
@@ -95,7 +210,7 @@ pub struct Upload(usize);
 
 impl Upload {
     /// Get the upload value.
-    pub fn value(&self, ctx: &Context<'_>) -> std::io::Result<UploadValue> {
+    pub fn value(&self, ctx: &Context<'_>) -> io::Result<UploadValue> {
         ctx.query_env.uploads[self.0].try_clone()
     }
 }
@@ -111,6 +226,7 @@ impl Type for Upload {
             description: None,
             is_valid: |value| matches!(value, Value::String(_)),
             visible: None,
+            specified_by_url: None,
         })
     }
 }
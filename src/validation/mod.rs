@@ -10,12 +10,14 @@ mod visitors;
 
 use crate::parser::types::ExecutableDocument;
 use crate::registry::Registry;
-use crate::{CacheControl, ServerError, Variables};
+use crate::schema::SchemaOptions;
+use crate::{CacheControl, Context, Pos, ServerError, UploadValue, Variables};
 
-pub use visitor::VisitorContext;
 use visitor::{visit, VisitorNil};
+pub use visitor::{VisitMode, Visitor, VisitorContext};
 
 /// Validation results.
+#[derive(Clone)]
 pub struct ValidationResult {
     /// Cache control
     pub cache_control: CacheControl,
@@ -25,6 +27,35 @@ pub struct ValidationResult {
 
     /// Query depth
     pub depth: usize,
+
+    /// Number of aliases in the query
+    pub aliases: usize,
+
+    /// Maximum number of root fields selected by an operation
+    pub root_fields: usize,
+
+    /// Maximum number of times the same directive is applied to a single location
+    pub directives: usize,
+
+    /// Total number of selections in the query
+    pub selections: usize,
+
+    /// Uses of deprecated fields, arguments, input fields and enum values by the executed operation
+    pub deprecations: Vec<DeprecatedUsage>,
+}
+
+/// A use of a deprecated element of the schema in a query.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeprecatedUsage {
+    /// The schema coordinate of the element, e.g. `User.name` for a field, `Query.users(first:)`
+    /// for an argument, `UserFilter.name` for an input field or `Role.ADMIN` for an enum value.
+    pub coordinate: String,
+
+    /// The reason of the deprecation.
+    pub reason: String,
+
+    /// The position of the use in the query.
+    pub pos: Pos,
 }
 
 /// Validation mode
@@ -37,56 +68,209 @@ pub enum ValidationMode {
     Fast,
 }
 
+/// The built-in validation rules.
+///
+/// Individual rules can be disabled with
+/// [`SchemaBuilder::disable_validation_rule`](struct.SchemaBuilder.html#method.disable_validation_rule).
+/// Fragment cycles are always rejected, because a document containing one cannot be executed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum BuiltinRule {
+    ArgumentsOfCorrectType,
+    DefaultValuesOfCorrectType,
+    FieldsOnCorrectType,
+    FragmentsOnCompositeTypes,
+    KnownArgumentNames,
+    KnownFragmentNames,
+    KnownTypeNames,
+    NoUndefinedVariables,
+    NoUnusedFragments,
+    NoUnusedVariables,
+    UniqueArgumentNames,
+    UniqueVariableNames,
+    VariablesAreInputTypes,
+    VariableInAllowedPosition,
+    ScalarLeafs,
+    PossibleFragmentSpreads,
+    ProvidedNonNullArguments,
+    KnownDirectives,
+    OverlappingFieldsCanBeMerged,
+    UploadFile,
+}
+
+/// Represents a custom validation rule.
+///
+/// A new visitor is created for every document that is validated.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::parser::types::{OperationDefinition, OperationType};
+///
+/// struct MutationsMustBeNamed;
+///
+/// impl<'a> Visitor<'a> for MutationsMustBeNamed {
+///     fn enter_operation_definition(
+///         &mut self,
+///         ctx: &mut VisitorContext<'a>,
+///         name: Option<&'a Name>,
+///         operation_definition: &'a Positioned<OperationDefinition>,
+///     ) {
+///         if operation_definition.node.ty == OperationType::Mutation && name.is_none() {
+///             ctx.report_error(
+///                 vec![operation_definition.pos],
+///                 "Mutations must have an operation name.",
+///             );
+///         }
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         100
+///     }
+/// }
+///
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .validation_rule(|| MutationsMustBeNamed)
+///     .finish();
+/// ```
+pub trait ValidationRuleFactory: Send + Sync + 'static {
+    /// Create a visitor for validating a document.
+    fn create<'a>(&self) -> Box<dyn Visitor<'a> + 'a>;
+}
+
+impl<F, V> ValidationRuleFactory for F
+where
+    F: Fn() -> V + Send + Sync + 'static,
+    V: for<'a> Visitor<'a> + 'static,
+{
+    fn create<'a>(&self) -> Box<dyn Visitor<'a> + 'a> {
+        Box::new(self())
+    }
+}
+
+macro_rules! rule {
+    ($options:expr, $name:ident) => {
+        if $options.disabled_rules.contains(&BuiltinRule::$name) {
+            None
+        } else {
+            Some(rules::$name)
+        }
+    };
+    ($options:expr, $name:ident::default) => {
+        if $options.disabled_rules.contains(&BuiltinRule::$name) {
+            None
+        } else {
+            Some(rules::$name::default())
+        }
+    };
+}
+
+/// Checks the rules which do not depend on the values of the variables, so the result can be
+/// cached with the document.
+///
+/// The limits of the aliases, root fields, directives and selections are checked first, so a
+/// document exceeding them is rejected before the rules run on it.
+///
+/// The complexity and the deprecations are computed by [`check_variables`], which must be called
+/// even if the document declares no variables.
 pub fn check_rules(
     registry: &Registry,
     doc: &ExecutableDocument,
-    variables: Option<&Variables>,
-    mode: ValidationMode,
+    options: &SchemaOptions,
 ) -> Result<ValidationResult, Vec<ServerError>> {
-    let mut ctx = VisitorContext::new(registry, doc, variables);
+    let mut aliases = 0;
+    let mut root_fields = 0;
+    let mut directives = 0;
+    let mut selections = 0;
+    let mut visitor = VisitorNil
+        .with(visitors::AliasesCalculate::new(
+            &mut aliases,
+            options.aliases,
+        ))
+        .with(visitors::RootFieldsCalculate::new(
+            &mut root_fields,
+            options.root_fields,
+        ))
+        .with(visitors::DirectivesCalculate::new(&mut directives))
+        .with(visitors::SelectionsCalculate::new(
+            &mut selections,
+            options.selections,
+        ));
+    visit(
+        &mut visitor,
+        &mut VisitorContext::new(registry, doc, None),
+        doc,
+    );
+    for (count, limit, message) in &[
+        (aliases, options.aliases, "Query has too many aliases."),
+        (
+            root_fields,
+            options.root_fields,
+            "Query has too many root fields.",
+        ),
+        (
+            directives,
+            options.directives,
+            "Query repeats a directive too many times on a single location.",
+        ),
+        (
+            selections,
+            options.selections,
+            "Query has too many selections.",
+        ),
+    ] {
+        if matches!(limit, Some(limit) if count > limit) {
+            return Err(vec![ServerError::new(*message)]);
+        }
+    }
+
+    let mut ctx = VisitorContext::new(registry, doc, None);
     let mut cache_control = CacheControl::default();
-    let mut complexity = 0;
     let mut depth = 0;
 
-    match mode {
+    match options.validation_mode {
         ValidationMode::Strict => {
             let mut visitor = VisitorNil
-                .with(rules::ArgumentsOfCorrectType::default())
-                .with(rules::DefaultValuesOfCorrectType)
-                .with(rules::FieldsOnCorrectType)
-                .with(rules::FragmentsOnCompositeTypes)
-                .with(rules::KnownArgumentNames::default())
+                .with(rule!(options, DefaultValuesOfCorrectType))
+                .with(rule!(options, FieldsOnCorrectType))
+                .with(rule!(options, FragmentsOnCompositeTypes))
+                .with(rule!(options, KnownArgumentNames::default))
                 .with(rules::NoFragmentCycles::default())
-                .with(rules::KnownFragmentNames)
-                .with(rules::KnownTypeNames)
-                .with(rules::NoUndefinedVariables::default())
-                .with(rules::NoUnusedFragments::default())
-                .with(rules::NoUnusedVariables::default())
-                .with(rules::UniqueArgumentNames::default())
-                .with(rules::UniqueVariableNames::default())
-                .with(rules::VariablesAreInputTypes)
-                .with(rules::VariableInAllowedPosition::default())
-                .with(rules::ScalarLeafs)
-                .with(rules::PossibleFragmentSpreads::default())
-                .with(rules::ProvidedNonNullArguments)
-                .with(rules::KnownDirectives::default())
-                .with(rules::OverlappingFieldsCanBeMerged)
-                .with(rules::UploadFile)
+                .with(rule!(options, KnownFragmentNames))
+                .with(rule!(options, KnownTypeNames))
+                .with(rule!(options, NoUndefinedVariables::default))
+                .with(rule!(options, NoUnusedFragments::default))
+                .with(rule!(options, NoUnusedVariables::default))
+                .with(rule!(options, UniqueArgumentNames::default))
+                .with(rule!(options, UniqueVariableNames::default))
+                .with(rule!(options, VariablesAreInputTypes))
+                .with(rule!(options, VariableInAllowedPosition::default))
+                .with(rule!(options, ScalarLeafs))
+                .with(rule!(options, PossibleFragmentSpreads::default))
+                .with(rule!(options, ProvidedNonNullArguments))
+                .with(rule!(options, KnownDirectives::default))
+                .with(rule!(options, OverlappingFieldsCanBeMerged))
+                .with(rule!(options, UploadFile))
                 .with(visitors::CacheControlCalculate {
                     cache_control: &mut cache_control,
                 })
-                .with(visitors::ComplexityCalculate::new(&mut complexity))
                 .with(visitors::DepthCalculate::new(&mut depth));
             visit(&mut visitor, &mut ctx, doc);
         }
         ValidationMode::Fast => {
             let mut visitor = VisitorNil
                 .with(rules::NoFragmentCycles::default())
-                .with(rules::UploadFile)
+                .with(rule!(options, UploadFile))
                 .with(visitors::CacheControlCalculate {
                     cache_control: &mut cache_control,
                 })
-                .with(visitors::ComplexityCalculate::new(&mut complexity))
                 .with(visitors::DepthCalculate::new(&mut depth));
             visit(&mut visitor, &mut ctx, doc);
         }
@@ -98,7 +282,89 @@ pub fn check_rules(
 
     Ok(ValidationResult {
         cache_control,
-        complexity,
+        complexity: 0,
         depth,
+        aliases,
+        root_fields,
+        directives,
+        selections,
+        deprecations: Vec::new(),
     })
 }
+
+/// Checks the rules which depend on the values of the variables and the uploaded files of a
+/// request, and the custom rules, which can read the variables. It also computes the complexity
+/// of the query, because the complexity functions of the fields can read their arguments, and the
+/// uses of deprecated elements by the operation named `operation_name`, because input fields and
+/// enum values can be passed in variables.
+///
+/// If `visibility_ctx` is set, the types, fields and arguments which are hidden from the request
+/// are reported as unknown, see `SchemaBuilder::validate_visibility`.
+///
+/// Must be called with the result of [`check_rules`] for the same document.
+#[allow(clippy::too_many_arguments)]
+pub fn check_variables<'a>(
+    registry: &'a Registry,
+    doc: &'a ExecutableDocument,
+    operation_name: Option<&str>,
+    variables: &'a Variables,
+    uploads: &'a [UploadValue],
+    visibility_ctx: Option<&'a Context<'a>>,
+    options: &SchemaOptions,
+    result: &mut ValidationResult,
+) -> Result<(), Vec<ServerError>> {
+    let mut ctx = VisitorContext::new(registry, doc, Some(variables));
+    ctx.uploads = uploads;
+    ctx.visibility_ctx = visibility_ctx;
+    let mut complexity = 0;
+    let mut deprecations = Vec::new();
+
+    match options.validation_mode {
+        ValidationMode::Strict => {
+            let mut visitor = VisitorNil
+                .with(rule!(options, ArgumentsOfCorrectType::default))
+                .with(rules::UploadValidators::default())
+                .with(visitors::ComplexityCalculate::new(&mut complexity))
+                .with(visitors::DeprecationsCalculate::new(
+                    &mut deprecations,
+                    operation_name,
+                ));
+            visit(&mut visitor, &mut ctx, doc);
+        }
+        ValidationMode::Fast => {
+            let mut visitor = VisitorNil
+                .with(rules::UploadValidators::default())
+                .with(visitors::ComplexityCalculate::new(&mut complexity))
+                .with(visitors::DeprecationsCalculate::new(
+                    &mut deprecations,
+                    operation_name,
+                ));
+            visit(&mut visitor, &mut ctx, doc);
+        }
+    }
+
+    // The rules reporting unknown elements are run again, hiding the elements the request cannot
+    // see.
+    if visibility_ctx.is_some() {
+        let mut visitor = VisitorNil
+            .with(rules::FieldsOnCorrectType)
+            .with(rules::KnownArgumentNames::default())
+            .with(rules::KnownTypeNames);
+        visit(&mut visitor, &mut ctx, doc);
+    }
+
+    // Custom rules may inline fragments, so they only see documents the built-in rules accept.
+    if ctx.errors.is_empty() {
+        for rule in &options.validation_rules {
+            visit(&mut *rule.create(), &mut ctx, doc);
+        }
+    }
+
+    if !ctx.errors.is_empty() {
+        return Err(ctx.errors.into_iter().map(Into::into).collect());
+    }
+
+    result.complexity = complexity;
+    result.deprecations = deprecations;
+    Ok(())
+}
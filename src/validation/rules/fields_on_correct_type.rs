@@ -20,6 +20,7 @@ impl<'a> Visitor<'a> for FieldsOnCorrectType {
             if parent_type
                 .fields()
                 .and_then(|fields| fields.get(field.node.name.node.as_str()))
+                .filter(|field| ctx.is_visible(&field.visible))
                 .is_none()
                 && !field
                     .node
@@ -38,9 +39,10 @@ impl<'a> Visitor<'a> for FieldsOnCorrectType {
                             parent_type
                                 .fields()
                                 .iter()
-                                .map(|fields| fields.keys())
+                                .map(|fields| fields.values())
                                 .flatten()
-                                .map(String::as_str),
+                                .filter(|field| ctx.is_visible(&field.visible))
+                                .map(|field| field.name.as_str()),
                             &field.node.name.node,
                         )
                         .unwrap_or_default()
@@ -17,17 +17,19 @@ enum ArgsType<'a> {
 
 #[derive(Default)]
 pub struct KnownArgumentNames<'a> {
-    current_args: Option<(&'a IndexMap<&'static str, MetaInputValue>, ArgsType<'a>)>,
+    current_args: Option<(&'a IndexMap<String, MetaInputValue>, ArgsType<'a>)>,
 }
 
 impl<'a> KnownArgumentNames<'a> {
-    fn get_suggestion(&self, name: &str) -> String {
+    fn get_suggestion(&self, ctx: &VisitorContext<'_>, name: &str) -> String {
         make_suggestion(
             " Did you mean",
             self.current_args
                 .iter()
-                .map(|(args, _)| args.iter().map(|arg| *arg.0))
-                .flatten(),
+                .map(|(args, _)| args.values())
+                .flatten()
+                .filter(|arg| ctx.is_visible(&arg.visible))
+                .map(|arg| arg.name.as_str()),
             name,
         )
         .unwrap_or_default()
@@ -62,7 +64,11 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
         _value: &'a Positioned<Value>,
     ) {
         if let Some((args, arg_type)) = &self.current_args {
-            if !args.contains_key(name.node.as_str()) {
+            if args
+                .get(name.node.as_str())
+                .filter(|arg| ctx.is_visible(&arg.visible))
+                .is_none()
+            {
                 match arg_type {
                     ArgsType::Field {
                         field_name,
@@ -75,7 +81,7 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
                                 name,
                                 field_name,
                                 type_name,
-                                self.get_suggestion(name.node.as_str())
+                                self.get_suggestion(ctx, name.node.as_str())
                             ),
                         );
                     }
@@ -86,7 +92,7 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
                                 "Unknown argument \"{}\" on directive \"{}\".{}",
                                 name,
                                 directive_name,
-                                self.get_suggestion(name.node.as_str())
+                                self.get_suggestion(ctx, name.node.as_str())
                             ),
                         );
                     }
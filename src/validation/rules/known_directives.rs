@@ -1,16 +1,39 @@
 use crate::model::__DirectiveLocation;
 use crate::parser::types::{
     Directive, Field, FragmentDefinition, FragmentSpread, InlineFragment, OperationDefinition,
-    OperationType,
+    OperationType, VariableDefinition,
 };
 use crate::validation::visitor::{Visitor, VisitorContext};
-use crate::{Name, Positioned};
+use crate::{Name, Pos, Positioned};
 
 #[derive(Default)]
 pub struct KnownDirectives {
     location_stack: Vec<__DirectiveLocation>,
 }
 
+fn check_directive(
+    ctx: &mut VisitorContext<'_>,
+    name: &Name,
+    pos: Pos,
+    location: Option<&__DirectiveLocation>,
+) {
+    if let Some(schema_directive) = ctx.registry.directives.get(name.as_str()) {
+        if let Some(location) = location {
+            if !schema_directive.locations.contains(location) {
+                ctx.report_error(
+                    vec![pos],
+                    format!(
+                        "Directive \"{}\" may not be used on \"{:?}\"",
+                        name, location
+                    ),
+                )
+            }
+        }
+    } else {
+        ctx.report_error(vec![pos], format!("Unknown directive \"{}\"", name));
+    }
+}
+
 impl<'a> Visitor<'a> for KnownDirectives {
     fn enter_operation_definition(
         &mut self,
@@ -59,26 +82,25 @@ impl<'a> Visitor<'a> for KnownDirectives {
         ctx: &mut VisitorContext<'a>,
         directive: &'a Positioned<Directive>,
     ) {
-        if let Some(schema_directive) = ctx
-            .registry
-            .directives
-            .get(directive.node.name.node.as_str())
-        {
-            if let Some(current_location) = self.location_stack.last() {
-                if !schema_directive.locations.contains(current_location) {
-                    ctx.report_error(
-                        vec![directive.pos],
-                        format!(
-                            "Directive \"{}\" may not be used on \"{:?}\"",
-                            directive.node.name.node, current_location
-                        ),
-                    )
-                }
-            }
-        } else {
-            ctx.report_error(
-                vec![directive.pos],
-                format!("Unknown directive \"{}\"", directive.node.name.node),
+        check_directive(
+            ctx,
+            &directive.node.name.node,
+            directive.pos,
+            self.location_stack.last(),
+        );
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        variable_definition: &'a Positioned<VariableDefinition>,
+    ) {
+        for directive in &variable_definition.node.directives {
+            check_directive(
+                ctx,
+                &directive.node.name.node,
+                directive.pos,
+                Some(&__DirectiveLocation::VARIABLE_DEFINITION),
             );
         }
     }
@@ -201,6 +223,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn with_unknown_directive_on_variable_definition() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query Foo($var: Boolean @unknown) {
+            dog @include(if: $var) {
+              name
+            }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn with_misplaced_directive_on_variable_definition() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query Foo($var: Boolean @skip(if: true)) {
+            dog @include(if: $var) {
+              name
+            }
+          }
+        "#,
+        );
+    }
+
     #[test]
     fn with_well_placed_directives() {
         expect_passes_rule!(
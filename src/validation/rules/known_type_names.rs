@@ -46,7 +46,13 @@ impl<'a> Visitor<'a> for KnownTypeNames {
 }
 
 fn validate_type(ctx: &mut VisitorContext<'_>, type_name: &str, pos: Pos) {
-    if ctx.registry.types.get(type_name).is_none() {
+    if ctx
+        .registry
+        .types
+        .get(type_name)
+        .filter(|ty| ctx.is_type_visible(ty))
+        .is_none()
+    {
         ctx.report_error(vec![pos], format!(r#"Unknown type "{}""#, type_name));
     }
 }
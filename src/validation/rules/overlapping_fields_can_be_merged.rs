@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::parser::types::{Field, Selection, SelectionSet};
 use crate::validation::visitor::{Visitor, VisitorContext};
@@ -15,6 +15,7 @@ impl<'a> Visitor<'a> for OverlappingFieldsCanBeMerged {
     ) {
         let mut find_conflicts = FindConflicts {
             outputs: Default::default(),
+            visited: Default::default(),
             ctx,
         };
         find_conflicts.find(selection_set);
@@ -23,6 +24,7 @@ impl<'a> Visitor<'a> for OverlappingFieldsCanBeMerged {
 
 struct FindConflicts<'a, 'ctx> {
     outputs: HashMap<&'a str, &'a Positioned<Field>>,
+    visited: HashSet<&'a str>,
     ctx: &'a mut VisitorContext<'ctx>,
 }
 
@@ -43,9 +45,13 @@ impl<'a, 'ctx> FindConflicts<'a, 'ctx> {
                     self.find(&inline_fragment.node.selection_set);
                 }
                 Selection::FragmentSpread(fragment_spread) => {
-                    if let Some(fragment) =
-                        self.ctx.fragment(&fragment_spread.node.fragment_name.node)
-                    {
+                    let name = fragment_spread.node.fragment_name.node.as_str();
+                    // The fields of a fragment are the same at every spread of it, and following
+                    // a spread only once also stops at fragment cycles.
+                    if !self.visited.insert(name) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.ctx.fragment(name) {
                         self.find(&fragment.node.selection_set);
                     }
                 }
@@ -82,3 +88,72 @@ impl<'a, 'ctx> FindConflicts<'a, 'ctx> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_query;
+    use crate::validation::test_harness::validate;
+
+    pub fn factory() -> OverlappingFieldsCanBeMerged {
+        OverlappingFieldsCanBeMerged
+    }
+
+    #[test]
+    fn same_fields_in_fragment() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          fragment fragA on Dog { name nickname }
+          { dog { name ...fragA ...fragA } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn conflicting_fields_in_fragment() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          fragment fragA on Dog { name: nickname }
+          { dog { name ...fragA } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn conflicting_fields_in_nested_fragment() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          fragment fragA on Dog { ...fragB }
+          fragment fragB on Dog { name: nickname }
+          { dog { name ...fragA } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn conflict_in_fragment_spread_twice_is_reported_once() {
+        let doc = parse_query(
+            r#"
+          fragment fragA on Dog { name: nickname }
+          { dog { name ...fragA ...fragA } }
+        "#,
+        )
+        .unwrap();
+        assert_eq!(validate(&doc, factory).unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn fragment_cycle_terminates() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          fragment fragA on Dog { name ...fragB }
+          fragment fragB on Dog { name ...fragA }
+          { dog { ...fragA } }
+        "#,
+        );
+    }
+}
@@ -1,9 +1,17 @@
 use std::collections::HashMap;
 
 use crate::parser::types::{ExecutableDocument, FragmentSpread, InlineFragment, TypeCondition};
+use crate::registry::MetaType;
 use crate::validation::visitor::{Visitor, VisitorContext};
 use crate::Positioned;
 
+/// An interface overlaps with the interfaces it implements even if no object type implements it.
+fn type_overlap(ctx: &VisitorContext<'_>, a: &MetaType, b: &MetaType) -> bool {
+    a.type_overlap(b)
+        || ctx.registry.implements_interface(a.name(), b.name())
+        || ctx.registry.implements_interface(b.name(), a.name())
+}
+
 #[derive(Default)]
 pub struct PossibleFragmentSpreads<'a> {
     fragment_types: HashMap<&'a str, &'a str>,
@@ -28,7 +36,7 @@ impl<'a> Visitor<'a> for PossibleFragmentSpreads<'a> {
         {
             if let Some(current_type) = ctx.current_type() {
                 if let Some(on_type) = ctx.registry.types.get(*fragment_type) {
-                    if !current_type.type_overlap(on_type) {
+                    if !type_overlap(ctx, current_type, on_type) {
                         ctx.report_error(
                             vec![fragment_spread.pos],
                             format!(
@@ -55,7 +63,7 @@ impl<'a> Visitor<'a> for PossibleFragmentSpreads<'a> {
                 .map(|c| &c.node)
             {
                 if let Some(on_type) = ctx.registry.types.get(fragment_type.node.as_str()) {
-                    if !parent_type.type_overlap(&on_type) {
+                    if !type_overlap(ctx, parent_type, on_type) {
                         ctx.report_error(
                             vec![inline_fragment.pos],
                             format!(
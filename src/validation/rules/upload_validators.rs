@@ -0,0 +1,104 @@
+use indexmap::map::IndexMap;
+
+use crate::parser::types::{Directive, Field};
+use crate::registry::{MetaInputValue, MetaTypeName};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::validators::InputValueValidator;
+use crate::{Name, Positioned, UploadValue};
+use async_graphql_value::{ConstValue, Value};
+
+/// Checks the uploaded files passed to the arguments of type `Upload` with their validators.
+///
+/// Unlike the other rules it cannot be disabled, and it runs in both validation modes.
+#[derive(Default)]
+pub struct UploadValidators<'a> {
+    current_args: Option<&'a IndexMap<String, MetaInputValue>>,
+}
+
+impl<'a> Visitor<'a> for UploadValidators<'a> {
+    fn enter_directive(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        directive: &'a Positioned<Directive>,
+    ) {
+        self.current_args = ctx
+            .registry
+            .directives
+            .get(directive.node.name.node.as_str())
+            .map(|d| &d.args);
+    }
+
+    fn exit_directive(
+        &mut self,
+        _ctx: &mut VisitorContext<'a>,
+        _directive: &'a Positioned<Directive>,
+    ) {
+        self.current_args = None;
+    }
+
+    fn enter_argument(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Positioned<Name>,
+        value: &'a Positioned<Value>,
+    ) {
+        let arg = match self
+            .current_args
+            .and_then(|args| args.get(name.node.as_str()))
+        {
+            Some(arg) if MetaTypeName::concrete_typename(&arg.ty) == "Upload" => arg,
+            _ => return,
+        };
+        let validator = match &arg.validator {
+            Some(validator) => validator,
+            None => return,
+        };
+        let value = value.node.clone().into_const_with(|var_name| {
+            ctx.variables
+                .and_then(|variables| variables.0.get(&var_name))
+                .cloned()
+                .ok_or(())
+        });
+
+        if let Ok(value) = value {
+            if let Err(reason) = check_uploads(ctx.uploads, &**validator, &value) {
+                ctx.report_error(
+                    vec![name.pos],
+                    format!("Invalid value for argument \"{}\", {}", arg.name, reason),
+                );
+            }
+        }
+    }
+
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        self.current_args = ctx
+            .parent_type()
+            .and_then(|p| p.field_by_name(&field.node.name.node))
+            .map(|f| &f.args);
+    }
+
+    fn exit_field(&mut self, _ctx: &mut VisitorContext<'a>, _field: &'a Positioned<Field>) {
+        self.current_args = None;
+    }
+}
+
+fn check_uploads(
+    uploads: &[UploadValue],
+    validator: &dyn InputValueValidator,
+    value: &ConstValue,
+) -> Result<(), String> {
+    match value {
+        ConstValue::String(s) => match s
+            .strip_prefix("#__graphql_file__:")
+            .and_then(|idx| idx.parse::<usize>().ok())
+            .and_then(|idx| uploads.get(idx))
+        {
+            Some(upload) => validator.is_valid_upload(upload),
+            None => Ok(()),
+        },
+        ConstValue::List(values) => values
+            .iter()
+            .try_for_each(|value| check_uploads(uploads, validator, value)),
+        _ => Ok(()),
+    }
+}
@@ -1,7 +1,8 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::context::QueryPathNode;
-use crate::{registry, QueryPathSegment};
+use crate::parser::types::ExecutableDocument;
+use crate::{registry, Name, QueryPathSegment};
 use async_graphql_value::{ConstValue, Value};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -10,6 +11,86 @@ pub enum Scope<'a> {
     Fragment(&'a str),
 }
 
+/// Counts something per operation and fragment definition of a document, the count of a fragment
+/// is added at every spread of it without visiting its selection set again.
+#[derive(Default)]
+pub struct ScopedCounter<'a> {
+    current_scope: Option<Scope<'a>>,
+    counts: HashMap<Scope<'a>, usize>,
+    spreads: HashMap<Scope<'a>, Vec<&'a str>>,
+}
+
+impl<'a> ScopedCounter<'a> {
+    pub fn enter_scope(&mut self, scope: Scope<'a>) {
+        self.current_scope = Some(scope);
+    }
+
+    pub fn add(&mut self, count: usize) {
+        if let Some(scope) = &self.current_scope {
+            *self.counts.entry(scope.clone()).or_default() += count;
+        }
+    }
+
+    pub fn add_spread(&mut self, fragment_name: &'a str) {
+        if let Some(scope) = &self.current_scope {
+            self.spreads
+                .entry(scope.clone())
+                .or_default()
+                .push(fragment_name);
+        }
+    }
+
+    /// Returns the sum of the counts of the operations of the document.
+    ///
+    /// Counting stops as soon as the sum exceeds `limit`, so the result is only exact if it
+    /// doesn't.
+    pub fn total(&self, doc: &'a ExecutableDocument, limit: Option<usize>) -> usize {
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut fragments = HashMap::new();
+        let mut total = 0usize;
+        for (name, _) in doc.operations.iter() {
+            if total > limit {
+                break;
+            }
+            let count = self.count(
+                &Scope::Operation(name.map(Name::as_str)),
+                limit,
+                &mut fragments,
+            );
+            total = total.saturating_add(count);
+        }
+        total
+    }
+
+    fn count(
+        &self,
+        scope: &Scope<'a>,
+        limit: usize,
+        fragments: &mut HashMap<&'a str, Option<usize>>,
+    ) -> usize {
+        let mut count = self.counts.get(scope).copied().unwrap_or_default();
+        for fragment_name in self.spreads.get(scope).into_iter().flatten() {
+            if count > limit {
+                break;
+            }
+            let fragment_count = match fragments.get(fragment_name) {
+                Some(Some(fragment_count)) => *fragment_count,
+                // A fragment spread inside of itself, the cycle is reported by `NoFragmentCycles`.
+                Some(None) => 0,
+                None => {
+                    fragments.insert(fragment_name, None);
+                    let fragment_count =
+                        self.count(&Scope::Fragment(fragment_name), limit, fragments);
+                    fragments.insert(fragment_name, Some(fragment_count));
+                    fragment_count
+                }
+            };
+            count = count.saturating_add(fragment_count);
+        }
+        count
+    }
+}
+
 fn valid_error(path_node: &QueryPathNode, msg: String) -> String {
     format!("\"{}\", {}", path_node, msg)
 }
@@ -119,21 +200,43 @@ pub fn is_valid_input_value(
                 registry::MetaType::InputObject {
                     input_fields,
                     name: object_name,
+                    oneof,
                     ..
                 } => match value {
                     ConstValue::Object(values) => {
+                        if *oneof {
+                            if values.len() != 1 {
+                                return Some(valid_error(
+                                    &path_node,
+                                    format!(
+                                        "oneof input object \"{}\" must have exactly one field",
+                                        object_name
+                                    ),
+                                ));
+                            }
+                            if let Some((name, ConstValue::Null)) = values.iter().next() {
+                                return Some(valid_error(
+                                    &path_node,
+                                    format!(
+                                        "field \"{}\" of oneof input object \"{}\" must not be null",
+                                        name, object_name
+                                    ),
+                                ));
+                            }
+                        }
+
                         let mut input_names =
                             values.keys().map(AsRef::as_ref).collect::<HashSet<_>>();
 
                         for field in input_fields.values() {
-                            input_names.remove(field.name);
-                            if let Some(value) = values.get(field.name) {
+                            input_names.remove(field.name.as_str());
+                            if let Some(value) = values.get(field.name.as_str()) {
                                 if let Some(validator) = &field.validator {
                                     if let Err(reason) = validator.is_valid(value) {
                                         return Some(valid_error(
                                             &QueryPathNode {
                                                 parent: Some(&path_node),
-                                                segment: QueryPathSegment::Name(field.name),
+                                                segment: QueryPathSegment::Name(&field.name),
                                             },
                                             reason,
                                         ));
@@ -146,7 +249,7 @@ pub fn is_valid_input_value(
                                     value,
                                     QueryPathNode {
                                         parent: Some(&path_node),
-                                        segment: QueryPathSegment::Name(field.name),
+                                        segment: QueryPathSegment::Name(&field.name),
                                     },
                                 ) {
                                     return Some(reason);
@@ -7,17 +7,22 @@ use crate::parser::types::{
     Directive, ExecutableDocument, Field, FragmentDefinition, FragmentSpread, InlineFragment,
     OperationDefinition, OperationType, Selection, SelectionSet, TypeCondition, VariableDefinition,
 };
-use crate::registry::{self, MetaType, MetaTypeName};
-use crate::{InputType, Name, Pos, Positioned, ServerError, ServerResult, Variables};
+use crate::registry::{self, MetaType, MetaTypeName, MetaVisibleFn};
+use crate::{
+    Context, InputType, Name, Pos, Positioned, ServerError, ServerResult, UploadValue, Variables,
+};
 
-#[doc(hidden)]
+/// Context for the validation visitors.
 pub struct VisitorContext<'a> {
     pub(crate) registry: &'a registry::Registry,
     pub(crate) variables: Option<&'a Variables>,
+    pub(crate) uploads: &'a [UploadValue],
     pub(crate) errors: Vec<RuleError>,
+    pub(crate) visibility_ctx: Option<&'a Context<'a>>,
     type_stack: Vec<Option<&'a registry::MetaType>>,
     input_type: Vec<Option<MetaTypeName<'a>>>,
     fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    inlined_fragments: Vec<&'a str>,
 }
 
 impl<'a> VisitorContext<'a> {
@@ -29,14 +34,18 @@ impl<'a> VisitorContext<'a> {
         Self {
             registry,
             variables,
+            uploads: &[],
             errors: Default::default(),
+            visibility_ctx: None,
             type_stack: Default::default(),
             input_type: Default::default(),
             fragments: &doc.fragments,
+            inlined_fragments: Vec::new(),
         }
     }
 
-    pub(crate) fn report_error<T: Into<String>>(&mut self, locations: Vec<Pos>, msg: T) {
+    /// Report a validation error at the specified locations.
+    pub fn report_error<T: Into<String>>(&mut self, locations: Vec<Pos>, msg: T) {
         self.errors.push(RuleError {
             locations,
             message: msg.into(),
@@ -67,7 +76,37 @@ impl<'a> VisitorContext<'a> {
         self.input_type.pop();
     }
 
-    pub(crate) fn parent_type(&self) -> Option<&'a registry::MetaType> {
+    /// Returns the registry of the schema being validated against.
+    pub fn registry(&self) -> &'a registry::Registry {
+        self.registry
+    }
+
+    /// Returns the variables of the request, if they are known.
+    pub fn variables(&self) -> Option<&'a Variables> {
+        self.variables
+    }
+
+    /// Returns `false` if the element of the schema is hidden from the request being validated.
+    ///
+    /// Everything is visible unless the request is validated against the visibility of the schema,
+    /// see `SchemaBuilder::validate_visibility`.
+    pub(crate) fn is_visible(&self, visible: &Option<MetaVisibleFn>) -> bool {
+        match (self.visibility_ctx, visible) {
+            (Some(ctx), Some(f)) => f(ctx),
+            _ => true,
+        }
+    }
+
+    /// Returns `false` if the type is hidden from the request being validated.
+    pub(crate) fn is_type_visible(&self, ty: &MetaType) -> bool {
+        match self.visibility_ctx {
+            Some(ctx) => ty.is_visible(ctx),
+            None => true,
+        }
+    }
+
+    /// Returns the type that contains the current type, if it is known.
+    pub fn parent_type(&self) -> Option<&'a registry::MetaType> {
         if self.type_stack.len() >= 2 {
             self.type_stack
                 .get(self.type_stack.len() - 2)
@@ -78,15 +117,18 @@ impl<'a> VisitorContext<'a> {
         }
     }
 
-    pub(crate) fn current_type(&self) -> Option<&'a registry::MetaType> {
+    /// Returns the type being visited, if it is known.
+    pub fn current_type(&self) -> Option<&'a registry::MetaType> {
         self.type_stack.last().copied().flatten()
     }
 
-    pub(crate) fn is_known_fragment(&self, name: &str) -> bool {
+    /// Returns `true` if the document defines a fragment with the specified name.
+    pub fn is_known_fragment(&self, name: &str) -> bool {
         self.fragments.contains_key(name)
     }
 
-    pub(crate) fn fragment(&self, name: &str) -> Option<&'a Positioned<FragmentDefinition>> {
+    /// Returns the fragment definition with the specified name.
+    pub fn fragment(&self, name: &str) -> Option<&'a Positioned<FragmentDefinition>> {
         self.fragments.get(name)
     }
 
@@ -140,20 +182,33 @@ impl<'a> VisitorContext<'a> {
     }
 }
 
+/// How fragments are visited.
 #[derive(Copy, Clone, Eq, PartialEq)]
-pub(crate) enum VisitMode {
+pub enum VisitMode {
+    /// Fragment definitions are visited once, fragment spreads are not followed.
     Normal,
+
+    /// Fragment definitions are skipped, and the selection set of a fragment is visited at every
+    /// spread of it, except for the spreads nested inside of the fragment itself.
     Inline,
 }
 
-pub(crate) trait Visitor<'a> {
+/// A validation visitor, it is notified of every node while the document is walked through.
+///
+/// Custom validation rules implement this trait and report errors with
+/// [`VisitorContext::report_error`](struct.VisitorContext.html#method.report_error).
+pub trait Visitor<'a> {
+    /// How fragments are visited, the default is `VisitMode::Normal`.
     fn mode(&self) -> VisitMode {
         VisitMode::Normal
     }
 
+    /// Called when entering a document.
     fn enter_document(&mut self, _ctx: &mut VisitorContext<'a>, _doc: &'a ExecutableDocument) {}
+    /// Called when leaving a document.
     fn exit_document(&mut self, _ctx: &mut VisitorContext<'a>, _doc: &'a ExecutableDocument) {}
 
+    /// Called when entering an operation definition.
     fn enter_operation_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -161,6 +216,7 @@ pub(crate) trait Visitor<'a> {
         _operation_definition: &'a Positioned<OperationDefinition>,
     ) {
     }
+    /// Called when leaving an operation definition.
     fn exit_operation_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -169,6 +225,7 @@ pub(crate) trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a fragment definition.
     fn enter_fragment_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -176,6 +233,7 @@ pub(crate) trait Visitor<'a> {
         _fragment_definition: &'a Positioned<FragmentDefinition>,
     ) {
     }
+    /// Called when leaving a fragment definition.
     fn exit_fragment_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -184,12 +242,14 @@ pub(crate) trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a variable definition.
     fn enter_variable_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _variable_definition: &'a Positioned<VariableDefinition>,
     ) {
     }
+    /// Called when leaving a variable definition.
     fn exit_variable_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -197,12 +257,14 @@ pub(crate) trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a directive.
     fn enter_directive(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _directive: &'a Positioned<Directive>,
     ) {
     }
+    /// Called when leaving a directive.
     fn exit_directive(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -210,6 +272,7 @@ pub(crate) trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering an argument.
     fn enter_argument(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -217,6 +280,7 @@ pub(crate) trait Visitor<'a> {
         _value: &'a Positioned<Value>,
     ) {
     }
+    /// Called when leaving an argument.
     fn exit_argument(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -225,12 +289,14 @@ pub(crate) trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a selection set.
     fn enter_selection_set(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _selection_set: &'a Positioned<SelectionSet>,
     ) {
     }
+    /// Called when leaving a selection set.
     fn exit_selection_set(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -238,12 +304,14 @@ pub(crate) trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a selection.
     fn enter_selection(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _selection: &'a Positioned<Selection>,
     ) {
     }
+    /// Called when leaving a selection.
     fn exit_selection(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -251,15 +319,19 @@ pub(crate) trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a field.
     fn enter_field(&mut self, _ctx: &mut VisitorContext<'a>, _field: &'a Positioned<Field>) {}
+    /// Called when leaving a field.
     fn exit_field(&mut self, _ctx: &mut VisitorContext<'a>, _field: &'a Positioned<Field>) {}
 
+    /// Called when entering a fragment spread.
     fn enter_fragment_spread(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _fragment_spread: &'a Positioned<FragmentSpread>,
     ) {
     }
+    /// Called when leaving a fragment spread.
     fn exit_fragment_spread(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -267,12 +339,14 @@ pub(crate) trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering an inline fragment.
     fn enter_inline_fragment(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _inline_fragment: &'a Positioned<InlineFragment>,
     ) {
     }
+    /// Called when leaving an inline fragment.
     fn exit_inline_fragment(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -280,6 +354,7 @@ pub(crate) trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering an input value.
     fn enter_input_value(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -288,6 +363,7 @@ pub(crate) trait Visitor<'a> {
         _value: &'a Value,
     ) {
     }
+    /// Called when leaving an input value.
     fn exit_input_value(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -518,7 +594,251 @@ where
     }
 }
 
-pub(crate) fn visit<'a, V: Visitor<'a>>(
+/// A disabled rule is represented by `None`.
+impl<'a, V> Visitor<'a> for Option<V>
+where
+    V: Visitor<'a> + 'a,
+{
+    fn mode(&self) -> VisitMode {
+        self.as_ref().map_or(VisitMode::Normal, Visitor::mode)
+    }
+
+    fn enter_document(&mut self, ctx: &mut VisitorContext<'a>, doc: &'a ExecutableDocument) {
+        if let Some(visitor) = self {
+            visitor.enter_document(ctx, doc);
+        }
+    }
+
+    fn exit_document(&mut self, ctx: &mut VisitorContext<'a>, doc: &'a ExecutableDocument) {
+        if let Some(visitor) = self {
+            visitor.exit_document(ctx, doc);
+        }
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: Option<&'a Name>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.enter_operation_definition(ctx, name, operation_definition);
+        }
+    }
+
+    fn exit_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: Option<&'a Name>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.exit_operation_definition(ctx, name, operation_definition);
+        }
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Name,
+        fragment_definition: &'a Positioned<FragmentDefinition>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.enter_fragment_definition(ctx, name, fragment_definition);
+        }
+    }
+
+    fn exit_fragment_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Name,
+        fragment_definition: &'a Positioned<FragmentDefinition>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.exit_fragment_definition(ctx, name, fragment_definition);
+        }
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        variable_definition: &'a Positioned<VariableDefinition>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.enter_variable_definition(ctx, variable_definition);
+        }
+    }
+
+    fn exit_variable_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        variable_definition: &'a Positioned<VariableDefinition>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.exit_variable_definition(ctx, variable_definition);
+        }
+    }
+
+    fn enter_directive(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        directive: &'a Positioned<Directive>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.enter_directive(ctx, directive);
+        }
+    }
+
+    fn exit_directive(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        directive: &'a Positioned<Directive>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.exit_directive(ctx, directive);
+        }
+    }
+
+    fn enter_argument(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Positioned<Name>,
+        value: &'a Positioned<Value>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.enter_argument(ctx, name, value);
+        }
+    }
+
+    fn exit_argument(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Positioned<Name>,
+        value: &'a Positioned<Value>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.exit_argument(ctx, name, value);
+        }
+    }
+
+    fn enter_selection_set(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection_set: &'a Positioned<SelectionSet>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.enter_selection_set(ctx, selection_set);
+        }
+    }
+
+    fn exit_selection_set(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection_set: &'a Positioned<SelectionSet>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.exit_selection_set(ctx, selection_set);
+        }
+    }
+
+    fn enter_selection(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection: &'a Positioned<Selection>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.enter_selection(ctx, selection);
+        }
+    }
+
+    fn exit_selection(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection: &'a Positioned<Selection>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.exit_selection(ctx, selection);
+        }
+    }
+
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        if let Some(visitor) = self {
+            visitor.enter_field(ctx, field);
+        }
+    }
+
+    fn exit_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        if let Some(visitor) = self {
+            visitor.exit_field(ctx, field);
+        }
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_spread: &'a Positioned<FragmentSpread>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.enter_fragment_spread(ctx, fragment_spread);
+        }
+    }
+
+    fn exit_fragment_spread(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_spread: &'a Positioned<FragmentSpread>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.exit_fragment_spread(ctx, fragment_spread);
+        }
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        inline_fragment: &'a Positioned<InlineFragment>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.enter_inline_fragment(ctx, inline_fragment);
+        }
+    }
+
+    fn exit_inline_fragment(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        inline_fragment: &'a Positioned<InlineFragment>,
+    ) {
+        if let Some(visitor) = self {
+            visitor.exit_inline_fragment(ctx, inline_fragment);
+        }
+    }
+
+    fn enter_input_value(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        pos: Pos,
+        expected_type: &Option<MetaTypeName<'a>>,
+        value: &'a Value,
+    ) {
+        if let Some(visitor) = self {
+            visitor.enter_input_value(ctx, pos, expected_type, value);
+        }
+    }
+
+    fn exit_input_value(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        pos: Pos,
+        expected_type: &Option<MetaTypeName<'a>>,
+        value: &Value,
+    ) {
+        if let Some(visitor) = self {
+            visitor.exit_input_value(ctx, pos, expected_type, value);
+        }
+    }
+}
+
+pub(crate) fn visit<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     doc: &'a ExecutableDocument,
@@ -541,7 +861,7 @@ pub(crate) fn visit<'a, V: Visitor<'a>>(
     v.exit_document(ctx, doc);
 }
 
-fn visit_operation_definition<'a, V: Visitor<'a>>(
+fn visit_operation_definition<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     name: Option<&'a Name>,
@@ -569,7 +889,7 @@ fn visit_operation_definition<'a, V: Visitor<'a>>(
     v.exit_operation_definition(ctx, name, operation);
 }
 
-fn visit_selection_set<'a, V: Visitor<'a>>(
+fn visit_selection_set<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     selection_set: &'a Positioned<SelectionSet>,
@@ -583,7 +903,7 @@ fn visit_selection_set<'a, V: Visitor<'a>>(
     }
 }
 
-fn visit_selection<'a, V: Visitor<'a>>(
+fn visit_selection<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     selection: &'a Positioned<Selection>,
@@ -623,7 +943,7 @@ fn visit_selection<'a, V: Visitor<'a>>(
     v.exit_selection(ctx, selection);
 }
 
-fn visit_field<'a, V: Visitor<'a>>(
+fn visit_field<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     field: &'a Positioned<Field>,
@@ -648,7 +968,7 @@ fn visit_field<'a, V: Visitor<'a>>(
     v.exit_field(ctx, field);
 }
 
-fn visit_input_value<'a, V: Visitor<'a>>(
+fn visit_input_value<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     pos: Pos,
@@ -706,7 +1026,7 @@ fn visit_input_value<'a, V: Visitor<'a>>(
     v.exit_input_value(ctx, pos, &expected_ty, value);
 }
 
-fn visit_variable_definitions<'a, V: Visitor<'a>>(
+fn visit_variable_definitions<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     variable_definitions: &'a [Positioned<VariableDefinition>],
@@ -717,7 +1037,7 @@ fn visit_variable_definitions<'a, V: Visitor<'a>>(
     }
 }
 
-fn visit_directives<'a, V: Visitor<'a>>(
+fn visit_directives<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     directives: &'a [Positioned<Directive>],
@@ -742,7 +1062,7 @@ fn visit_directives<'a, V: Visitor<'a>>(
     }
 }
 
-fn visit_fragment_definition<'a, V: Visitor<'a>>(
+fn visit_fragment_definition<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     name: &'a Name,
@@ -756,7 +1076,7 @@ fn visit_fragment_definition<'a, V: Visitor<'a>>(
     }
 }
 
-fn visit_fragment_spread<'a, V: Visitor<'a>>(
+fn visit_fragment_spread<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     fragment_spread: &'a Positioned<FragmentSpread>,
@@ -764,17 +1084,21 @@ fn visit_fragment_spread<'a, V: Visitor<'a>>(
     v.enter_fragment_spread(ctx, fragment_spread);
     visit_directives(v, ctx, &fragment_spread.node.directives);
     if v.mode() == VisitMode::Inline {
-        if let Some(fragment) = ctx
-            .fragments
-            .get(fragment_spread.node.fragment_name.node.as_str())
-        {
-            visit_selection_set(v, ctx, &fragment.node.selection_set);
+        let name = fragment_spread.node.fragment_name.node.as_str();
+        // A fragment spread inside of itself is not followed again, the cycle is reported by
+        // `NoFragmentCycles`.
+        if let Some(fragment) = ctx.fragments.get(name) {
+            if !ctx.inlined_fragments.contains(&name) {
+                ctx.inlined_fragments.push(name);
+                visit_selection_set(v, ctx, &fragment.node.selection_set);
+                ctx.inlined_fragments.pop();
+            }
         }
     }
     v.exit_fragment_spread(ctx, fragment_spread);
 }
 
-fn visit_inline_fragment<'a, V: Visitor<'a>>(
+fn visit_inline_fragment<'a, V: Visitor<'a> + ?Sized>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     inline_fragment: &'a Positioned<InlineFragment>,
@@ -0,0 +1,136 @@
+use crate::parser::types::{
+    ExecutableDocument, Field, FragmentDefinition, FragmentSpread, OperationDefinition,
+};
+use crate::validation::utils::{Scope, ScopedCounter};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::{Name, Positioned};
+
+/// Calculates the number of aliases in the query, the aliases of a fragment are counted at every
+/// spread of it.
+pub struct AliasesCalculate<'ctx, 'a> {
+    aliases: &'a mut usize,
+    limit: Option<usize>,
+    counter: ScopedCounter<'ctx>,
+}
+
+impl<'ctx, 'a> AliasesCalculate<'ctx, 'a> {
+    pub fn new(aliases: &'a mut usize, limit: Option<usize>) -> Self {
+        Self {
+            aliases,
+            limit,
+            counter: Default::default(),
+        }
+    }
+}
+
+impl<'ctx, 'a> Visitor<'ctx> for AliasesCalculate<'ctx, 'a> {
+    fn exit_document(&mut self, _ctx: &mut VisitorContext<'ctx>, doc: &'ctx ExecutableDocument) {
+        *self.aliases = self.counter.total(doc, self.limit);
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        name: Option<&'ctx Name>,
+        _operation_definition: &'ctx Positioned<OperationDefinition>,
+    ) {
+        self.counter
+            .enter_scope(Scope::Operation(name.map(Name::as_str)));
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        name: &'ctx Name,
+        _fragment_definition: &'ctx Positioned<FragmentDefinition>,
+    ) {
+        self.counter.enter_scope(Scope::Fragment(name));
+    }
+
+    fn enter_field(&mut self, _ctx: &mut VisitorContext<'ctx>, field: &'ctx Positioned<Field>) {
+        if field.node.alias.is_some() {
+            self.counter.add(1);
+        }
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        fragment_spread: &'ctx Positioned<FragmentSpread>,
+    ) {
+        self.counter
+            .add_spread(&fragment_spread.node.fragment_name.node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_query;
+    use crate::validation::{visit, VisitorContext};
+    use crate::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    struct Query;
+
+    struct MyObj;
+
+    #[Object(internal)]
+    impl MyObj {
+        async fn a(&self) -> i32 {
+            todo!()
+        }
+
+        async fn b(&self) -> i32 {
+            todo!()
+        }
+    }
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            todo!()
+        }
+
+        async fn obj(&self) -> MyObj {
+            todo!()
+        }
+    }
+
+    fn check_aliases(query: &str, expect_aliases: usize) {
+        let registry = Schema::<Query, EmptyMutation, EmptySubscription>::create_registry();
+        let doc = parse_query(query).unwrap();
+        let mut ctx = VisitorContext::new(&registry, &doc, None);
+        let mut aliases = 0;
+        let mut aliases_calculate = AliasesCalculate::new(&mut aliases, None);
+        visit(&mut aliases_calculate, &mut ctx, &doc);
+        assert_eq!(aliases, expect_aliases);
+    }
+
+    #[test]
+    fn aliases() {
+        check_aliases("{ value }", 0);
+
+        check_aliases(
+            r#"
+        {
+            v1: value
+            v2: value
+            obj { a b1: b b2: b }
+        }"#,
+            4,
+        );
+
+        check_aliases(
+            r#"
+        fragment A on MyObj {
+            a1: a a2: a
+        }
+
+        query {
+            o1: obj { ... A }
+            o2: obj { ... A }
+        }"#,
+            6,
+        );
+    }
+}
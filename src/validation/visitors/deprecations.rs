@@ -0,0 +1,371 @@
+use std::collections::{HashMap, HashSet};
+
+use async_graphql_value::{ConstValue, Value};
+use indexmap::IndexMap;
+
+use crate::parser::types::{
+    Directive, ExecutableDocument, Field, FragmentDefinition, FragmentSpread, OperationDefinition,
+};
+use crate::registry::{MetaEnumValue, MetaInputValue, MetaType, MetaTypeName, Registry};
+use crate::validation::utils::Scope;
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::validation::DeprecatedUsage;
+use crate::{Name, Pos, Positioned};
+
+/// Finds the uses of deprecated elements by the operation named `operation_name`, including the
+/// fragments it spreads, or by all the operations if it is `None`.
+pub struct DeprecationsCalculate<'ctx, 'a> {
+    deprecations: &'a mut Vec<DeprecatedUsage>,
+    operation_name: Option<&'a str>,
+    current_scope: Option<Scope<'ctx>>,
+    current_args: Option<(String, &'ctx IndexMap<String, MetaInputValue>)>,
+    usages: Vec<(Scope<'ctx>, DeprecatedUsage)>,
+    spreads: HashMap<Scope<'ctx>, Vec<&'ctx str>>,
+}
+
+impl<'ctx, 'a> DeprecationsCalculate<'ctx, 'a> {
+    pub fn new(
+        deprecations: &'a mut Vec<DeprecatedUsage>,
+        operation_name: Option<&'a str>,
+    ) -> Self {
+        Self {
+            deprecations,
+            operation_name,
+            current_scope: None,
+            current_args: None,
+            usages: Vec::new(),
+            spreads: HashMap::new(),
+        }
+    }
+
+    fn add(&mut self, coordinate: String, reason: &str, pos: Pos) {
+        if let Some(scope) = &self.current_scope {
+            self.usages.push((
+                scope.clone(),
+                DeprecatedUsage {
+                    coordinate,
+                    reason: reason.to_string(),
+                    pos,
+                },
+            ));
+        }
+    }
+
+    /// Finds the deprecated input fields and enum values used in a value.
+    fn check_input_value(&mut self, registry: &Registry, ty: &str, value: &ConstValue, pos: Pos) {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.check_input_value(registry, ty, value, pos),
+            MetaTypeName::List(ty) => match value {
+                ConstValue::List(items) => {
+                    for item in items {
+                        self.check_input_value(registry, ty, item, pos);
+                    }
+                }
+                _ => self.check_input_value(registry, ty, value, pos),
+            },
+            MetaTypeName::Named(name) => match (registry.types.get(name), value) {
+                (Some(MetaType::InputObject { input_fields, .. }), ConstValue::Object(values)) => {
+                    for (field_name, value) in values {
+                        if let Some(field) = input_fields.get(field_name.as_str()) {
+                            if let Some(reason) = &field.deprecation {
+                                self.add(format!("{}.{}", name, field_name), reason, pos);
+                            }
+                            self.check_input_value(registry, &field.ty, value, pos);
+                        }
+                    }
+                }
+                (Some(MetaType::Enum { enum_values, .. }), ConstValue::Enum(item)) => {
+                    self.check_enum_value(name, enum_values.get(item.as_str()), pos);
+                }
+                (Some(MetaType::Enum { enum_values, .. }), ConstValue::String(item)) => {
+                    self.check_enum_value(name, enum_values.get(item.as_str()), pos);
+                }
+                _ => {}
+            },
+        }
+    }
+
+    fn check_enum_value(&mut self, enum_name: &str, value: Option<&MetaEnumValue>, pos: Pos) {
+        if let Some(MetaEnumValue {
+            name,
+            deprecation: Some(reason),
+            ..
+        }) = value
+        {
+            self.add(format!("{}.{}", enum_name, name), reason, pos);
+        }
+    }
+}
+
+impl<'ctx, 'a> Visitor<'ctx> for DeprecationsCalculate<'ctx, 'a> {
+    fn exit_document(&mut self, _ctx: &mut VisitorContext<'ctx>, doc: &'ctx ExecutableDocument) {
+        let mut pending = doc
+            .operations
+            .iter()
+            .map(|(name, _)| Scope::Operation(name.map(Name::as_str)))
+            .filter(|scope| match (scope, self.operation_name) {
+                (Scope::Operation(name), Some(operation_name)) => *name == Some(operation_name),
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+        let mut selected = HashSet::new();
+        while let Some(scope) = pending.pop() {
+            if let Some(fragment_names) = self.spreads.get(&scope) {
+                pending.extend(
+                    fragment_names
+                        .iter()
+                        .map(|fragment_name| Scope::Fragment(fragment_name))
+                        .filter(|fragment| !selected.contains(fragment)),
+                );
+            }
+            selected.insert(scope);
+        }
+
+        // The fragments are not visited in the order of the document.
+        let start = self.deprecations.len();
+        self.deprecations.extend(
+            self.usages
+                .drain(..)
+                .filter(|(scope, _)| selected.contains(scope))
+                .map(|(_, usage)| usage),
+        );
+        self.deprecations[start..].sort_by_key(|usage| usage.pos);
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        name: Option<&'ctx Name>,
+        _operation_definition: &'ctx Positioned<OperationDefinition>,
+    ) {
+        self.current_scope = Some(Scope::Operation(name.map(Name::as_str)));
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        name: &'ctx Name,
+        _fragment_definition: &'ctx Positioned<FragmentDefinition>,
+    ) {
+        self.current_scope = Some(Scope::Fragment(name));
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        fragment_spread: &'ctx Positioned<FragmentSpread>,
+    ) {
+        if let Some(scope) = &self.current_scope {
+            self.spreads
+                .entry(scope.clone())
+                .or_default()
+                .push(&fragment_spread.node.fragment_name.node);
+        }
+    }
+
+    fn enter_directive(
+        &mut self,
+        ctx: &mut VisitorContext<'ctx>,
+        directive: &'ctx Positioned<Directive>,
+    ) {
+        let name = &directive.node.name.node;
+        self.current_args = ctx
+            .registry
+            .directives
+            .get(name.as_str())
+            .map(|d| (format!("@{}", name), &d.args));
+    }
+
+    fn exit_directive(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _directive: &'ctx Positioned<Directive>,
+    ) {
+        self.current_args = None;
+    }
+
+    fn enter_argument(
+        &mut self,
+        ctx: &mut VisitorContext<'ctx>,
+        name: &'ctx Positioned<Name>,
+        value: &'ctx Positioned<Value>,
+    ) {
+        let (prefix, arg) = match &self.current_args {
+            Some((prefix, args)) => match args.get(name.node.as_str()) {
+                Some(arg) => (prefix.clone(), arg),
+                None => return,
+            },
+            None => return,
+        };
+
+        if let Some(reason) = &arg.deprecation {
+            self.add(format!("{}({}:)", prefix, arg.name), reason, name.pos);
+        }
+
+        let value = value.node.clone().into_const_with(|var_name| {
+            ctx.variables
+                .and_then(|variables| variables.0.get(&var_name))
+                .cloned()
+                .ok_or(())
+        });
+        if let Ok(value) = value {
+            self.check_input_value(ctx.registry, &arg.ty, &value, name.pos);
+        }
+    }
+
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'ctx>, field: &'ctx Positioned<Field>) {
+        self.current_args = None;
+        if let Some(parent) = ctx.parent_type() {
+            if let Some(meta_field) = parent.field_by_name(&field.node.name.node) {
+                let coordinate = format!("{}.{}", parent.name(), meta_field.name);
+                if let Some(reason) = &meta_field.deprecation {
+                    self.add(coordinate.clone(), reason, field.pos);
+                }
+                self.current_args = Some((coordinate, &meta_field.args));
+            }
+        }
+    }
+
+    fn exit_field(&mut self, _ctx: &mut VisitorContext<'ctx>, _field: &'ctx Positioned<Field>) {
+        self.current_args = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_query;
+    use crate::validation::{visit, VisitorContext};
+    use crate::{EmptyMutation, EmptySubscription, Enum, InputObject, Object, Schema};
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(internal)]
+    enum MyEnum {
+        A,
+        #[graphql(deprecation = "Use `A`.")]
+        B,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(internal)]
+    struct MyInput {
+        a: Option<i32>,
+        #[graphql(deprecation = "Use `a`.")]
+        b: Option<i32>,
+        children: Option<Vec<MyInput>>,
+        e: Option<MyEnum>,
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(
+            &self,
+            a: Option<i32>,
+            #[graphql(deprecation = "Use `a`.")] b: Option<i32>,
+        ) -> i32 {
+            todo!()
+        }
+
+        async fn input(&self, input: MyInput) -> i32 {
+            todo!()
+        }
+
+        #[graphql(deprecation = "Use `value`.")]
+        async fn old_value(&self, e: Option<Vec<MyEnum>>) -> i32 {
+            todo!()
+        }
+    }
+
+    fn check_deprecations(query: &str, expect_coordinates: &[&str]) {
+        check_operation_deprecations(query, None, expect_coordinates);
+    }
+
+    fn check_operation_deprecations(
+        query: &str,
+        operation_name: Option<&str>,
+        expect_coordinates: &[&str],
+    ) {
+        let registry = Schema::<Query, EmptyMutation, EmptySubscription>::create_registry();
+        let doc = parse_query(query).unwrap();
+        let mut ctx = VisitorContext::new(&registry, &doc, None);
+        let mut deprecations = Vec::new();
+        let mut deprecations_calculate =
+            DeprecationsCalculate::new(&mut deprecations, operation_name);
+        visit(&mut deprecations_calculate, &mut ctx, &doc);
+        assert_eq!(
+            deprecations
+                .iter()
+                .map(|usage| usage.coordinate.as_str())
+                .collect::<Vec<_>>(),
+            expect_coordinates
+        );
+    }
+
+    #[test]
+    fn deprecations() {
+        check_deprecations("{ value(a: 1) }", &[]);
+        check_deprecations("{ value(b: 1) }", &["Query.value(b:)"]);
+        check_deprecations("{ input(input: { a: 1 }) }", &[]);
+        check_deprecations(
+            "{ input(input: { b: 1, children: [{ a: 1 }, { b: 2 }] }) }",
+            &["MyInput.b", "MyInput.b"],
+        );
+        check_deprecations("{ oldValue }", &["Query.oldValue"]);
+        check_deprecations(
+            "{ oldValue(e: [A, B]) input(input: { e: B }) }",
+            &["Query.oldValue", "MyEnum.B", "MyEnum.B"],
+        );
+        check_deprecations(
+            r#"
+        fragment A on Query {
+            value(b: 1)
+        }
+
+        query {
+            ... A
+            ... A
+        }"#,
+            &["Query.value(b:)"],
+        );
+    }
+    #[test]
+    fn deprecations_of_operation() {
+        let query = r#"
+        fragment A on Query {
+            value(b: 1)
+            ... B
+        }
+
+        fragment B on Query {
+            oldValue
+        }
+
+        fragment C on Query {
+            input(input: { b: 1 })
+        }
+
+        query X {
+            ... A
+        }
+
+        query Y {
+            oldValue
+            ... C
+        }"#;
+        check_operation_deprecations(query, Some("X"), &["Query.value(b:)", "Query.oldValue"]);
+        check_operation_deprecations(query, Some("Y"), &["MyInput.b", "Query.oldValue"]);
+        check_operation_deprecations(
+            query,
+            None,
+            &[
+                "Query.value(b:)",
+                "Query.oldValue",
+                "MyInput.b",
+                "Query.oldValue",
+            ],
+        );
+        check_operation_deprecations(query, Some("Z"), &[]);
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use crate::parser::types::{
+    FragmentDefinition, OperationDefinition, Selection, VariableDefinition,
+};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::{Name, Positioned};
+
+/// Calculates the maximum number of times the same directive is applied to a single location.
+pub struct DirectivesCalculate<'a> {
+    max_directives: &'a mut usize,
+}
+
+impl<'a> DirectivesCalculate<'a> {
+    pub fn new(max_directives: &'a mut usize) -> Self {
+        Self { max_directives }
+    }
+
+    fn check<'b>(&mut self, names: impl Iterator<Item = &'b Name>) {
+        let mut counts = HashMap::new();
+        for name in names {
+            let count = counts.entry(name).or_insert(0);
+            *count += 1;
+            *self.max_directives = (*self.max_directives).max(*count);
+        }
+    }
+}
+
+impl<'ctx, 'a> Visitor<'ctx> for DirectivesCalculate<'a> {
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _name: Option<&'ctx Name>,
+        operation_definition: &'ctx Positioned<OperationDefinition>,
+    ) {
+        self.check(
+            operation_definition
+                .node
+                .directives
+                .iter()
+                .map(|directive| &directive.node.name.node),
+        );
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        variable_definition: &'ctx Positioned<VariableDefinition>,
+    ) {
+        self.check(
+            variable_definition
+                .node
+                .directives
+                .iter()
+                .map(|directive| &directive.node.name.node),
+        );
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _name: &'ctx Name,
+        fragment_definition: &'ctx Positioned<FragmentDefinition>,
+    ) {
+        self.check(
+            fragment_definition
+                .node
+                .directives
+                .iter()
+                .map(|directive| &directive.node.name.node),
+        );
+    }
+
+    fn enter_selection(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        selection: &'ctx Positioned<Selection>,
+    ) {
+        self.check(
+            selection
+                .node
+                .directives()
+                .iter()
+                .map(|directive| &directive.node.name.node),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_query;
+    use crate::validation::{visit, VisitorContext};
+    use crate::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    struct Query;
+
+    struct MyObj;
+
+    #[Object(internal)]
+    impl MyObj {
+        async fn a(&self) -> i32 {
+            todo!()
+        }
+    }
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            todo!()
+        }
+
+        async fn obj(&self) -> MyObj {
+            todo!()
+        }
+    }
+
+    fn check_directives(query: &str, expect_directives: usize) {
+        let registry = Schema::<Query, EmptyMutation, EmptySubscription>::create_registry();
+        let doc = parse_query(query).unwrap();
+        let mut ctx = VisitorContext::new(&registry, &doc, None);
+        let mut directives = 0;
+        let mut directives_calculate = DirectivesCalculate::new(&mut directives);
+        visit(&mut directives_calculate, &mut ctx, &doc);
+        assert_eq!(directives, expect_directives);
+    }
+
+    #[test]
+    fn directives() {
+        check_directives("{ value }", 0);
+
+        check_directives(
+            r#"
+        {
+            value @skip(if: false) @include(if: true)
+            obj @skip(if: false) @skip(if: false) { a }
+        }"#,
+            2,
+        );
+
+        check_directives(
+            r#"
+        fragment A on MyObj @a @a @b {
+            a @skip(if: false)
+        }
+
+        query @a @b @c {
+            obj { ... A @skip(if: false) }
+        }"#,
+            2,
+        );
+
+        check_directives(
+            r#"
+        query($a: Int @a @a @a @b) {
+            value @skip(if: false)
+        }"#,
+            3,
+        );
+    }
+}
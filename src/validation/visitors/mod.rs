@@ -1,7 +1,17 @@
+mod aliases;
 mod cache_control;
 mod complexity;
+mod deprecations;
 mod depth;
+mod directives;
+mod root_fields;
+mod selections;
 
+pub use aliases::AliasesCalculate;
 pub use cache_control::CacheControlCalculate;
 pub use complexity::ComplexityCalculate;
+pub use deprecations::DeprecationsCalculate;
 pub use depth::DepthCalculate;
+pub use directives::DirectivesCalculate;
+pub use root_fields::RootFieldsCalculate;
+pub use selections::SelectionsCalculate;
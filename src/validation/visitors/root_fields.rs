@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use crate::parser::types::{OperationDefinition, Selection, SelectionSet};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::{Name, Positioned};
+
+/// Calculates the maximum number of root fields selected by an operation.
+///
+/// Fields selected through fragments on the root type are counted as well, the fields of a
+/// fragment are counted once and added at every spread of it.
+pub struct RootFieldsCalculate<'a> {
+    root_fields: &'a mut usize,
+    limit: Option<usize>,
+}
+
+impl<'a> RootFieldsCalculate<'a> {
+    pub fn new(root_fields: &'a mut usize, limit: Option<usize>) -> Self {
+        Self { root_fields, limit }
+    }
+}
+
+/// Counts the fields of a selection set, counting stops as soon as it exceeds `limit`.
+///
+/// `fragments` holds the counts of the fragments, `None` while a fragment is being counted.
+fn count_fields<'a>(
+    ctx: &VisitorContext<'a>,
+    selection_set: &'a SelectionSet,
+    limit: usize,
+    fragments: &mut HashMap<&'a str, Option<usize>>,
+) -> usize {
+    let mut count = 0usize;
+    for selection in &selection_set.items {
+        if count > limit {
+            break;
+        }
+        let selection_count = match &selection.node {
+            Selection::Field(_) => 1,
+            Selection::FragmentSpread(fragment_spread) => {
+                let name = fragment_spread.node.fragment_name.node.as_str();
+                match (fragments.get(name), ctx.fragment(name)) {
+                    (Some(Some(fragment_count)), _) => *fragment_count,
+                    (None, Some(fragment)) => {
+                        fragments.insert(name, None);
+                        let fragment_count =
+                            count_fields(ctx, &fragment.node.selection_set.node, limit, fragments);
+                        fragments.insert(name, Some(fragment_count));
+                        fragment_count
+                    }
+                    // A fragment spread inside of itself or an unknown fragment, they are
+                    // reported by other rules.
+                    _ => 0,
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => count_fields(
+                ctx,
+                &inline_fragment.node.selection_set.node,
+                limit,
+                fragments,
+            ),
+        };
+        count = count.saturating_add(selection_count);
+    }
+    count
+}
+
+impl<'ctx, 'a> Visitor<'ctx> for RootFieldsCalculate<'a> {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'ctx>,
+        _name: Option<&'ctx Name>,
+        operation_definition: &'ctx Positioned<OperationDefinition>,
+    ) {
+        let count = count_fields(
+            ctx,
+            &operation_definition.node.selection_set.node,
+            self.limit.unwrap_or(usize::MAX),
+            &mut HashMap::new(),
+        );
+        *self.root_fields = (*self.root_fields).max(count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_query;
+    use crate::validation::{visit, VisitorContext};
+    use crate::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    struct Query;
+
+    struct MyObj;
+
+    #[Object(internal)]
+    impl MyObj {
+        async fn a(&self) -> i32 {
+            todo!()
+        }
+
+        async fn b(&self) -> i32 {
+            todo!()
+        }
+    }
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            todo!()
+        }
+
+        async fn obj(&self) -> MyObj {
+            todo!()
+        }
+    }
+
+    fn check_root_fields(query: &str, expect_root_fields: usize) {
+        let registry = Schema::<Query, EmptyMutation, EmptySubscription>::create_registry();
+        let doc = parse_query(query).unwrap();
+        let mut ctx = VisitorContext::new(&registry, &doc, None);
+        let mut root_fields = 0;
+        let mut root_fields_calculate = RootFieldsCalculate::new(&mut root_fields, None);
+        visit(&mut root_fields_calculate, &mut ctx, &doc);
+        assert_eq!(root_fields, expect_root_fields);
+    }
+
+    #[test]
+    fn root_fields() {
+        check_root_fields("{ value }", 1);
+
+        check_root_fields(
+            r#"
+        {
+            value
+            obj { a b }
+        }"#,
+            2,
+        );
+
+        check_root_fields(
+            r#"
+        fragment A on Query {
+            v1: value
+            v2: value
+            obj { a }
+        }
+
+        query A {
+            value
+            ... A
+            ... on Query { obj { b } }
+        }
+
+        query B {
+            value
+        }"#,
+            5,
+        );
+    }
+
+    #[test]
+    fn root_fields_of_nested_fragments() {
+        check_root_fields(
+            r#"
+        fragment A on Query {
+            ... B ... B
+        }
+
+        fragment B on Query {
+            value obj { a }
+        }
+
+        query {
+            value
+            ... A
+        }"#,
+            5,
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use crate::parser::types::{
+    ExecutableDocument, FragmentDefinition, FragmentSpread, OperationDefinition, Selection,
+};
+use crate::validation::utils::{Scope, ScopedCounter};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::{Name, Positioned};
+
+/// Calculates the total number of selections in the query, the selections of a fragment are
+/// counted at every spread of it.
+pub struct SelectionsCalculate<'ctx, 'a> {
+    selections: &'a mut usize,
+    limit: Option<usize>,
+    counter: ScopedCounter<'ctx>,
+}
+
+impl<'ctx, 'a> SelectionsCalculate<'ctx, 'a> {
+    pub fn new(selections: &'a mut usize, limit: Option<usize>) -> Self {
+        Self {
+            selections,
+            limit,
+            counter: Default::default(),
+        }
+    }
+}
+
+impl<'ctx, 'a> Visitor<'ctx> for SelectionsCalculate<'ctx, 'a> {
+    fn exit_document(&mut self, _ctx: &mut VisitorContext<'ctx>, doc: &'ctx ExecutableDocument) {
+        *self.selections = self.counter.total(doc, self.limit);
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        name: Option<&'ctx Name>,
+        _operation_definition: &'ctx Positioned<OperationDefinition>,
+    ) {
+        self.counter
+            .enter_scope(Scope::Operation(name.map(Name::as_str)));
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        name: &'ctx Name,
+        _fragment_definition: &'ctx Positioned<FragmentDefinition>,
+    ) {
+        self.counter.enter_scope(Scope::Fragment(name));
+    }
+
+    fn enter_selection(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _selection: &'ctx Positioned<Selection>,
+    ) {
+        self.counter.add(1);
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        fragment_spread: &'ctx Positioned<FragmentSpread>,
+    ) {
+        self.counter
+            .add_spread(&fragment_spread.node.fragment_name.node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_query;
+    use crate::validation::{visit, VisitorContext};
+    use crate::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    struct Query;
+
+    struct MyObj;
+
+    #[Object(internal)]
+    impl MyObj {
+        async fn a(&self) -> i32 {
+            todo!()
+        }
+
+        async fn b(&self) -> i32 {
+            todo!()
+        }
+    }
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self) -> i32 {
+            todo!()
+        }
+
+        async fn obj(&self) -> MyObj {
+            todo!()
+        }
+    }
+
+    fn calculate_selections(query: &str, limit: Option<usize>) -> usize {
+        let registry = Schema::<Query, EmptyMutation, EmptySubscription>::create_registry();
+        let doc = parse_query(query).unwrap();
+        let mut ctx = VisitorContext::new(&registry, &doc, None);
+        let mut selections = 0;
+        let mut selections_calculate = SelectionsCalculate::new(&mut selections, limit);
+        visit(&mut selections_calculate, &mut ctx, &doc);
+        selections
+    }
+
+    fn check_selections(query: &str, expect_selections: usize) {
+        assert_eq!(calculate_selections(query, None), expect_selections);
+    }
+
+    #[test]
+    fn selections() {
+        check_selections("{ value }", 1);
+
+        check_selections(
+            r#"
+        {
+            value
+            obj { a b }
+        }"#,
+            4,
+        );
+
+        check_selections(
+            r#"
+        fragment A on MyObj {
+            a b
+        }
+
+        query {
+            obj { ... A }
+            o: obj { ... on MyObj { a } }
+        }"#,
+            7,
+        );
+    }
+
+    #[test]
+    fn selections_of_nested_fragments() {
+        check_selections(
+            r#"
+        fragment A on MyObj {
+            ... B ... B
+        }
+
+        fragment B on MyObj {
+            ... C ... C
+        }
+
+        fragment C on MyObj {
+            a b
+        }
+
+        query {
+            obj { ... A }
+        }"#,
+            16,
+        );
+
+        // Every fragment spreads the next one twice, so the query has more than 2^64 selections.
+        let mut query = String::from("{ obj { ... F0 } }");
+        for i in 0..64 {
+            query += &format!(
+                " fragment F{} on MyObj {{ ... F{} ... F{} }}",
+                i,
+                i + 1,
+                i + 1
+            );
+        }
+        query += " fragment F64 on MyObj { a }";
+        assert_eq!(calculate_selections(&query, None), usize::MAX);
+        assert!(calculate_selections(&query, Some(100)) > 100);
+    }
+}
@@ -3,12 +3,14 @@
 mod int_validators;
 mod list_validators;
 mod string_validators;
+mod upload_validators;
 
-use crate::Value;
+use crate::{UploadValue, Value};
 
 pub use int_validators::{IntEqual, IntGreaterThan, IntLessThan, IntNonZero, IntRange};
 pub use list_validators::{ListMaxLength, ListMinLength};
 pub use string_validators::{Email, StringMaxLength, StringMinLength, MAC};
+pub use upload_validators::{UploadContentType, UploadMaxSize};
 
 /// Input value validator
 ///
@@ -18,7 +20,7 @@ pub use string_validators::{Email, StringMaxLength, StringMinLength, MAC};
 ///
 /// ```no_run
 /// use async_graphql::*;
-/// use async_graphql::validators::{Email, MAC, IntRange};
+/// use async_graphql::validators::{Email, MAC, IntRange, UploadMaxSize, UploadContentType};
 ///
 /// struct QueryRoot;
 ///
@@ -39,6 +41,20 @@ pub use string_validators::{Email, StringMaxLength, StringMinLength, MAC};
 ///         unimplemented!()
 ///     }
 /// }
+///
+/// struct MutationRoot;
+///
+/// #[Object]
+/// impl MutationRoot {
+///     // Input is an image of at most 1MB
+///     async fn upload(
+///         &self,
+///         #[graphql(validator(and(UploadMaxSize(size = "1048576"), UploadContentType(allowed = r#""image/*""#))))]
+///         file: Upload,
+///     ) -> bool {
+///         unimplemented!()
+///     }
+/// }
 /// ```
 pub trait InputValueValidator
 where
@@ -48,6 +64,14 @@ where
     ///
     /// If the input type is different from the required type, return `Ok(())` directly, and other validators will find this error.
     fn is_valid(&self, value: &Value) -> Result<(), String>;
+
+    /// Check an uploaded file is valid, returns the reason for the error if it fails.
+    ///
+    /// This is called for the files passed to an argument of type `Upload`, before the resolvers
+    /// are executed.
+    fn is_valid_upload(&self, _upload: &UploadValue) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 /// An extension trait for `InputValueValidator`
@@ -82,6 +106,11 @@ where
         self.0.is_valid(value)?;
         self.1.is_valid(value)
     }
+
+    fn is_valid_upload(&self, upload: &UploadValue) -> Result<(), String> {
+        self.0.is_valid_upload(upload)?;
+        self.1.is_valid_upload(upload)
+    }
 }
 
 /// Invalidator for `InputValueValidator::or`
@@ -99,6 +128,14 @@ where
             Ok(())
         }
     }
+
+    fn is_valid_upload(&self, upload: &UploadValue) -> Result<(), String> {
+        if self.0.is_valid_upload(upload).is_err() {
+            self.1.is_valid_upload(upload)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 /// Invalidator for `InputValueValidator::map_err`
@@ -112,4 +149,8 @@ where
     fn is_valid(&self, value: &Value) -> Result<(), String> {
         self.0.is_valid(value).map_err(&self.1)
     }
+
+    fn is_valid_upload(&self, upload: &UploadValue) -> Result<(), String> {
+        self.0.is_valid_upload(upload).map_err(&self.1)
+    }
 }
@@ -0,0 +1,62 @@
+use crate::types::content_type_matches;
+use crate::validators::InputValueValidator;
+use crate::{UploadValue, Value};
+
+/// Upload maximum size validator
+///
+/// The size of a streamed upload is unknown before the resolver reads it, so it is not checked
+/// by this validator.
+pub struct UploadMaxSize {
+    /// Must be less than or equal to this value, in bytes.
+    pub size: i64,
+}
+
+impl InputValueValidator for UploadMaxSize {
+    fn is_valid(&self, _value: &Value) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn is_valid_upload(&self, upload: &UploadValue) -> Result<(), String> {
+        match upload.size() {
+            Ok(size) if size > self.size as u64 => Err(format!(
+                "the file \"{}\" is {} bytes, must be less than or equal to {} bytes",
+                upload.filename, size, self.size
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Upload content type validator
+///
+/// A file without a content type is treated as `application/octet-stream`.
+pub struct UploadContentType {
+    /// A comma separated list of the allowed content types, which may contain wildcards such as
+    /// `image/*`.
+    pub allowed: String,
+}
+
+impl InputValueValidator for UploadContentType {
+    fn is_valid(&self, _value: &Value) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn is_valid_upload(&self, upload: &UploadValue) -> Result<(), String> {
+        let content_type = upload
+            .content_type
+            .as_deref()
+            .unwrap_or("application/octet-stream");
+        if self
+            .allowed
+            .split(',')
+            .any(|pattern| content_type_matches(pattern, content_type))
+        {
+            Ok(())
+        } else {
+            Err(format!(
+                "the content type of the file \"{}\" is \"{}\", must be one of \"{}\"",
+                upload.filename, content_type, self.allowed
+            ))
+        }
+    }
+}
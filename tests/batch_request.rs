@@ -31,3 +31,297 @@ pub async fn test_batch_request() {
         ])
     );
 }
+
+#[async_std::test]
+pub async fn test_batch_request_limit_size() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_batch_size(2)
+        .finish();
+
+    let batch: BatchRequest = vec![Request::new("{ value }"), Request::new("{ value }")].into();
+    let resp = schema.execute_batch(batch).await;
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!([{"data": { "value": 10 }}, {"data": { "value": 10 }}])
+    );
+
+    let batch: BatchRequest = vec![
+        Request::new("{ value }"),
+        Request::new("{ value }"),
+        Request::new("{ value }"),
+    ]
+    .into();
+    let resp = schema.execute_batch(batch).await;
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!([
+            {
+                "data": null,
+                "errors": [{"message": "The batch contains 3 requests, which exceeds the limit of 2."}]
+            },
+            {
+                "data": null,
+                "errors": [{"message": "The batch contains 3 requests, which exceeds the limit of 2."}]
+            },
+            {
+                "data": null,
+                "errors": [{"message": "The batch contains 3 requests, which exceeds the limit of 2."}]
+            },
+        ])
+    );
+}
+
+#[async_std::test]
+pub async fn test_batch_request_concurrency() {
+    use futures_util::lock::Mutex;
+    use std::sync::Arc;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>, n: i32) -> i32 {
+            let running = ctx.data_unchecked::<Arc<Mutex<(usize, usize)>>>();
+            {
+                let mut running = running.lock().await;
+                running.0 += 1;
+                running.1 = running.1.max(running.0);
+            }
+            async_std::task::sleep(std::time::Duration::from_millis(20)).await;
+            running.lock().await.0 -= 1;
+            n
+        }
+    }
+
+    for &(concurrency, expected) in &[(None, 8), (Some(2), 2), (Some(1), 1)] {
+        let running = Arc::new(Mutex::new((0usize, 0usize)));
+        let mut builder = Schema::build(Query, EmptyMutation, EmptySubscription);
+        if let Some(concurrency) = concurrency {
+            builder = builder.limit_batch_concurrency(concurrency);
+        }
+        let schema = builder.finish();
+
+        let mut data = Data::default();
+        data.insert(running.clone());
+        let batch: BatchRequest = (1..=10)
+            .map(|n| Request::new(format!("{{ value(n: {}) }}", n)))
+            .collect::<Vec<_>>()
+            .into();
+        let resp = schema.execute_batch_with_data(batch, data).await;
+        assert_eq!(
+            serde_json::to_value(&resp).unwrap(),
+            serde_json::Value::Array(
+                (1..=10)
+                    .map(|n| serde_json::json!({"data": { "value": n }}))
+                    .collect()
+            )
+        );
+        assert_eq!(running.lock().await.1, expected);
+    }
+}
+
+#[cfg(feature = "apollo_persisted_queries")]
+#[async_std::test]
+pub async fn test_batch_request_persisted_queries_concurrency() {
+    use async_graphql::extensions::apollo_persisted_queries::{
+        ApolloPersistedQueries, LruCacheStorage,
+    };
+    use futures_util::lock::Mutex;
+    use std::sync::Arc;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>, n: i32) -> i32 {
+            let running = ctx.data_unchecked::<Arc<Mutex<(usize, usize)>>>();
+            {
+                let mut running = running.lock().await;
+                running.0 += 1;
+                running.1 = running.1.max(running.0);
+            }
+            async_std::task::sleep(std::time::Duration::from_millis(20)).await;
+            running.lock().await.0 -= 1;
+            n
+        }
+    }
+
+    let persisted_request = |query: &str| {
+        let mut request = Request::new(query);
+        request.extensions.insert(
+            "persistedQuery".to_string(),
+            value!({
+                "version": 1,
+                "sha256Hash": "da39d06202acd0858b66ad58ba0fb94d51852d429fb5a138c63faed5aeb61fab",
+            }),
+        );
+        request
+    };
+
+    let running = Arc::new(Mutex::new((0usize, 0usize)));
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension(ApolloPersistedQueries::new(LruCacheStorage::new(256)))
+        .data(running.clone())
+        .finish();
+    assert!(schema
+        .execute(persisted_request("{ value(n: 1) }"))
+        .await
+        .is_ok());
+    *running.lock().await = (0, 0);
+
+    // The queries of the requests are only known once the extension has loaded them, and none of
+    // them is a mutation.
+    let batch: BatchRequest = vec![
+        persisted_request(""),
+        persisted_request(""),
+        persisted_request(""),
+    ]
+    .into();
+    let resp = schema.execute_batch(batch).await;
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!([
+            {"data": { "value": 1 }},
+            {"data": { "value": 1 }},
+            {"data": { "value": 1 }},
+        ])
+    );
+    assert_eq!(running.lock().await.1, 3);
+}
+
+#[async_std::test]
+pub async fn test_batch_request_with_mutation() {
+    use futures_util::lock::Mutex;
+    use std::sync::Arc;
+
+    type Running = Arc<Mutex<(usize, usize, i32)>>;
+
+    async fn run<T>(ctx: &Context<'_>, f: impl FnOnce(&mut i32) -> T) -> T {
+        let running = ctx.data_unchecked::<Running>();
+        {
+            let mut running = running.lock().await;
+            running.0 += 1;
+            running.1 = running.1.max(running.0);
+        }
+        async_std::task::sleep(std::time::Duration::from_millis(20)).await;
+        let mut running = running.lock().await;
+        running.0 -= 1;
+        f(&mut running.2)
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>) -> i32 {
+            run(ctx, |value| *value).await
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn set_value(&self, ctx: &Context<'_>, value: i32) -> i32 {
+            run(ctx, |current| {
+                *current = value;
+                value
+            })
+            .await
+        }
+    }
+
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let running: Running = Default::default();
+    let mut data = Data::default();
+    data.insert(running.clone());
+    let batch: BatchRequest = vec![
+        Request::new("mutation { setValue(value: 10) }"),
+        Request::new("{ value }"),
+        Request::new("mutation { setValue(value: 20) }"),
+        Request::new("{ value }"),
+    ]
+    .into();
+    let resp = schema.execute_batch_with_data(batch, data).await;
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!([
+            {"data": { "setValue": 10 }},
+            {"data": { "value": 10 }},
+            {"data": { "setValue": 20 }},
+            {"data": { "value": 20 }},
+        ])
+    );
+    assert_eq!(running.lock().await.1, 1);
+}
+
+#[cfg(feature = "dataloader")]
+#[async_std::test]
+pub async fn test_batch_request_shared_dataloader() {
+    use async_graphql::dataloader::{DataLoader, Loader};
+    use futures_util::lock::Mutex;
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+    use std::sync::Arc;
+
+    struct MyLoader {
+        calls: Arc<Mutex<Vec<Vec<i32>>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Loader<i32> for MyLoader {
+        type Value = i32;
+        type Error = Infallible;
+
+        async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+            let mut keys = keys.to_vec();
+            keys.sort_unstable();
+            self.calls.lock().await.push(keys.clone());
+            Ok(keys.into_iter().map(|n| (n, n * 10)).collect())
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>, n: i32) -> Option<i32> {
+            ctx.data_unchecked::<DataLoader<MyLoader>>()
+                .load_one(n)
+                .await
+                .unwrap()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let mut data = Data::default();
+    data.insert(DataLoader::new(MyLoader {
+        calls: calls.clone(),
+    }));
+    let batch: BatchRequest = vec![
+        Request::new("{ value(n: 1) }"),
+        Request::new("{ value(n: 2) }"),
+        Request::new("{ value(n: 3) }"),
+    ]
+    .into();
+    let resp = schema.execute_batch_with_data(batch, data).await;
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!([
+            {"data": { "value": 10 }},
+            {"data": { "value": 20 }},
+            {"data": { "value": 30 }},
+        ])
+    );
+    assert_eq!(*calls.lock().await, vec![vec![1, 2, 3]]);
+}
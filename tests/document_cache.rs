@@ -0,0 +1,188 @@
+#![cfg(feature = "document_cache")]
+
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+struct MyObj {
+    a: i32,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    #[graphql(complexity = "count * child_complexity")]
+    async fn objs(&self, count: usize) -> Vec<MyObj> {
+        (0..count as i32).map(|a| MyObj { a }).collect()
+    }
+
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+#[async_std::test]
+pub async fn test_document_cache() {
+    let cache = DocumentCache::new(2);
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .document_cache(cache.clone())
+        .finish();
+
+    for _ in 0..3 {
+        assert_eq!(
+            schema.execute("{ value }").await.data,
+            value!({ "value": 10 })
+        );
+    }
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.hits(), 2);
+    assert_eq!(cache.len(), 1);
+
+    // Validation errors are cached as well.
+    for _ in 0..2 {
+        assert!(schema.execute("{ unknown }").await.is_err());
+    }
+    assert_eq!(cache.misses(), 2);
+    assert_eq!(cache.hits(), 3);
+
+    // Parse errors are not cached.
+    for _ in 0..2 {
+        assert!(schema.execute("{ value").await.is_err());
+    }
+    assert_eq!(cache.misses(), 4);
+    assert_eq!(cache.len(), 2);
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[async_std::test]
+pub async fn test_document_cache_with_variables() {
+    let cache = DocumentCache::new(10);
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .document_cache(cache.clone())
+        .limit_complexity(5)
+        .finish();
+    let query = "query($count: Int!) { objs(count: $count) { a } }";
+
+    let request = Request::new(query).variables(Variables::from_json(serde_json::json!({
+        "count": 3,
+    })));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "objs": [{ "a": 0 }, { "a": 1 }, { "a": 2 }] })
+    );
+
+    // The document is cached, but it is validated again with the new variables.
+    let request = Request::new(query).variables(Variables::from_json(serde_json::json!({
+        "count": 10,
+    })));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap_err(),
+        vec![ServerError::new("Query is too complex.")]
+    );
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.hits(), 1);
+
+    // The checks which depend on the variables are run again on every request.
+    let request = Request::new(query).variables(Variables::from_json(serde_json::json!({
+        "count": "x",
+    })));
+    assert!(schema.execute(request).await.is_err());
+    let request = Request::new(query).variables(Variables::from_json(serde_json::json!({
+        "count": 1,
+    })));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "objs": [{ "a": 0 }] })
+    );
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.hits(), 3);
+
+    // The other checks are only run once, and their errors are cached.
+    let query = "query($count: Int!) { objs(count: $count) { unknown } }";
+    for count in 0..2 {
+        let request = Request::new(query).variables(Variables::from_json(serde_json::json!({
+            "count": count,
+        })));
+        assert!(schema.execute(request).await.is_err());
+    }
+    assert_eq!(cache.misses(), 2);
+    assert_eq!(cache.hits(), 4);
+}
+
+#[async_std::test]
+pub async fn test_document_cache_shared_by_schemas() {
+    let cache = DocumentCache::new(10);
+    let strict_schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .document_cache(cache.clone())
+        .finish();
+    let lenient_schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .document_cache(cache.clone())
+        .disable_validation_rule(BuiltinRule::NoUnusedFragments)
+        .finish();
+    let query = "fragment F on Query { value } { value }";
+
+    for _ in 0..2 {
+        assert!(strict_schema.execute(query).await.is_err());
+        assert_eq!(
+            lenient_schema
+                .execute(query)
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "value": 10 })
+        );
+    }
+    assert_eq!(cache.misses(), 2);
+    assert_eq!(cache.hits(), 2);
+    assert_eq!(cache.len(), 2);
+}
+
+#[async_std::test]
+pub async fn test_document_cache_with_visibility() {
+    struct IsAdmin(bool);
+
+    fn is_admin(ctx: &Context<'_>) -> bool {
+        ctx.data::<IsAdmin>()
+            .map(|is_admin| is_admin.0)
+            .unwrap_or_default()
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(visible = "is_admin")]
+        async fn secret(&self) -> i32 {
+            20
+        }
+    }
+
+    let cache = DocumentCache::new(10);
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .document_cache(cache.clone())
+        .validate_visibility()
+        .finish();
+
+    // The document is cached, but its visibility is checked again for each request.
+    for &is_admin in &[false, true, false] {
+        let resp = schema
+            .execute(Request::new("{ secret }").data(IsAdmin(is_admin)))
+            .await;
+        if is_admin {
+            assert_eq!(resp.data, value!({ "secret": 20 }));
+        } else {
+            assert_eq!(
+                resp.errors
+                    .into_iter()
+                    .map(|err| err.message)
+                    .collect::<Vec<_>>(),
+                vec![r#"Unknown field "secret" on type "Query"."#.to_string()]
+            );
+        }
+    }
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.hits(), 2);
+}
@@ -0,0 +1,679 @@
+#![cfg(feature = "dynamic_schema")]
+
+use async_graphql::dynamic::{
+    Enum, EnumItem, Field, FieldFuture, FieldValue, InputObject, InputValue, Interface, Object,
+    Scalar, Schema, SchemaError, Union,
+};
+use async_graphql::*;
+
+const SDL: &str = r#"
+    interface Node {
+        id: ID!
+    }
+
+    type User implements Node {
+        id: ID!
+        name: String!
+        friends: [User!]!
+    }
+
+    type Post implements Node {
+        id: ID!
+        title: String!
+    }
+
+    union SearchResult = User | Post
+
+    enum Order {
+        ASC
+        DESC
+    }
+
+    input Filter {
+        keyword: String!
+        limit: Int = 10
+        order: Order = ASC
+    }
+
+    type Query {
+        hello(name: String = "world"): String!
+        user: User
+        search(filter: Filter!): [SearchResult!]!
+        node: Node
+    }
+
+    type Mutation {
+        append(value: String!): [String!]!
+    }
+"#;
+
+fn user() -> Value {
+    value!({
+        "id": "1",
+        "name": "Alice",
+        "friends": [
+            { "id": "2", "name": "Bob", "friends": [] },
+        ],
+    })
+}
+
+#[async_std::test]
+pub async fn test_dynamic_query() {
+    let schema = Schema::build_from_sdl(SDL)
+        .resolver("Query.hello", |ctx| {
+            Ok(Value::String(format!(
+                "hello {}",
+                ctx.arg::<String>("name")?
+            )))
+        })
+        .async_resolver("Query.user", |_| {
+            FieldFuture::new(async move { Ok(user()) })
+        })
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(r#"{ a: hello hello(name: "rust") user { name friends { id name } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "a": "hello world",
+            "hello": "hello rust",
+            "user": {
+                "name": "Alice",
+                "friends": [{ "id": "2", "name": "Bob" }],
+            },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ user { age } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Unknown field "age" on type "User"."#.to_owned(),
+            locations: vec![Pos {
+                line: 1,
+                column: 10
+            }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_abstract_types() {
+    let schema = Schema::build_from_sdl(SDL)
+        .resolver("Query.search", |ctx| {
+            assert_eq!(
+                ctx.args["filter"],
+                value!({ "keyword": "a", "limit": 10, "order": "DESC" })
+            );
+            assert!(matches!(
+                &ctx.args["filter"],
+                Value::Object(filter) if matches!(filter["order"], Value::Enum(_))
+            ));
+            Ok(value!([
+                { "__typename": "User", "id": "1", "name": "Alice", "friends": [] },
+                { "__typename": "Post", "id": "2", "title": "Hello" },
+            ]))
+        })
+        .resolver("Query.node", |_| Ok(value!({ "id": "1" })))
+        .finish()
+        .unwrap();
+
+    let query = r#"
+        query($order: Order) {
+            search(filter: { keyword: "a", order: $order }) {
+                __typename
+                ... on Node { id }
+                ... on User { name }
+                ... on Post { title }
+            }
+        }
+    "#;
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(query)
+                    .variables(Variables::from_json(serde_json::json!({ "order": "DESC" })))
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "search": [
+                { "__typename": "User", "id": "1", "name": "Alice" },
+                { "__typename": "Post", "id": "2", "title": "Hello" },
+            ]
+        })
+    );
+
+    let errors = schema
+        .execute("{ node { id } }")
+        .await
+        .into_result()
+        .unwrap_err();
+    assert_eq!(
+        errors[0].message,
+        r#"The value of the abstract type "Node" must have a "__typename" naming one of its possible types."#
+    );
+    assert_eq!(errors[0].path, vec![PathSegment::Field("node".to_owned())]);
+}
+
+#[async_std::test]
+pub async fn test_dynamic_mutation() {
+    struct Log(async_std::sync::Mutex<Vec<String>>);
+
+    let schema = Schema::build_from_sdl(SDL)
+        .async_resolver("Mutation.append", |ctx| {
+            FieldFuture::new(async move {
+                let value = ctx.arg::<String>("value")?;
+                let mut log = ctx.ctx.data_unchecked::<Log>().0.lock().await;
+                log.push(value);
+                Ok(Value::List(
+                    log.iter().cloned().map(Value::String).collect(),
+                ))
+            })
+        })
+        .data(Log(Default::default()))
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(r#"mutation { a: append(value: "a") b: append(value: "b") }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "a": ["a"], "b": ["a", "b"] })
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_introspection() {
+    let schema = Schema::build_from_sdl(SDL).finish().unwrap();
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    __schema { mutationType { name } }
+                    __type(name: "Filter") { inputFields { name defaultValue } }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__schema": { "mutationType": { "name": "Mutation" } },
+            "__type": {
+                "inputFields": [
+                    { "name": "keyword", "defaultValue": null },
+                    { "name": "limit", "defaultValue": "10" },
+                    { "name": "order", "defaultValue": "ASC" },
+                ]
+            }
+        })
+    );
+
+    let schema = Schema::build_from_sdl(SDL)
+        .disable_introspection()
+        .finish()
+        .unwrap();
+    assert!(schema
+        .execute("{ __schema { mutationType { name } } }")
+        .await
+        .is_err());
+}
+
+#[async_std::test]
+pub async fn test_dynamic_schema_errors() {
+    assert_eq!(
+        Schema::build_from_sdl("type Query { a: A }").finish().err(),
+        Some(SchemaError(
+            r#"Unknown type "A" referenced by "Query.a"."#.to_owned()
+        ))
+    );
+    assert_eq!(
+        Schema::build_from_sdl("type Query { a(input: Query): Int }")
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"The type of "Query.a(input:)" must be an input type, "Query" is not."#.to_owned()
+        ))
+    );
+    assert_eq!(
+        Schema::build_from_sdl("type A { a: Int } type B { a: Int }")
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"The root type "Query" must be a defined object type."#.to_owned()
+        ))
+    );
+    assert_eq!(
+        Schema::build_from_sdl(SDL)
+            .resolver("Query.unknown", |_| Ok(Value::Null))
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"Cannot set the resolver of "Query.unknown", which is not a field of an object type."#
+                .to_owned()
+        ))
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_types() {
+    let schema = Schema::build("Query", None, None)
+        .register(Interface::new("Node").field(Field::new("id", "ID!")))
+        .register(
+            Object::new("Table")
+                .description("A table of the database")
+                .implement("Node")
+                .field(Field::new("id", "ID!"))
+                .field(Field::new("name", "String!"))
+                .field(
+                    Field::new("rows", "[JSON!]!")
+                        .argument(InputValue::new("page", "Page!"))
+                        .resolver(|ctx| {
+                            let page = match &ctx.args["page"] {
+                                Value::Object(page) => page.clone(),
+                                _ => unreachable!(),
+                            };
+                            Ok(value!([{ "page": page["index"].clone(), "size": page["size"].clone() }]))
+                        }),
+                ),
+        )
+        .register(Union::new("Item").possible_type("Table"))
+        .register(
+            Enum::new("Kind")
+                .item("TABLE")
+                .item(EnumItem::new("VIEW").deprecation("Views are tables")),
+        )
+        .register(
+            InputObject::new("Page")
+                .field(InputValue::new("index", "Int!"))
+                .field(InputValue::new("size", "Int").default_value(20)),
+        )
+        .register(Scalar::new("JSON"))
+        .register(
+            Object::new("Query")
+                .field(
+                    Field::new("tables", "[Table!]!")
+                        .argument(InputValue::new("kind", "Kind").default_value(Value::Enum(Name::new("TABLE"))))
+                        .resolver(|ctx| {
+                            assert_eq!(ctx.args["kind"], Value::Enum(Name::new("TABLE")));
+                            Ok(value!([{ "id": "1", "name": "users" }]))
+                        }),
+                )
+                .field(Field::new("items", "[Item!]!").async_resolver(|_| {
+                    FieldFuture::new(async move {
+                        Ok(value!([{ "__typename": "Table", "id": "2", "name": "posts" }]))
+                    })
+                })),
+        )
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    tables { id name rows(page: { index: 2 }) }
+                    items { ... on Node { id } ... on Table { name } }
+                    __type(name: "Kind") { enumValues(includeDeprecated: true) { name isDeprecated } }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "tables": [{ "id": "1", "name": "users", "rows": [{ "page": 2, "size": 20 }] }],
+            "items": [{ "id": "2", "name": "posts" }],
+            "__type": {
+                "enumValues": [
+                    { "name": "TABLE", "isDeprecated": false },
+                    { "name": "VIEW", "isDeprecated": true },
+                ]
+            },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_static_types() {
+    #[derive(SimpleObject)]
+    struct Account {
+        id: i32,
+        kind: AccountKind,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum AccountKind {
+        Personal,
+        Business,
+    }
+
+    #[derive(InputObject)]
+    struct AccountFilter {
+        kind: AccountKind,
+        #[graphql(default = 1)]
+        min_id: i32,
+    }
+
+    let schema = Schema::build_from_sdl(
+        r#"
+        type Query {
+            account(filter: AccountFilter!): Account
+        }
+        "#,
+    )
+    .register_type::<Account>()
+    .register_type::<AccountFilter>()
+    .register(
+        Object::new("Stats").field(Field::new("accounts", "Int!").resolver(|_| Ok(Value::from(2)))),
+    )
+    .resolver("Query.account", |ctx| {
+        let filter = ctx.arg::<AccountFilter>("filter")?;
+        Ok(FieldValue::output(Account {
+            id: filter.min_id,
+            kind: filter.kind,
+        }))
+    })
+    .finish()
+    .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(r#"{ account(filter: { kind: BUSINESS }) { id kind } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "account": { "id": 1, "kind": "BUSINESS" } })
+    );
+    assert!(schema.sdl().contains("type Stats"));
+}
+
+#[async_std::test]
+pub async fn test_dynamic_type_errors() {
+    let query = || Object::new("Query").field(Field::new("a", "Int"));
+
+    assert_eq!(
+        Schema::build("Query", None, None)
+            .register(query())
+            .register(Object::new("Query"))
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"Type "Query" is defined more than once."#.to_owned()
+        ))
+    );
+    assert_eq!(
+        Schema::build("Query", None, None)
+            .register(query().field(Field::new("b", "[Int")))
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"Invalid type "[Int" of "Query.b"."#.to_owned()
+        ))
+    );
+    assert_eq!(
+        Schema::build("Query", None, None)
+            .register(query())
+            .register(
+                Interface::new("Node").field(Field::new("id", "ID!").resolver(|_| Ok(Value::Null)))
+            )
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"Cannot set the resolver of "Node.id", which is not a field of an object type."#
+                .to_owned()
+        ))
+    );
+    assert_eq!(
+        Schema::build("Query", Some("Mutation"), None)
+            .register(query())
+            .finish()
+            .err(),
+        Some(SchemaError(
+            r#"The root type "Mutation" must be a defined object type."#.to_owned()
+        ))
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_oneof() {
+    let schema = Schema::build_from_sdl(
+        r#"
+        input UserBy @oneOf {
+            id: ID
+            email: String
+        }
+
+        type Query {
+            user(by: UserBy!): String
+        }
+        "#,
+    )
+    .register(
+        InputObject::new("PostBy")
+            .oneof()
+            .field(InputValue::new("id", "ID")),
+    )
+    .resolver("Query.user", |ctx| match &ctx.args["by"] {
+        Value::Object(by) => Ok(Value::from(by.keys().next().unwrap().to_string())),
+        _ => unreachable!(),
+    })
+    .finish()
+    .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(r#"{ user(by: { email: "a@b.c" }) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "user": "email" })
+    );
+    assert!(schema
+        .execute(r#"{ user(by: { id: "1", email: "a@b.c" }) }"#)
+        .await
+        .is_err());
+    assert!(schema
+        .sdl_with_options(SDLExportOptions::new().directive_definitions())
+        .contains("input PostBy @oneOf {"));
+    assert!(!schema.sdl().contains("@oneOf"));
+}
+
+#[async_std::test]
+pub async fn test_dynamic_interface_implements_interface() {
+    let schema = Schema::build_from_sdl(
+        r#"
+        interface Node {
+            id: ID!
+        }
+
+        interface Named implements Node {
+            id: ID!
+            name: String!
+        }
+
+        type User implements Named {
+            id: ID!
+            name: String!
+        }
+
+        type Query {
+            node: Node
+        }
+        "#,
+    )
+    .register(
+        Interface::new("Titled")
+            .implement("Node")
+            .field(Field::new("id", "ID!"))
+            .field(Field::new("title", "String!")),
+    )
+    .resolver("Query.node", |_| {
+        Ok(value!({ "__typename": "User", "id": "1", "name": "Alice" }))
+    })
+    .finish()
+    .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    node { ... on Named { name } ... on Titled { title } }
+                    __type(name: "Node") { possibleTypes { name } }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "node": { "name": "Alice" },
+            "__type": { "possibleTypes": [{ "name": "User" }] },
+        })
+    );
+
+    assert_eq!(
+        Schema::build_from_sdl(
+            "interface A implements B { a: Int } interface B implements A { a: Int } type Query { a: A }"
+        )
+        .finish()
+        .err()
+        .map(|err| err.0.ends_with("cannot implement itself.")),
+        Some(true)
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_specified_by_and_repeatable() {
+    let sdl = r#"
+        scalar Date @specifiedBy(url: "https://datatracker.ietf.org/doc/html/rfc3339")
+
+        directive @tag(name: String!) repeatable on FIELD
+
+        type Query {
+            today: Date
+        }
+    "#;
+    let schema = Schema::build_from_sdl(sdl).finish().unwrap();
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "Date") { specifiedByURL } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": { "specifiedByURL": "https://datatracker.ietf.org/doc/html/rfc3339" },
+        })
+    );
+
+    let resp = schema
+        .execute("{ __schema { directives { name isRepeatable } } }")
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap();
+    let mut directives = resp["__schema"]["directives"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|directive| {
+            (
+                directive["name"].as_str().unwrap().to_string(),
+                directive["isRepeatable"].as_bool().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    directives.sort();
+    assert_eq!(
+        directives,
+        vec![
+            ("ifdef".to_string(), false),
+            ("include".to_string(), false),
+            ("skip".to_string(), false),
+            ("tag".to_string(), true),
+        ]
+    );
+
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().directive_definitions());
+    assert!(sdl.contains(
+        r#"scalar Date @specifiedBy(url: "https://datatracker.ietf.org/doc/html/rfc3339")"#
+    ));
+    assert!(sdl.contains("directive @tag(name: String!) repeatable on FIELD"));
+    assert!(!schema.sdl().contains("directive @tag"));
+    assert!(!schema.sdl().contains("@specifiedBy"));
+}
+
+#[async_std::test]
+pub async fn test_dynamic_visible() {
+    struct Role(&'static str);
+
+    let allowed_roles = ["admin"];
+    let schema = Schema::build("Query", None, None)
+        .register(
+            Object::new("Query")
+                .field(Field::new("value", "Int!").resolver(|_| Ok(value!(10))))
+                .field(
+                    Field::new("secret", "Int!")
+                        .visible(move |ctx| {
+                            ctx.data::<Role>()
+                                .map(|role| allowed_roles.contains(&role.0))
+                                .unwrap_or_default()
+                        })
+                        .resolver(|_| Ok(value!(20))),
+                ),
+        )
+        .validate_visibility()
+        .finish()
+        .unwrap();
+
+    let query = "{ __type(name: \"Query\") { fields { name } } }";
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "__type": { "fields": [{ "name": "value" }] },
+        })
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value secret }").data(Role("admin")))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10, "secret": 20 })
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value secret }").data(Role("user")))
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Unknown field "secret" on type "Query"."#.to_string(),
+            locations: vec![Pos { line: 1, column: 9 }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+}
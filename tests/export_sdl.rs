@@ -0,0 +1,172 @@
+use async_graphql::*;
+
+struct Audience(&'static str);
+
+fn is_internal(ctx: &Context<'_>) -> bool {
+    matches!(ctx.data::<Audience>(), Ok(Audience("internal")))
+}
+
+fn is_partner(ctx: &Context<'_>) -> bool {
+    matches!(
+        ctx.data::<Audience>(),
+        Ok(Audience("internal")) | Ok(Audience("partner"))
+    )
+}
+
+/// A product
+#[derive(SimpleObject)]
+struct Product {
+    name: String,
+    #[graphql(visible = "is_partner")]
+    price: i32,
+    #[graphql(visible = "is_internal")]
+    cost: i32,
+    /// The identifier of the product
+    id: ID,
+}
+
+#[derive(SimpleObject)]
+#[graphql(visible = "is_internal")]
+struct Supplier {
+    name: String,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Sort {
+    Name,
+    #[graphql(visible = "is_partner")]
+    Price,
+}
+
+struct Query;
+
+#[Object]
+#[allow(unused_variables)]
+impl Query {
+    async fn products(
+        &self,
+        sort: Option<Sort>,
+        #[graphql(visible = "is_internal")] with_discontinued: Option<bool>,
+    ) -> Vec<Product> {
+        todo!()
+    }
+
+    #[graphql(visible = "is_internal")]
+    async fn suppliers(&self) -> Vec<Supplier> {
+        todo!()
+    }
+}
+
+#[async_std::test]
+pub async fn test_export_sdl_visibility() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains("suppliers: [Supplier!]!"));
+    assert!(sdl.contains("cost: Int!"));
+
+    assert_eq!(
+        schema.sdl_with_options(SDLExportOptions::new().visible_only()),
+        r#"type Query {
+	products(sort: Sort): [Product!]!
+}
+enum Sort {
+	NAME
+}
+"""
+A product
+"""
+type Product {
+	name: String!
+	"""
+	The identifier of the product
+	"""
+	id: ID!
+}
+schema {
+	query: Query
+}
+"#
+    );
+
+    assert_eq!(
+        schema.sdl_with_options(SDLExportOptions::new().data(Audience("partner"))),
+        r#"type Query {
+	products(sort: Sort): [Product!]!
+}
+enum Sort {
+	NAME
+	PRICE
+}
+"""
+A product
+"""
+type Product {
+	name: String!
+	price: Int!
+	"""
+	The identifier of the product
+	"""
+	id: ID!
+}
+schema {
+	query: Query
+}
+"#
+    );
+
+    assert_eq!(
+        schema.sdl_with_options(SDLExportOptions::new().data(Audience("internal"))),
+        schema.sdl()
+    );
+}
+
+#[async_std::test]
+pub async fn test_export_sdl_sorted() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema.sdl_with_options(
+            SDLExportOptions::new()
+                .sorted_types()
+                .sorted_fields()
+                .data(Audience("partner"))
+        ),
+        r#""""
+A product
+"""
+type Product {
+	"""
+	The identifier of the product
+	"""
+	id: ID!
+	name: String!
+	price: Int!
+}
+type Query {
+	products(sort: Sort): [Product!]!
+}
+enum Sort {
+	NAME
+	PRICE
+}
+schema {
+	query: Query
+}
+"#
+    );
+}
+
+#[async_std::test]
+pub async fn test_export_sdl_federation_descriptions() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert!(!schema.federation_sdl().contains("A product"));
+    assert!(schema
+        .sdl_with_options(
+            SDLExportOptions::new()
+                .federation()
+                .federation_descriptions()
+        )
+        .contains("\"\"\"\nA product\n\"\"\"\ntype Product {"));
+}
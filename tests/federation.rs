@@ -87,3 +87,308 @@ pub async fn test_federation() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_federation_2_sdl() {
+    #[derive(SimpleObject)]
+    #[graphql(shareable, tag = "public")]
+    struct Position {
+        x: i32,
+        #[graphql(inaccessible)]
+        y: i32,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(unresolvable = "id", unresolvable = "email")]
+    struct Account {
+        id: ID,
+        email: String,
+    }
+
+    /// An entity this subgraph resolves by `sku` only.
+    #[derive(SimpleObject)]
+    #[graphql(unresolvable = "upc", tag = "team \"a\"")]
+    struct Product {
+        sku: String,
+        upc: String,
+    }
+
+    struct Shop {
+        id: ID,
+        region: String,
+    }
+
+    #[Object]
+    impl Shop {
+        async fn id(&self) -> &ID {
+            &self.id
+        }
+
+        async fn region(&self) -> &str {
+            &self.region
+        }
+
+        #[graphql(override_from = "inventory", tag = "internal", tag = "beta")]
+        async fn stock(&self) -> i32 {
+            10
+        }
+
+        async fn position(&self) -> Position {
+            Position { x: 1, y: 2 }
+        }
+
+        async fn owner(&self) -> Account {
+            Account {
+                id: self.id.clone(),
+                email: String::new(),
+            }
+        }
+
+        async fn product(&self) -> Product {
+            Product {
+                sku: String::new(),
+                upc: String::new(),
+            }
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_shop_by_id(&self, id: ID) -> Shop {
+            Shop {
+                id,
+                region: "EU".to_string(),
+            }
+        }
+
+        #[graphql(entity)]
+        async fn find_shop_by_id_and_region(
+            &self,
+            #[graphql(key)] id: ID,
+            #[graphql(key)] region: String,
+        ) -> Shop {
+            Shop { id, region }
+        }
+
+        #[graphql(entity)]
+        async fn find_product_by_sku(&self, sku: String) -> Product {
+            Product {
+                sku,
+                upc: String::new(),
+            }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_federation_2()
+        .finish();
+    let sdl = schema.federation_sdl();
+
+    assert!(sdl.starts_with(
+        "extend schema @link(url: \"https://specs.apollo.dev/federation/v2.3\", import: [\"@key\", \"@external\", \"@requires\", \"@provides\", \"@shareable\", \"@inaccessible\", \"@override\", \"@tag\", \"@interfaceObject\"])\n"
+    ));
+    assert!(sdl.contains("type Shop @key(fields: \"id\") @key(fields: \"id region\") {"));
+    assert!(sdl.contains(
+        "\tstock: Int! @override(from: \"inventory\") @tag(name: \"internal\") @tag(name: \"beta\")\n"
+    ));
+    assert!(sdl.contains("type Position @shareable @tag(name: \"public\") {"));
+    assert!(sdl.contains("\ty: Int! @inaccessible\n"));
+    assert!(sdl.contains(
+        "type Account @key(fields: \"id\", resolvable: false) @key(fields: \"email\", resolvable: false) {"
+    ));
+    assert!(sdl.contains(
+        "type Product @key(fields: \"upc\", resolvable: false) @key(fields: \"sku\") @tag(name: \"team \\\"a\\\"\") {"
+    ));
+
+    // Federation 1 SDL does not contain any of the Federation 2 directives.
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.federation_sdl();
+    assert!(!sdl.contains("@link"));
+    assert!(!sdl.contains("@shareable"));
+    assert!(sdl.contains("type Account {"));
+    assert!(sdl.contains("type Product @key(fields: \"sku\") {"));
+}
+
+#[async_std::test]
+pub async fn test_federation_sdl_entity_interface() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+        name: String,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&ID"))]
+    enum Node {
+        User(User),
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        implements = "Node",
+        tag = "public",
+        field(name = "id", type = "&ID"),
+        field(name = "name", type = "&String")
+    )]
+    enum Named {
+        User(User),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_named_by_id(&self, id: ID) -> Named {
+            User {
+                id,
+                name: "Alice".to_string(),
+            }
+            .into()
+        }
+
+        async fn node(&self) -> Node {
+            User {
+                id: "1".into(),
+                name: "Alice".to_string(),
+            }
+            .into()
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_federation_2()
+        .finish();
+    assert!(schema
+        .federation_sdl()
+        .contains("interface Named implements Node @key(fields: \"id\") @tag(name: \"public\") {"));
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema
+        .federation_sdl()
+        .contains("interface Named implements Node @key(fields: \"id\") {"));
+}
+
+#[async_std::test]
+pub async fn test_batched_entities() {
+    use std::sync::{Arc, Mutex};
+
+    #[derive(SimpleObject)]
+    struct Item {
+        upc: String,
+        price: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct Seller {
+        id: ID,
+    }
+
+    struct Query {
+        batches: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    #[Object]
+    impl Query {
+        #[graphql(entity, batch)]
+        async fn find_items_by_upc(
+            &self,
+            #[graphql(name = "upc")] upcs: Vec<String>,
+        ) -> Vec<Result<Option<Item>>> {
+            self.batches.lock().unwrap().push(upcs.clone());
+            upcs.into_iter()
+                .map(|upc| match upc.as_str() {
+                    "missing" => Ok(None),
+                    "error" => Err("Failed to load the item.".into()),
+                    _ => Ok(Some(Item {
+                        price: upc.len() as i32,
+                        upc,
+                    })),
+                })
+                .collect()
+        }
+
+        #[graphql(entity)]
+        async fn find_seller_by_id(&self, id: ID) -> Seller {
+            Seller { id }
+        }
+    }
+
+    let batches = Arc::new(Mutex::new(Vec::new()));
+    let schema = Schema::new(
+        Query {
+            batches: batches.clone(),
+        },
+        EmptyMutation,
+        EmptySubscription,
+    );
+    let query = r#"{
+            _entities(representations: [
+                {__typename: "Item", upc: "a"},
+                {__typename: "Seller", id: "1"},
+                {__typename: "Item", upc: "bb"},
+                {__typename: "Item", upc: "ccc"},
+            ]) {
+                __typename
+                ... on Item { upc price }
+                ... on Seller { id }
+            }
+        }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "_entities": [
+                {"__typename": "Item", "upc": "a", "price": 1},
+                {"__typename": "Seller", "id": "1"},
+                {"__typename": "Item", "upc": "bb", "price": 2},
+                {"__typename": "Item", "upc": "ccc", "price": 3},
+            ]
+        })
+    );
+    assert_eq!(
+        *batches.lock().unwrap(),
+        vec![vec!["a".to_string(), "bb".to_string(), "ccc".to_string()]]
+    );
+
+    let query = r#"{
+            _entities(representations: [
+                {__typename: "Item", upc: "a"},
+                {__typename: "Item", upc: "missing"},
+                {__typename: "Item", upc: "error"},
+            ]) {
+                __typename
+            }
+        }"#;
+    let resp = schema.execute(query).await;
+    assert_eq!(
+        resp.data,
+        value!({
+            "_entities": [
+                {"__typename": "Item"},
+                null,
+                null,
+            ]
+        })
+    );
+    let errors = resp.errors;
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].message, "Entity not found.");
+    assert_eq!(
+        errors[0].path,
+        vec![
+            PathSegment::Field("_entities".to_string()),
+            PathSegment::Index(1)
+        ]
+    );
+    assert_eq!(errors[1].message, "Failed to load the item.");
+    assert_eq!(
+        errors[1].path,
+        vec![
+            PathSegment::Field("_entities".to_string()),
+            PathSegment::Index(2)
+        ]
+    );
+}
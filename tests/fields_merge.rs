@@ -118,3 +118,39 @@ pub async fn test_field_object_merge2() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_field_order() {
+    #[derive(SimpleObject)]
+    struct MyObject {
+        a: i32,
+        b: i32,
+        c: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> MyObject {
+            MyObject { a: 1, b: 2, c: 3 }
+        }
+
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"
+        {
+            value
+            obj { c a }
+            ... { obj { b c } }
+        }
+    "#;
+    assert_eq!(
+        serde_json::to_string(&schema.execute(query).await.data).unwrap(),
+        r#"{"value":10,"obj":{"c":3,"a":1,"b":2}}"#
+    );
+}
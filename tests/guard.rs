@@ -486,3 +486,347 @@ pub async fn test_guard_race_operator() {
         }]
     );
 }
+
+#[async_std::test]
+pub async fn test_guard_or_operator_short_circuit() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct Checks(Arc<AtomicUsize>);
+
+    struct CountGuard;
+
+    #[async_trait::async_trait]
+    impl Guard for CountGuard {
+        async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+            ctx.data_unchecked::<Checks>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            Err("Counted".into())
+        }
+    }
+
+    #[derive(SimpleObject)]
+    struct Query {
+        #[graphql(guard(or(RoleGuard(role = "Role::Admin"), CountGuard())))]
+        value: i32,
+        #[graphql(guard(race(CountGuard(), RoleGuard(role = "Role::Admin"), CountGuard())))]
+        value2: i32,
+    }
+
+    let checks = Arc::new(AtomicUsize::new(0));
+    let schema = Schema::build(
+        Query {
+            value: 10,
+            value2: 20,
+        },
+        EmptyMutation,
+        EmptySubscription,
+    )
+    .data(Checks(checks.clone()))
+    .finish();
+
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value value2 }").data(Role::Admin))
+            .await
+            .data,
+        value!({"value": 10, "value2": 20})
+    );
+    assert_eq!(checks.load(Ordering::SeqCst), 1);
+
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value }").data(Role::Guest))
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Counted".to_string(),
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("value".to_owned())],
+            extensions: None,
+        }]
+    );
+}
+
+#[async_std::test]
+pub async fn test_guard_with_arguments() {
+    struct OwnerGuard {
+        owner: String,
+    }
+
+    #[async_trait::async_trait]
+    impl Guard for OwnerGuard {
+        async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+            if ctx.data_opt::<Username>().map(|name| &name.0) == Some(&self.owner) {
+                Ok(())
+            } else {
+                Err("Forbidden".into())
+            }
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(guard(OwnerGuard(owner = "@username")))]
+        async fn email(&self, username: String) -> String {
+            format!("{}@example.com", username)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let query = r#"{ email(username: "test") }"#;
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Username("test".to_string())))
+            .await
+            .data,
+        value!({"email": "test@example.com"})
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(Username("test1".to_string())))
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Forbidden".to_string(),
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("email".to_owned())],
+            extensions: None,
+        }]
+    );
+}
+
+#[async_std::test]
+pub async fn test_guard_on_type() {
+    #[derive(SimpleObject)]
+    #[graphql(guard(RoleGuard(role = "Role::Admin")))]
+    struct Account {
+        id: i32,
+        #[graphql(guard(UserGuard(username = r#""test""#)))]
+        email: String,
+    }
+
+    struct Secret;
+
+    #[Object(guard(RoleGuard(role = "Role::Admin")))]
+    impl Secret {
+        async fn id(&self) -> i32 {
+            1
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn account(&self) -> Account {
+            Account {
+                id: 1,
+                email: "test@example.com".to_string(),
+            }
+        }
+
+        async fn secret(&self) -> Secret {
+            Secret
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(
+                Request::new("{ account { id email } secret { id } }")
+                    .data(Role::Admin)
+                    .data(Username("test".to_string()))
+            )
+            .await
+            .data,
+        value!({
+            "account": { "id": 1, "email": "test@example.com" },
+            "secret": { "id": 1 },
+        })
+    );
+
+    for (query, path) in &[
+        ("{ account { id } }", vec!["account", "id"]),
+        ("{ secret { id } }", vec!["secret", "id"]),
+    ] {
+        let errors = schema
+            .execute(
+                Request::new(*query)
+                    .data(Role::Guest)
+                    .data(Username("test".to_string())),
+            )
+            .await
+            .errors;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Forbidden");
+        assert_eq!(
+            errors[0].path,
+            path.iter()
+                .map(|name| PathSegment::Field(name.to_string()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    let errors = schema
+        .execute(
+            Request::new("{ account { email } }")
+                .data(Role::Admin)
+                .data(Username("test1".to_string())),
+        )
+        .await
+        .errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "Forbidden");
+}
+
+#[async_std::test]
+pub async fn test_guard_on_interface() {
+    struct Secret;
+
+    #[Object]
+    impl Secret {
+        async fn id(&self) -> i32 {
+            1
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        guard(RoleGuard(role = "Role::Admin")),
+        field(name = "id", type = "i32")
+    )]
+    enum Node {
+        Secret(Secret),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn node(&self) -> Node {
+            Secret.into()
+        }
+
+        async fn nodes(&self) -> Vec<Node> {
+            vec![Secret.into()]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(Request::new("{ node { id } nodes { id } }").data(Role::Admin))
+            .await
+            .data,
+        value!({
+            "node": { "id": 1 },
+            "nodes": [{ "id": 1 }],
+        })
+    );
+
+    for query in &[
+        "{ node { id } }",
+        "{ node { ... on Secret { id } } }",
+        "{ node { __typename } }",
+    ] {
+        let errors = schema
+            .execute(Request::new(*query).data(Role::Guest))
+            .await
+            .errors;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Forbidden");
+        assert_eq!(errors[0].path, vec![PathSegment::Field("node".to_string())]);
+    }
+
+    let errors = schema
+        .execute(Request::new("{ nodes { id } }").data(Role::Guest))
+        .await
+        .errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "Forbidden");
+}
+
+#[async_std::test]
+pub async fn test_guard_fallback() {
+    #[derive(SimpleObject)]
+    #[graphql(guard(RoleGuard(role = "Role::Admin")))]
+    struct Query {
+        #[graphql(guard_fallback)]
+        email: Option<String>,
+        #[graphql(
+            guard(UserGuard(username = r#""test""#)),
+            guard_fallback = r#""***".to_string()"#
+        )]
+        phone: String,
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        #[graphql(guard(RoleGuard(role = "Role::Admin")), guard_fallback)]
+        async fn delete(&self) -> Option<bool> {
+            Some(true)
+        }
+    }
+
+    let schema = Schema::new(
+        Query {
+            email: Some("test@example.com".to_string()),
+            phone: "0123456789".to_string(),
+        },
+        Mutation,
+        EmptySubscription,
+    );
+
+    let response = schema
+        .execute(
+            Request::new("{ email phone }")
+                .data(Role::Admin)
+                .data(Username("test".to_string())),
+        )
+        .await;
+    assert_eq!(
+        response.data,
+        value!({ "email": "test@example.com", "phone": "0123456789" })
+    );
+
+    let response = schema
+        .execute(
+            Request::new("{ email phone }")
+                .data(Role::Admin)
+                .data(Username("test1".to_string())),
+        )
+        .await;
+    assert!(response.errors.is_empty());
+    assert_eq!(
+        response.data,
+        value!({ "email": "test@example.com", "phone": "***" })
+    );
+
+    let response = schema
+        .execute(
+            Request::new("{ email phone }")
+                .data(Role::Guest)
+                .data(Username("test".to_string())),
+        )
+        .await;
+    assert!(response.errors.is_empty());
+    assert_eq!(response.data, value!({ "email": null, "phone": "***" }));
+
+    let response = schema
+        .execute(Request::new("mutation { delete }").data(Role::Guest))
+        .await;
+    assert!(response.errors.is_empty());
+    assert_eq!(response.data, value!({ "delete": null }));
+}
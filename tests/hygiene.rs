@@ -86,6 +86,13 @@ struct MyInputObject {
     bar: ::std::string::String,
 }
 
+#[derive(async_graphql::OneofObject)]
+enum MyOneofObject {
+    /// Foo.
+    Foo(::std::primitive::i32),
+    Bar(MyInputObject),
+}
+
 #[derive(async_graphql::Interface)]
 #[graphql(
     field(name = "value", type = "::std::primitive::i32"),
@@ -1,6 +1,6 @@
 use async_graphql::validators::{
     Email, IntEqual, IntGreaterThan, IntLessThan, IntNonZero, IntRange, ListMaxLength,
-    ListMinLength, StringMaxLength, StringMinLength, MAC,
+    ListMinLength, StringMaxLength, StringMinLength, UploadContentType, UploadMaxSize, MAC,
 };
 use async_graphql::*;
 
@@ -1772,3 +1772,129 @@ pub async fn test_input_validator_variable() {
         }
     }
 }
+
+#[async_std::test]
+pub async fn test_input_validator_upload() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct MutationRoot;
+
+    #[Object]
+    impl MutationRoot {
+        async fn upload(
+            &self,
+            #[graphql(validator(and(
+                UploadMaxSize(size = "5"),
+                UploadContentType(allowed = r#""image/*, text/plain""#)
+            )))]
+            _files: Vec<Upload>,
+        ) -> bool {
+            true
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, MutationRoot, EmptySubscription);
+
+    let request = |files: Vec<(&str, Option<&str>, &[u8])>| {
+        let mut request = Request::new("mutation($files: [Upload!]!) { upload(files: $files) }")
+            .variables(Variables::from_json(
+                serde_json::json!({ "files": vec![(); files.len()] }),
+            ));
+        for (idx, (filename, content_type, data)) in files.into_iter().enumerate() {
+            request.set_upload(
+                &format!("variables.files.{}", idx),
+                UploadValue {
+                    filename: filename.to_string(),
+                    content_type: content_type.map(ToString::to_string),
+                    content: UploadContent::Memory(data.into()),
+                    sha256: None,
+                },
+            );
+        }
+        request
+    };
+
+    assert_eq!(
+        schema
+            .execute(request(vec![
+                ("a.png", Some("image/png"), b"abc"),
+                ("b.txt", Some("text/plain; charset=utf-8"), b"hello"),
+            ]))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({"upload": true})
+    );
+
+    assert_eq!(
+        schema
+            .execute(request(vec![
+                ("a.png", Some("image/png"), b"abc"),
+                ("b.txt", Some("text/plain"), b"hello world"),
+            ]))
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Invalid value for argument \"files\", the file \"b.txt\" is 11 bytes, must be less than or equal to 5 bytes".to_string(),
+            locations: vec![Pos {
+                line: 1,
+                column: 39
+            }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+
+    assert_eq!(
+        schema
+            .execute(request(vec![("a.pdf", None, b"abc")]))
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Invalid value for argument \"files\", the content type of the file \"a.pdf\" is \"application/octet-stream\", must be one of \"image/*, text/plain\"".to_string(),
+            locations: vec![Pos {
+                line: 1,
+                column: 39
+            }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+
+    // The upload validators can't be skipped.
+    for schema in &[
+        Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+            .validation_mode(ValidationMode::Fast)
+            .finish(),
+        Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+            .disable_validation_rule(BuiltinRule::ArgumentsOfCorrectType)
+            .finish(),
+    ] {
+        assert_eq!(
+            schema
+                .execute(request(vec![("b.txt", Some("text/plain"), b"hello world")]))
+                .await
+                .into_result()
+                .unwrap_err(),
+            vec![ServerError {
+                message: "Invalid value for argument \"files\", the file \"b.txt\" is 11 bytes, must be less than or equal to 5 bytes".to_string(),
+                locations: vec![Pos {
+                    line: 1,
+                    column: 39
+                }],
+                path: Vec::new(),
+                extensions: None,
+            }]
+        );
+    }
+}
@@ -380,7 +380,7 @@ pub async fn test_interface_implement_other_interface() {
 
     let query = r#"
         {
-            company { id }
+            company { __typename id }
             organization { id }
         }
     "#;
@@ -389,6 +389,7 @@ pub async fn test_interface_implement_other_interface() {
         schema.execute(query).await.into_result().unwrap().data,
         value!({
             "company": {
+                "__typename": "Company",
                 "id": "88",
             },
             "organization": {
@@ -473,3 +474,98 @@ pub async fn test_issue_330() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_interface_implements_attribute() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Post {
+        id: ID,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&ID"))]
+    enum Node {
+        Post(Post),
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        implements = "Node",
+        field(name = "id", type = "&ID"),
+        field(name = "name", type = "&String")
+    )]
+    enum Named {
+        User(User),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn node(&self) -> Node {
+            Post { id: "2".into() }.into()
+        }
+
+        async fn named(&self) -> Named {
+            User {
+                id: "1".into(),
+                name: "Alice".to_string(),
+            }
+            .into()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        named { __typename ...NodeFragment ... on Named { name } }
+        node { ... on Named { name } ... on Node { id } }
+    }
+    fragment NodeFragment on Node { id }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "named": { "__typename": "User", "id": "1", "name": "Alice" },
+            "node": { "id": "2" },
+        })
+    );
+
+    let query = r#"{
+        named: __type(name: "Named") { interfaces { name } }
+        node: __type(name: "Node") { interfaces { name } possibleTypes { name } }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "named": { "interfaces": [{ "name": "Node" }] },
+            "node": {
+                "interfaces": [],
+                "possibleTypes": [{ "name": "Post" }, { "name": "User" }],
+            },
+        })
+    );
+
+    let data = schema
+        .execute(r#"{ __type(name: "User") { interfaces { name } } }"#)
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap();
+    let mut user_interfaces = data["__type"]["interfaces"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|ty| ty["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    user_interfaces.sort_unstable();
+    assert_eq!(user_interfaces, vec!["Named", "Node"]);
+
+    assert!(schema.sdl().contains("interface Named implements Node {"));
+}
@@ -1189,3 +1189,76 @@ pub async fn test_introspection_subscription() {
 //
 //     assert_eq!(res, res_json)
 // }
+
+#[async_std::test]
+pub async fn test_introspection_deprecated_input_values() {
+    #[derive(InputObject)]
+    struct MyInput {
+        a: i32,
+        #[graphql(deprecation = "Use `a`.")]
+        b: Option<i32>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(
+            &self,
+            input: MyInput,
+            #[graphql(deprecation = "Use `input`.")] c: Option<i32>,
+        ) -> i32 {
+            input.a + input.b.unwrap_or_default() + c.unwrap_or_default()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let query = r#"{
+        __type(name: "Query") {
+            fields {
+                args { name }
+                allArgs: args(includeDeprecated: true) {
+                    name
+                    isDeprecated
+                    deprecationReason
+                }
+            }
+        }
+        input: __type(name: "MyInput") {
+            inputFields { name }
+            allInputFields: inputFields(includeDeprecated: true) {
+                name
+                isDeprecated
+                deprecationReason
+            }
+        }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "__type": {
+                "fields": [{
+                    "args": [{ "name": "input" }],
+                    "allArgs": [
+                        { "name": "input", "isDeprecated": false, "deprecationReason": null },
+                        { "name": "c", "isDeprecated": true, "deprecationReason": "Use `input`." },
+                    ],
+                }],
+            },
+            "input": {
+                "inputFields": [{ "name": "a" }],
+                "allInputFields": [
+                    { "name": "a", "isDeprecated": false, "deprecationReason": null },
+                    { "name": "b", "isDeprecated": true, "deprecationReason": "Use `a`." },
+                ],
+            },
+        })
+    );
+
+    let sdl = schema.sdl();
+    assert!(
+        sdl.contains(r#"value(input: MyInput!, c: Int @deprecated(reason: "Use `input`.")): Int!"#)
+    );
+    assert!(sdl.contains(r#"b: Int @deprecated(reason: "Use `a`.")"#));
+}
@@ -0,0 +1,215 @@
+use async_graphql::introspection::{
+    introspection_to_sdl, introspection_to_service_document, IntrospectionError,
+    INTROSPECTION_QUERY,
+};
+use async_graphql::*;
+
+/// A color
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Color {
+    Red,
+    #[graphql(deprecation = "Use `RED`.")]
+    Crimson,
+}
+
+#[derive(InputObject)]
+struct Filter {
+    #[graphql(default_with = "vec![Color::Red]")]
+    colors: Vec<Color>,
+    /// The "prefix" of the name
+    prefix: Option<String>,
+}
+
+#[derive(OneofObject)]
+enum Lookup {
+    Id(ID),
+    Name(String),
+}
+
+struct Email(String);
+
+#[Scalar(specified_by_url = "https://datatracker.ietf.org/doc/html/rfc5322")]
+impl ScalarType for Email {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => Ok(Email(s)),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.clone())
+    }
+}
+
+#[derive(SimpleObject)]
+struct Car {
+    id: ID,
+    color: Color,
+}
+
+#[derive(SimpleObject)]
+struct Bike {
+    id: ID,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "id", type = "&ID"))]
+enum Node {
+    Car(Car),
+    Bike(Bike),
+}
+
+#[derive(Union)]
+enum Vehicle {
+    Car(Car),
+    Bike(Bike),
+}
+
+struct Query;
+
+#[Object]
+#[allow(unused_variables)]
+impl Query {
+    /// The vehicles
+    async fn vehicles(
+        &self,
+        filter: Option<Filter>,
+        #[graphql(default = 10)] first: i32,
+    ) -> Vec<Vehicle> {
+        Vec::new()
+    }
+
+    async fn lookup(&self, lookup: Lookup) -> Option<Node> {
+        None
+    }
+
+    #[graphql(deprecation = "Use `vehicles`.")]
+    async fn cars(&self) -> Vec<Car> {
+        Vec::new()
+    }
+
+    async fn email(&self) -> Option<Email> {
+        None
+    }
+}
+
+struct Mutation;
+
+#[Object]
+#[allow(unused_variables)]
+impl Mutation {
+    async fn paint(&self, id: ID, color: Color) -> bool {
+        true
+    }
+}
+
+async fn introspect(schema: &Schema<Query, Mutation, EmptySubscription>) -> String {
+    let response = schema.execute(INTROSPECTION_QUERY).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    serde_json::to_string(&response).unwrap()
+}
+
+#[async_std::test]
+pub async fn test_introspection_to_sdl() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let sdl = introspection_to_sdl(&introspect(&schema).await).unwrap();
+
+    assert!(schema.diff_from_sdl(&sdl).unwrap().is_empty());
+    for expected in &[
+        "\"\"\"\nA color\n\"\"\"\nenum Color {\n\tRED\n\tCRIMSON @deprecated(reason: \"Use `RED`.\")\n}\n",
+        "input Filter {\n\tcolors: [Color!]! = [RED,]\n\t\"\"\"\n\tThe \"prefix\" of the name\n\t\"\"\"\n\tprefix: String\n}\n",
+        "input Lookup @oneOf {\n",
+        "type Car implements Node {\n",
+        "union Vehicle = Car | Bike\n",
+        "\tvehicles(filter: Filter, first: Int! = 10): [Vehicle!]!\n",
+        "\tcars: [Car!]! @deprecated(reason: \"Use `vehicles`.\")\n",
+        "scalar Email @specifiedBy(url: \"https://datatracker.ietf.org/doc/html/rfc5322\")\n",
+        "schema {\n\tquery: Query\n\tmutation: Mutation\n}\n",
+    ] {
+        assert!(sdl.contains(expected), "{} not found in:\n{}", expected, sdl);
+    }
+    assert!(!sdl.contains("__Schema"));
+    assert!(!sdl.contains("scalar String"));
+    assert!(!sdl.contains("directive @skip"));
+
+    let document = introspection_to_service_document(&introspect(&schema).await).unwrap();
+    assert!(
+        diff::diff_service_documents(&document, &parser::parse_schema(&sdl).unwrap()).is_empty()
+    );
+}
+
+#[cfg(feature = "dynamic_schema")]
+#[async_std::test]
+pub async fn test_introspection_to_dynamic_schema() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let sdl = introspection_to_sdl(&introspect(&schema).await).unwrap();
+
+    let dynamic_schema = dynamic::Schema::build_from_sdl(sdl.clone())
+        .finish()
+        .unwrap();
+    assert!(schema
+        .diff_from_sdl(&dynamic_schema.sdl())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+pub fn test_introspection_errors() {
+    assert!(matches!(
+        introspection_to_sdl("{"),
+        Err(IntrospectionError::InvalidJson(_))
+    ));
+    assert!(matches!(
+        introspection_to_sdl(r#"{ "data": null, "errors": [{ "message": "Forbidden" }] }"#),
+        Err(IntrospectionError::MissingSchema)
+    ));
+
+    let introspection = |default_value: &str, of_type: &str| {
+        format!(
+            r#"{{
+                "__schema": {{
+                    "queryType": {{ "name": "Query" }},
+                    "types": [{{
+                        "kind": "OBJECT",
+                        "name": "Query",
+                        "fields": [{{
+                            "name": "value",
+                            "args": [{{
+                                "name": "a",
+                                "type": {{ "kind": "SCALAR", "name": "Int" }},
+                                "defaultValue": {}
+                            }}],
+                            "type": {{ "kind": "NON_NULL", "ofType": {} }}
+                        }}]
+                    }}]
+                }}
+            }}"#,
+            default_value, of_type
+        )
+    };
+
+    assert_eq!(
+        introspection_to_sdl(&introspection(
+            "\"1\"",
+            r#"{ "kind": "SCALAR", "name": "Int" }"#
+        ))
+        .unwrap(),
+        "type Query {\n\tvalue(a: Int = 1): Int!\n}\nschema {\n\tquery: Query\n}\n"
+    );
+    assert_eq!(
+        introspection_to_sdl(&introspection(
+            "\"{\"",
+            r#"{ "kind": "SCALAR", "name": "Int" }"#
+        ))
+        .unwrap_err()
+        .to_string(),
+        r#"Invalid default value of "Query.value(a:)": {"#
+    );
+    assert_eq!(
+        introspection_to_sdl(&introspection("null", "null"))
+            .unwrap_err()
+            .to_string(),
+        r#"Invalid type of "Query.value""#
+    );
+}
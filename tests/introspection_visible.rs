@@ -252,3 +252,78 @@ pub async fn test_visible_fn() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_validate_visibility() {
+    struct IsAdmin(bool);
+
+    fn is_admin(ctx: &Context<'_>) -> bool {
+        ctx.data::<IsAdmin>()
+            .map(|is_admin| is_admin.0)
+            .unwrap_or_default()
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(visible = "is_admin")]
+    struct Secret {
+        value: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, #[graphql(visible = "is_admin")] add: Option<i32>) -> i32 {
+            10 + add.unwrap_or_default()
+        }
+
+        #[graphql(visible = "is_admin")]
+        async fn secret(&self) -> Secret {
+            Secret { value: 20 }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .validate_visibility()
+        .finish();
+    let query = r#"{ value(add: 1) secret { value } }"#;
+    assert_eq!(
+        schema
+            .execute(Request::new(query).data(IsAdmin(true)))
+            .await
+            .data,
+        value!({
+            "value": 11,
+            "secret": { "value": 20 },
+        })
+    );
+
+    let resp = schema
+        .execute(Request::new(query).data(IsAdmin(false)))
+        .await;
+    assert!(resp.is_err());
+    assert_eq!(
+        resp.errors
+            .into_iter()
+            .map(|err| err.message)
+            .collect::<Vec<_>>(),
+        vec![
+            r#"Unknown argument "add" on field "value" of type "Query"."#.to_string(),
+            r#"Unknown field "secret" on type "Query"."#.to_string(),
+        ]
+    );
+
+    let resp = schema
+        .execute(Request::new("{ secret { ... on Secret { value } } }").data(IsAdmin(false)))
+        .await;
+    assert_eq!(
+        resp.errors
+            .into_iter()
+            .map(|err| err.message)
+            .collect::<Vec<_>>(),
+        vec![
+            r#"Unknown field "secret" on type "Query"."#.to_string(),
+            r#"Unknown type "Secret""#.to_string(),
+        ]
+    );
+}
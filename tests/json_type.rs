@@ -53,3 +53,26 @@ pub async fn test_json_scalar() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_json_object_sorted_keys() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> HashMap<String, i32> {
+            (0..20).map(|n| (format!("k{:02}", n), n)).collect()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let data = schema.execute("{ obj }").await.into_result().unwrap().data;
+    let expected = (0..20)
+        .map(|n| format!(r#""k{:02}":{}"#, n, n))
+        .collect::<Vec<_>>()
+        .join(",");
+    assert_eq!(
+        serde_json::to_string(&data).unwrap(),
+        format!(r#"{{"obj":{{{}}}}}"#, expected)
+    );
+}
@@ -0,0 +1,158 @@
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+async fn check_limit<F>(build: F, ok_query: &str, err_query: &str, message: &str)
+where
+    F: Fn(
+        SchemaBuilder<Query, EmptyMutation, EmptySubscription>,
+    ) -> SchemaBuilder<Query, EmptyMutation, EmptySubscription>,
+{
+    let schema = build(Schema::build(Query, EmptyMutation, EmptySubscription)).finish();
+    assert!(schema.execute(ok_query).await.is_ok());
+    assert_eq!(
+        schema.execute(err_query).await.into_result().unwrap_err(),
+        vec![ServerError::new(message)]
+    );
+}
+
+#[async_std::test]
+pub async fn test_limit_aliases() {
+    check_limit(
+        |builder| builder.limit_aliases(2),
+        "{ a: value b: value }",
+        "{ a: value b: value c: value }",
+        "Query has too many aliases.",
+    )
+    .await;
+}
+
+#[async_std::test]
+pub async fn test_limit_aliases_in_fragments() {
+    check_limit(
+        |builder| builder.limit_aliases(4),
+        "fragment F on Query { a: value b: value } { ...F ...F }",
+        "fragment F on Query { a: value b: value } { ...F ...F ...F }",
+        "Query has too many aliases.",
+    )
+    .await;
+}
+
+#[async_std::test]
+pub async fn test_limit_root_fields() {
+    check_limit(
+        |builder| builder.limit_root_fields(2),
+        "{ value a: value }",
+        "{ value a: value ... on Query { b: value } }",
+        "Query has too many root fields.",
+    )
+    .await;
+}
+
+#[async_std::test]
+pub async fn test_limit_directives() {
+    check_limit(
+        |builder| builder.limit_directives(2),
+        "{ value @skip(if: false) @skip(if: false) @include(if: true) }",
+        "{ value @skip(if: false) @skip(if: false) @skip(if: false) }",
+        "Query repeats a directive too many times on a single location.",
+    )
+    .await;
+}
+
+#[async_std::test]
+pub async fn test_limit_directives_on_variable_definitions() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_directives(2)
+        .finish();
+    assert_eq!(
+        schema
+            .execute("query($a: Int @a @a @a) { value }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError::new(
+            "Query repeats a directive too many times on a single location."
+        )]
+    );
+}
+
+#[async_std::test]
+pub async fn test_limits_checked_before_rules() {
+    // The unknown field would fail validation, but the limit is checked first.
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_aliases(2)
+        .finish();
+    assert_eq!(
+        schema
+            .execute("{ a: value b: value c: unknown }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError::new("Query has too many aliases.")]
+    );
+}
+
+#[async_std::test]
+pub async fn test_limit_selections() {
+    check_limit(
+        |builder| builder.limit_selections(3),
+        "{ value a: value b: value }",
+        "{ value a: value ... on Query { b: value } }",
+        "Query has too many selections.",
+    )
+    .await;
+}
+
+#[async_std::test]
+pub async fn test_limit_selections_in_fragments() {
+    check_limit(
+        |builder| builder.limit_selections(6),
+        "fragment F on Query { value a: value } { ...F ...F }",
+        "fragment F on Query { value a: value } { ...F ...F ...F }",
+        "Query has too many selections.",
+    )
+    .await;
+}
+
+#[async_std::test]
+pub async fn test_limit_selections_in_nested_fragments() {
+    // Every fragment spreads the next one twice, so the query has more than 2^64 selections.
+    let mut query = String::from("{ ...F0 }");
+    for i in 0..64 {
+        query += &format!(
+            " fragment F{} on Query {{ ...F{} ...F{} }}",
+            i,
+            i + 1,
+            i + 1
+        );
+    }
+    query += " fragment F64 on Query { value }";
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_selections(100)
+        .limit_root_fields(100)
+        .finish();
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        vec![ServerError::new("Query has too many root fields.")]
+    );
+}
+
+#[async_std::test]
+pub async fn test_limit_tokens() {
+    check_limit(
+        |builder| builder.limit_tokens(4),
+        "{ value }",
+        "{ value a: value }",
+        "document contains more than 4 tokens",
+    )
+    .await;
+}
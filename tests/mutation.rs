@@ -84,3 +84,62 @@ pub async fn test_mutation_fragment() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_allowed_operation_types() {
+    use async_graphql::parser::types::OperationType;
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct MutationRoot;
+
+    #[Object]
+    impl MutationRoot {
+        async fn action(&self, ctx: &Context<'_>) -> bool {
+            *ctx.data_unchecked::<Arc<Mutex<bool>>>().lock().await = true;
+            true
+        }
+    }
+
+    let executed = Arc::new(Mutex::new(false));
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(executed.clone())
+        .finish();
+
+    let request = Request::new("{ value }").allowed_operation_types(vec![OperationType::Query]);
+    assert!(request.is_operation_type_allowed(OperationType::Query));
+    assert!(!request.is_operation_type_allowed(OperationType::Mutation));
+    assert!(Request::new("{ value }").is_operation_type_allowed(OperationType::Mutation));
+
+    let resp = schema.execute(request).await;
+    assert!(!resp.is_method_not_allowed());
+    assert_eq!(resp.into_result().unwrap().data, value!({ "value": 10 }));
+
+    let resp = schema
+        .execute(
+            Request::new("query A { value } mutation B { action }")
+                .operation_name("B")
+                .allowed_operation_types(vec![OperationType::Query]),
+        )
+        .await;
+    assert!(resp.is_method_not_allowed());
+    assert_eq!(
+        resp.into_result().unwrap_err(),
+        vec![ServerError::new(
+            r#"The "mutation" operation is not allowed in this request."#
+        )]
+    );
+    assert!(!*executed.lock().await);
+
+    let resp = schema.execute("mutation { action }").await;
+    assert!(!resp.is_method_not_allowed());
+    assert_eq!(resp.into_result().unwrap().data, value!({ "action": true }));
+    assert!(*executed.lock().await);
+}
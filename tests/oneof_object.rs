@@ -0,0 +1,150 @@
+use async_graphql::*;
+
+#[derive(OneofObject, Debug, PartialEq)]
+enum UserBy {
+    Id(ID),
+    Email(String),
+    #[graphql(name = "slug")]
+    ByPath(Vec<String>),
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn user(&self, by: UserBy) -> String {
+        match by {
+            UserBy::Id(id) => format!("id: {}", *id),
+            UserBy::Email(email) => format!("email: {}", email),
+            UserBy::ByPath(path) => format!("slug: {}", path.join("/")),
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_oneof_object() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    a: user(by: { id: "1" })
+                    b: user(by: { email: "a@b.c" })
+                    c: user(by: { slug: ["a", "b"] })
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "a": "id: 1",
+            "b": "email: a@b.c",
+            "c": "slug: a/b",
+        })
+    );
+
+    assert_eq!(
+        UserBy::parse(Some(value!({ "email": "a@b.c" }))).unwrap(),
+        UserBy::Email("a@b.c".to_string())
+    );
+    assert_eq!(
+        UserBy::Email("a@b.c".to_string()).to_value(),
+        value!({ "email": "a@b.c" })
+    );
+    assert!(UserBy::parse(Some(value!({}))).is_err());
+    assert!(UserBy::parse(Some(value!({ "id": "1", "email": "a@b.c" }))).is_err());
+}
+
+#[async_std::test]
+pub async fn test_oneof_object_validation() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(r#"{ user(by: { id: "1", email: "a@b.c" }) }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Invalid value for argument "by", oneof input object "UserBy" must have exactly one field"#
+                .to_owned(),
+            locations: vec![Pos { line: 1, column: 8 }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ user(by: {}) }"#)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "by", oneof input object "UserBy" must have exactly one field"#
+    );
+
+    let query = r#"query($by: UserBy!) { user(by: $by) }"#;
+    assert_eq!(
+        schema
+            .execute(Request::new(query).variables(Variables::from_json(
+                serde_json::json!({ "by": { "id": "1", "email": "a@b.c" } })
+            )))
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "by", oneof input object "UserBy" must have exactly one field"#
+    );
+
+    let query = r#"query($email: String) { user(by: { email: $email }) }"#;
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(query)
+                    .variables(Variables::from_json(serde_json::json!({ "email": null })))
+            )
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        r#"Invalid value for argument "by", field "email" of oneof input object "UserBy" must not be null"#
+    );
+}
+
+#[async_std::test]
+pub async fn test_oneof_object_introspection() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    oneof: __type(name: "UserBy") { isOneOf inputFields { name type { kind name } } }
+                    query: __type(name: "Query") { isOneOf }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "oneof": {
+                "isOneOf": true,
+                "inputFields": [
+                    { "name": "id", "type": { "kind": "SCALAR", "name": "ID" } },
+                    { "name": "email", "type": { "kind": "SCALAR", "name": "String" } },
+                    { "name": "slug", "type": { "kind": "LIST", "name": null } },
+                ],
+            },
+            "query": { "isOneOf": null },
+        })
+    );
+
+    assert!(schema
+        .sdl_with_options(SDLExportOptions::new().directive_definitions())
+        .contains("input UserBy @oneOf {"));
+    assert!(!schema.sdl().contains("@oneOf"));
+}
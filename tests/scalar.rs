@@ -38,3 +38,56 @@ pub async fn test_scalar_macro() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_scalar_specified_by_url() {
+    struct Email(String);
+
+    #[Scalar(specified_by_url = "https://datatracker.ietf.org/doc/html/rfc5322")]
+    impl ScalarType for Email {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            match value {
+                Value::String(s) => Ok(Email(s)),
+                _ => Err(InputValueError::expected_type(value)),
+            }
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.0.clone())
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, email: Email) -> Email {
+            email
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    email: __type(name: "Email") { specifiedByURL }
+                    string: __type(name: "String") { specifiedByURL }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "email": { "specifiedByURL": "https://datatracker.ietf.org/doc/html/rfc5322" },
+            "string": { "specifiedByURL": null },
+        })
+    );
+    assert!(schema
+        .sdl_with_options(SDLExportOptions::new().directive_definitions())
+        .contains(
+            r#"scalar Email @specifiedBy(url: "https://datatracker.ietf.org/doc/html/rfc5322")"#
+        ));
+    assert!(!schema.sdl().contains("@specifiedBy"));
+}
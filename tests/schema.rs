@@ -16,3 +16,29 @@ pub async fn test_schema_default() {
 
     let _schema = MySchema::default();
 }
+
+#[async_std::test]
+pub async fn test_request_error() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> Result<i32> {
+            Err("field error".into())
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    let resp = schema.execute("{ value").await;
+    assert!(resp.is_err());
+    assert!(resp.is_request_error());
+
+    let resp = schema.execute("{ unknown }").await;
+    assert!(resp.is_err());
+    assert!(resp.is_request_error());
+
+    let resp = schema.execute("{ value }").await;
+    assert!(resp.is_err());
+    assert!(!resp.is_request_error());
+}
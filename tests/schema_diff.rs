@@ -0,0 +1,134 @@
+use async_graphql::diff::{ChangeKind, ChangeSeverity};
+use async_graphql::*;
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Status {
+    Active,
+    Archived,
+}
+
+#[derive(InputObject)]
+struct ProductFilter {
+    status: Option<Status>,
+    #[graphql(default = 10)]
+    limit: i32,
+}
+
+#[derive(SimpleObject)]
+struct Product {
+    name: String,
+    price: Option<i32>,
+    status: Status,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "name", type = "&String"))]
+enum Node {
+    Product(Product),
+}
+
+struct Query;
+
+#[Object]
+#[allow(unused_variables)]
+impl Query {
+    async fn products(&self, filter: Option<ProductFilter>, first: i32) -> Vec<Product> {
+        Vec::new()
+    }
+
+    async fn node(&self) -> Option<Node> {
+        None
+    }
+}
+
+#[test]
+pub fn test_diff_same_schema() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(schema.diff_from_sdl(&schema.sdl()).unwrap(), Vec::new());
+}
+
+#[test]
+pub fn test_diff_from_sdl() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let changes = schema
+        .diff_from_sdl(
+            r#"
+            enum Status {
+                ACTIVE
+                DELETED
+            }
+
+            input ProductFilter {
+                status: Status
+                limit: Int! = 20
+            }
+
+            type Product {
+                name: String!
+                price: Int!
+                status: Status!
+                sku: String!
+            }
+
+            type Query {
+                products(filter: ProductFilter, first: Int!, last: Int): [Product!]!
+            }
+            "#,
+        )
+        .unwrap();
+
+    assert_eq!(
+        changes
+            .iter()
+            .map(|change| (change.severity, change.kind, change.coordinate.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                ChangeSeverity::Breaking,
+                ChangeKind::EnumValueRemoved,
+                "Status.DELETED"
+            ),
+            (
+                ChangeSeverity::Dangerous,
+                ChangeKind::EnumValueAdded,
+                "Status.ARCHIVED"
+            ),
+            (
+                ChangeSeverity::Dangerous,
+                ChangeKind::InputFieldDefaultValueChanged,
+                "ProductFilter.limit"
+            ),
+            (
+                ChangeSeverity::Breaking,
+                ChangeKind::FieldTypeChanged,
+                "Product.price"
+            ),
+            (
+                ChangeSeverity::Breaking,
+                ChangeKind::FieldRemoved,
+                "Product.sku"
+            ),
+            (
+                ChangeSeverity::Dangerous,
+                ChangeKind::InterfaceAdded,
+                "Product"
+            ),
+            (
+                ChangeSeverity::Breaking,
+                ChangeKind::ArgumentRemoved,
+                "Query.products(last:)"
+            ),
+            (ChangeSeverity::Safe, ChangeKind::FieldAdded, "Query.node"),
+            (ChangeSeverity::Safe, ChangeKind::TypeAdded, "Node"),
+        ]
+    );
+    assert_eq!(
+        changes[3].to_string(),
+        r#"BREAKING: Field "Product.price" changed type from "Int!" to "Int"."#
+    );
+}
+
+#[test]
+pub fn test_diff_sdl_syntax_error() {
+    assert!(diff::diff_sdl("type Query {", "type Query { a: Int }").is_err());
+}
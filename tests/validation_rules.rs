@@ -0,0 +1,164 @@
+use async_graphql::parser::types::{Field, FragmentSpread, OperationDefinition, OperationType};
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    #[graphql(deprecation = "use `value` instead")]
+    async fn old_value(&self) -> i32 {
+        10
+    }
+}
+
+struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn action(&self) -> bool {
+        true
+    }
+}
+
+struct MutationsMustBeNamed;
+
+impl<'a> Visitor<'a> for MutationsMustBeNamed {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: Option<&'a Name>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        if operation_definition.node.ty == OperationType::Mutation && name.is_none() {
+            ctx.report_error(
+                vec![operation_definition.pos],
+                "Mutations must have an operation name.",
+            );
+        }
+    }
+}
+
+struct NoDeprecatedFields;
+
+impl<'a> Visitor<'a> for NoDeprecatedFields {
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        let deprecated = ctx
+            .parent_type()
+            .and_then(|ty| ty.field_by_name(&field.node.name.node))
+            .map_or(false, |field| field.deprecation.is_some());
+        if deprecated {
+            ctx.report_error(
+                vec![field.pos],
+                format!(r#"Field "{}" is deprecated."#, field.node.name.node),
+            );
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_custom_validation_rules() {
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .validation_rule(|| MutationsMustBeNamed)
+        .validation_rule(|| NoDeprecatedFields)
+        .finish();
+
+    assert!(schema.execute("mutation A { action }").await.is_ok());
+    assert_eq!(
+        schema
+            .execute("mutation { action }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Mutations must have an operation name.".to_owned(),
+            locations: vec![Pos { line: 1, column: 1 }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+
+    assert!(schema.execute("{ value }").await.is_ok());
+    assert_eq!(
+        schema
+            .execute("{ value oldValue }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Field "oldValue" is deprecated."#.to_owned(),
+            locations: vec![Pos { line: 1, column: 9 }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+}
+
+#[async_std::test]
+pub async fn test_disable_validation_rule() {
+    let query = "query($a: Int) { value }";
+
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    assert!(schema.execute(query).await.is_err());
+
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .disable_validation_rule(BuiltinRule::NoUnusedVariables)
+        .finish();
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({ "value": 10 })
+    );
+}
+
+struct MaxFragmentSpreads {
+    spreads: usize,
+}
+
+impl<'a> Visitor<'a> for MaxFragmentSpreads {
+    fn mode(&self) -> VisitMode {
+        VisitMode::Inline
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_spread: &'a Positioned<FragmentSpread>,
+    ) {
+        self.spreads += 1;
+        if self.spreads > 2 {
+            ctx.report_error(vec![fragment_spread.pos], "Too many fragment spreads.");
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_inline_validation_rule() {
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .validation_rule(|| MaxFragmentSpreads { spreads: 0 })
+        .finish();
+
+    assert!(schema
+        .execute("fragment A on Query { value } { ...A ...A }")
+        .await
+        .is_ok());
+    assert_eq!(
+        schema
+            .execute("fragment A on Query { value } fragment B on Query { ...A ...A } { ...B }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Too many fragment spreads."
+    );
+
+    let errors = schema
+        .execute("fragment A on Query { value ...A } { ...A }")
+        .await
+        .into_result()
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, r#"Cannot spread fragment "A""#);
+}
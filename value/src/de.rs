@@ -1,8 +1,8 @@
-use std::collections::BTreeMap;
 use std::{fmt, vec};
 
 use crate::{ConstValue, Name};
 
+use indexmap::IndexMap;
 use serde::de::{
     self, Deserialize, DeserializeOwned, DeserializeSeed, EnumAccess, Error as DeError,
     IntoDeserializer, MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
@@ -72,7 +72,7 @@ where
 }
 
 fn visit_object<'de, V>(
-    object: BTreeMap<Name, ConstValue>,
+    object: IndexMap<Name, ConstValue>,
     visitor: V,
 ) -> Result<V::Value, DeserializerError>
 where
@@ -346,12 +346,12 @@ impl<'de> SeqAccess<'de> for SeqDeserializer {
 }
 
 struct MapDeserializer {
-    iter: <BTreeMap<Name, ConstValue> as IntoIterator>::IntoIter,
+    iter: <IndexMap<Name, ConstValue> as IntoIterator>::IntoIter,
     value: Option<ConstValue>,
 }
 
 impl MapDeserializer {
-    fn new(map: BTreeMap<Name, ConstValue>) -> Self {
+    fn new(map: IndexMap<Name, ConstValue>) -> Self {
         MapDeserializer {
             iter: map.into_iter(),
             value: None,
@@ -19,9 +19,12 @@ use serde::ser::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 pub use de::{from_value, DeserializerError};
+pub use indexmap;
 pub use ser::{to_value, SerializerError};
 pub use serde_json::Number;
 
+use indexmap::IndexMap;
+
 /// A GraphQL name.
 ///
 /// [Reference](https://spec.graphql.org/June2018/#Name).
@@ -134,8 +137,8 @@ pub enum ConstValue {
     Enum(Name),
     /// A list of values.
     List(Vec<ConstValue>),
-    /// An object. This is a map of keys to values.
-    Object(BTreeMap<Name, ConstValue>),
+    /// An object. This is a map of keys to values, in the order they were inserted.
+    Object(IndexMap<Name, ConstValue>),
 }
 
 impl PartialEq for ConstValue {
@@ -249,9 +252,15 @@ impl<T: Into<ConstValue>> From<Vec<T>> for ConstValue {
     }
 }
 
+impl From<IndexMap<Name, ConstValue>> for ConstValue {
+    fn from(f: IndexMap<Name, ConstValue>) -> Self {
+        ConstValue::Object(f)
+    }
+}
+
 impl From<BTreeMap<Name, ConstValue>> for ConstValue {
     fn from(f: BTreeMap<Name, ConstValue>) -> Self {
-        ConstValue::Object(f)
+        ConstValue::Object(f.into_iter().collect())
     }
 }
 
@@ -357,8 +366,8 @@ pub enum Value {
     Enum(Name),
     /// A list of values.
     List(Vec<Value>),
-    /// An object. This is a map of keys to values.
-    Object(BTreeMap<Name, Value>),
+    /// An object. This is a map of keys to values, in the order they were inserted.
+    Object(IndexMap<Name, Value>),
 }
 
 impl Value {
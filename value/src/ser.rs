@@ -1,7 +1,7 @@
-use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
 
+use indexmap::IndexMap;
 use serde::ser::{self, Impossible};
 use serde::Serialize;
 
@@ -158,7 +158,7 @@ impl ser::Serializer for Serializer {
         T: ser::Serialize,
     {
         value.serialize(self).map(|v| {
-            let mut map = BTreeMap::new();
+            let mut map = IndexMap::new();
             map.insert(Name::new(variant), v);
             ConstValue::Object(map)
         })
@@ -195,7 +195,7 @@ impl ser::Serializer for Serializer {
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         Ok(SerializeMap {
-            map: BTreeMap::new(),
+            map: IndexMap::new(),
             key: None,
         })
     }
@@ -205,7 +205,7 @@ impl ser::Serializer for Serializer {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Ok(SerializeStruct(BTreeMap::new()))
+        Ok(SerializeStruct(IndexMap::new()))
     }
 
     fn serialize_struct_variant(
@@ -215,7 +215,7 @@ impl ser::Serializer for Serializer {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        Ok(SerializeStructVariant(Name::new(variant), BTreeMap::new()))
+        Ok(SerializeStructVariant(Name::new(variant), IndexMap::new()))
     }
 }
 
@@ -295,14 +295,14 @@ impl ser::SerializeTupleVariant for SerializeTupleVariant {
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         map.insert(self.0, ConstValue::List(self.1));
         Ok(ConstValue::Object(map))
     }
 }
 
 struct SerializeMap {
-    map: BTreeMap<Name, ConstValue>,
+    map: IndexMap<Name, ConstValue>,
     key: Option<Name>,
 }
 
@@ -333,7 +333,7 @@ impl ser::SerializeMap for SerializeMap {
     }
 }
 
-struct SerializeStruct(BTreeMap<Name, ConstValue>);
+struct SerializeStruct(IndexMap<Name, ConstValue>);
 
 impl ser::SerializeStruct for SerializeStruct {
     type Ok = ConstValue;
@@ -358,7 +358,7 @@ impl ser::SerializeStruct for SerializeStruct {
     }
 }
 
-struct SerializeStructVariant(Name, BTreeMap<Name, ConstValue>);
+struct SerializeStructVariant(Name, IndexMap<Name, ConstValue>);
 
 impl ser::SerializeStructVariant for SerializeStructVariant {
     type Ok = ConstValue;
@@ -379,7 +379,7 @@ impl ser::SerializeStructVariant for SerializeStructVariant {
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         map.insert(self.0, ConstValue::Object(self.1));
         Ok(ConstValue::Object(map))
     }